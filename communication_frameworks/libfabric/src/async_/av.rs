@@ -1,12 +1,16 @@
 use super::eq::{AsyncReadEq, EventQueue};
 use crate::{
-    av::{AddressVectorAttr, AddressVectorBase, AddressVectorImplBase, NoBlock},
+    av::{
+        insert_results, AddressVectorAttr, AddressVectorBase, AddressVectorImplBase,
+        AddressVectorImplT, NoBlock,
+    },
     domain::DomainBase,
     enums::AVOptions,
     ep::Address,
     eq::Event,
+    error::ErrorKind,
     fid::{AsRawFid, AsRawTypedFid, AsTypedFid, Fid},
-    Context, MappedAddress, MyRc, RawMappedAddress, SyncSend,
+    Context, MappedAddress, MyRc, SyncSend,
 };
 
 pub(crate) type AsyncAddressVectorImpl = AddressVectorImplBase<dyn AsyncReadEq>;
@@ -17,7 +21,7 @@ impl AsyncAddressVectorImpl {
         addr: &[Address],
         flags: u64,
         ctx: &mut Context,
-    ) -> Result<(Event, Vec<u64>), crate::error::Error> {
+    ) -> Result<(Event, Vec<u64>, Vec<i32>), crate::error::Error> {
         let mut fi_addresses = vec![0u64; addr.len()];
         let total_size = addr.iter().fold(0, |acc, addr| acc + addr.as_bytes().len());
         let mut serialized: Vec<u8> = Vec::with_capacity(total_size);
@@ -47,19 +51,31 @@ impl AsyncAddressVectorImpl {
                 panic!("Calling insert_async on unbound AV");
             };
 
-            let res = eq
-                .async_event_wait(
-                    libfabric_sys::FI_AV_COMPLETE,
-                    Fid(self.as_typed_fid().as_raw_fid() as usize),
-                    Some(ctx),
-                    None,
-                )
-                .await?;
-
-            if let Event::AVComplete(ref entry) = res {
-                fi_addresses.truncate(entry.data() as usize);
+            // Each address that failed is reported with an error entry, carrying its index as data,
+            // before the completion of the whole insertion
+            let mut statuses = vec![0i32; addr.len()];
+            loop {
+                let res = eq
+                    .async_event_wait(
+                        libfabric_sys::FI_AV_COMPLETE,
+                        Fid(self.as_typed_fid().as_raw_fid() as usize),
+                        Some(&mut *ctx),
+                        None,
+                    )
+                    .await;
+
+                match res {
+                    Ok(event) => return Ok((event, fi_addresses, statuses)),
+                    Err(error) => match error.kind {
+                        ErrorKind::ErrorInEventQueue(ref err)
+                            if (err.data() as usize) < statuses.len() =>
+                        {
+                            statuses[err.data() as usize] = err.error().c_err as i32;
+                        }
+                        _ => return Err(error),
+                    },
+                }
             }
-            Ok((res, fi_addresses))
         }
     }
 }
@@ -67,26 +83,22 @@ impl AsyncAddressVectorImpl {
 pub type AddressVector = AddressVectorBase<NoBlock, dyn AsyncReadEq>;
 
 impl AddressVector {
+    /// Insert the `addr` [Address]es into the [AddressVector] and wait for the completion event.
+    ///
+    /// Returns the completion event along with the result of the insertion of each input address, at the
+    /// respective index. Addresses that the provider failed to insert are reported with the error carried by the
+    /// corresponding error entry of the [EventQueue].
     pub async fn insert_async(
         &self,
         addr: &[Address],
         options: AVOptions,
         ctx: &mut Context,
-    ) -> Result<(Event, Vec<MappedAddress>), crate::error::Error> {
+    ) -> Result<(Event, Vec<Result<MappedAddress, crate::error::Error>>), crate::error::Error> {
         // [TODO] as_raw_typed_fid async
-        let (event, fi_addresses) = self.inner.insert_async(addr, options.as_raw(), ctx).await?;
-        Ok((
-            event,
-            fi_addresses
-                .into_iter()
-                .map(|fi_addr| {
-                    MappedAddress::from_raw_addr(
-                        RawMappedAddress::from_raw(self.inner.type_, fi_addr),
-                        crate::AddressSource::Av(self.inner.clone()),
-                    )
-                })
-                .collect::<Vec<_>>(),
-        ))
+        let (event, fi_addresses, statuses) =
+            self.inner.insert_async(addr, options.as_raw(), ctx).await?;
+        let av: MyRc<dyn AddressVectorImplT> = self.inner.clone();
+        Ok((event, insert_results(&av, fi_addresses, &statuses)))
     }
 }
 
@@ -0,0 +1,170 @@
+use crate::{
+    cq::SingleCompletion,
+    mr::{MemoryRegion, MemoryRegionDesc},
+    Context,
+};
+
+/// The output of an owned-buffer asynchronous operation.
+///
+/// Along with the result of the operation, the buffer and the [Context] that were moved into the operation are
+/// handed back to the caller so that they can be reused.
+pub type BufResult<B> = (Result<SingleCompletion, crate::error::Error>, B, Context);
+
+/// Holds the buffer and the [Context] of an owned-buffer operation while it is in flight.
+///
+/// If the operation is dropped and its cancellation could not be confirmed (see [Context::leak]), the
+/// buffer is leaked instead of being released while the provider might still access it.
+pub(crate) struct InFlight<B> {
+    buf: Option<B>,
+    ctx: Option<Context>,
+}
+
+impl<B> InFlight<B> {
+    pub(crate) fn new(buf: B, mut ctx: Context) -> Self {
+        // Forget about the operations the context was previously given up to
+        ctx.reset();
+        Self {
+            buf: Some(buf),
+            ctx: Some(ctx),
+        }
+    }
+
+    pub(crate) fn parts_mut(&mut self) -> (&mut B, &mut Context) {
+        match (&mut self.buf, &mut self.ctx) {
+            (Some(buf), Some(ctx)) => (buf, ctx),
+            _ => unreachable!("Buffer and context are only taken once the operation is settled"),
+        }
+    }
+
+    /// Hands the buffer and the context back along with the result of the settled operation.
+    pub(crate) fn finish(
+        mut self,
+        res: Result<SingleCompletion, crate::error::Error>,
+    ) -> BufResult<B> {
+        match (self.buf.take(), self.ctx.take()) {
+            (Some(buf), Some(ctx)) => (res, buf, ctx),
+            _ => unreachable!("Buffer and context are only taken once the operation is settled"),
+        }
+    }
+}
+
+impl<B> Drop for InFlight<B> {
+    fn drop(&mut self) {
+        if let (Some(buf), Some(ctx)) = (self.buf.take(), &self.ctx) {
+            if ctx.abandoned() {
+                std::mem::forget(buf);
+            }
+        }
+    }
+}
+
+/// A buffer that can be moved into an owned-buffer asynchronous operation
+/// (e.g., [crate::async_::comm::message::AsyncSendEpOwned::send_to_owned]).
+///
+/// Since the operation owns the buffer until it completes, the returned future does not borrow from the caller
+/// and can be spawned onto a thread-local executor (the future itself is not [Send]).
+pub trait IoBuf: 'static {
+    type Item;
+
+    /// Returns the memory that the operation will read from.
+    fn as_slice(&self) -> &[Self::Item];
+
+    /// Returns the descriptor of the memory region that the buffer is registered with, if any.
+    fn desc(&self) -> Option<MemoryRegionDesc<'_>> {
+        None
+    }
+}
+
+/// An [IoBuf] that operations can also write to (e.g., receives and RMA reads).
+pub trait IoBufMut: IoBuf {
+    /// Returns the memory that the operation will write to.
+    fn as_mut_slice(&mut self) -> &mut [Self::Item];
+
+    /// Same as [IoBufMut::as_mut_slice] but also returns the descriptor of the buffer, if any.
+    fn as_mut_slice_desc(&mut self) -> (&mut [Self::Item], Option<MemoryRegionDesc<'_>>) {
+        (self.as_mut_slice(), None)
+    }
+}
+
+impl<T: 'static> IoBuf for Vec<T> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: 'static> IoBufMut for Vec<T> {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: 'static> IoBuf for Box<[T]> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: 'static> IoBufMut for Box<[T]> {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+/// A buffer along with the [MemoryRegion] it has been registered with.
+///
+/// The descriptor of the memory region is passed to the operations the buffer is moved into, as required
+/// by providers that set `FI_MR_LOCAL`.
+pub struct RegisteredBuf<B> {
+    buf: B,
+    mr: MemoryRegion,
+}
+
+impl<B: IoBuf> RegisteredBuf<B> {
+    /// Pairs `buf` with the memory region `mr`, which is expected to cover the whole buffer.
+    pub fn new(buf: B, mr: MemoryRegion) -> Self {
+        Self { buf, mr }
+    }
+
+    pub fn buf(&self) -> &B {
+        &self.buf
+    }
+
+    pub fn buf_mut(&mut self) -> &mut B {
+        &mut self.buf
+    }
+
+    pub fn mr(&self) -> &MemoryRegion {
+        &self.mr
+    }
+
+    /// Splits the registered buffer back into the buffer and its memory region.
+    pub fn into_parts(self) -> (B, MemoryRegion) {
+        (self.buf, self.mr)
+    }
+}
+
+impl<B: IoBuf> IoBuf for RegisteredBuf<B> {
+    type Item = B::Item;
+
+    fn as_slice(&self) -> &[Self::Item] {
+        self.buf.as_slice()
+    }
+
+    fn desc(&self) -> Option<MemoryRegionDesc<'_>> {
+        Some(self.mr.descriptor())
+    }
+}
+
+impl<B: IoBufMut> IoBufMut for RegisteredBuf<B> {
+    fn as_mut_slice(&mut self) -> &mut [Self::Item] {
+        self.buf.as_mut_slice()
+    }
+
+    fn as_mut_slice_desc(&mut self) -> (&mut [Self::Item], Option<MemoryRegionDesc<'_>>) {
+        (self.buf.as_mut_slice(), Some(self.mr.descriptor()))
+    }
+}
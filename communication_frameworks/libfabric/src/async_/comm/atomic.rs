@@ -1,5 +1,6 @@
 use crate::enums::AtomicOp;
 use crate::async_::cq::AsyncCq;
+use crate::async_::ctx_pool::acquire_from;
 use crate::async_::eq::AsyncReadEq;
 use crate::async_::xcontext::{TxContext, TxContextImpl};
 // use crate::async_::xcontext::{TxContext, TxContextImpl};
@@ -44,7 +45,7 @@ pub(crate) trait AsyncAtomicWriteEpImpl: AtomicWriteEpImpl + AsyncTxEp {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -87,7 +88,7 @@ pub(crate) trait AsyncAtomicWriteEpImpl: AtomicWriteEpImpl + AsyncTxEp {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn atomicmsg_async_impl<T: AsFiType>(
@@ -114,7 +115,7 @@ pub(crate) trait AsyncAtomicWriteEpImpl: AtomicWriteEpImpl + AsyncTxEp {
             Either::Right(msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -1542,7 +1543,7 @@ pub(crate) trait AsyncAtomicFetchEpImpl: AtomicFetchEpImpl + AsyncTxEp {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -1573,7 +1574,7 @@ pub(crate) trait AsyncAtomicFetchEpImpl: AtomicFetchEpImpl + AsyncTxEp {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn fetch_atomicmsg_async_impl<T: AsFiType>(
@@ -1602,7 +1603,7 @@ pub(crate) trait AsyncAtomicFetchEpImpl: AtomicFetchEpImpl + AsyncTxEp {
             Either::Right(msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -2368,7 +2369,7 @@ pub(crate) trait AsyncAtomicCASImpl: AtomicCASImpl + AsyncTxEp {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -2403,7 +2404,7 @@ pub(crate) trait AsyncAtomicCASImpl: AtomicCASImpl + AsyncTxEp {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -2442,7 +2443,7 @@ pub(crate) trait AsyncAtomicCASImpl: AtomicCASImpl + AsyncTxEp {
             Either::Right(msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -3182,3 +3183,289 @@ pub trait ConnectedAsyncAtomicCASRemoteMemAddrSliceEp: ConnectedAsyncAtomicCASEp
 }
 
 impl<EP: ConnectedAsyncAtomicCASEp> ConnectedAsyncAtomicCASRemoteMemAddrSliceEp for EP {}
+
+/// Versions of the [AsyncAtomicWriteEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncAtomicWriteEpPooled {
+    /// Pooled version of [AsyncAtomicWriteEp::atomic_to_async]
+    /// # Safety
+    /// See [AsyncAtomicWriteEp::atomic_to_async]
+    unsafe fn atomic_to_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &crate::MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: AtomicOp,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncAtomicWriteEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncAtomicWriteEpPooled {
+    /// Pooled version of [ConnectedAsyncAtomicWriteEp::atomic_async]
+    /// # Safety
+    /// See [ConnectedAsyncAtomicWriteEp::atomic_async]
+    unsafe fn atomic_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: AtomicOp,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [AsyncAtomicFetchEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncAtomicFetchEpPooled {
+    /// Pooled version of [AsyncAtomicFetchEp::fetch_atomic_from_async]
+    /// # Safety
+    /// See [AsyncAtomicFetchEp::fetch_atomic_from_async]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn fetch_atomic_from_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        res: &mut [T],
+        res_desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &crate::MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::FetchAtomicOp,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncAtomicFetchEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncAtomicFetchEpPooled {
+    /// Pooled version of [ConnectedAsyncAtomicFetchEp::fetch_atomic_async]
+    /// # Safety
+    /// See [ConnectedAsyncAtomicFetchEp::fetch_atomic_async]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn fetch_atomic_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        res: &mut [T],
+        res_desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::FetchAtomicOp,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [AsyncAtomicCASEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncAtomicCASEpPooled {
+    /// Pooled version of the [AsyncAtomicCASEp] operations, performing the comparison `op`
+    /// # Safety
+    /// See [AsyncAtomicCASEp::compare_atomic_swap_to_async]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn compare_atomic_to_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        compare: &[T],
+        compare_desc: Option<MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &crate::MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::CompareAtomicOp,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncAtomicCASEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncAtomicCASEpPooled {
+    /// Pooled version of the [ConnectedAsyncAtomicCASEp] operations, performing the comparison `op`
+    /// # Safety
+    /// See [ConnectedAsyncAtomicCASEp::compare_atomic_swap_async]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn compare_atomic_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        compare: &[T],
+        compare_desc: Option<MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::CompareAtomicOp,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+impl<EP: AsyncAtomicWriteEpImpl + ConnlessEp> AsyncAtomicWriteEpPooled for EP {
+    async unsafe fn atomic_to_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &crate::MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: AtomicOp,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.atomic_async_impl(
+            buf,
+            desc,
+            Some(dest_addr),
+            mem_addr,
+            mapped_key,
+            &mut ctx,
+            op,
+        )
+        .await
+    }
+}
+
+impl<EP: AsyncAtomicWriteEpImpl + ConnectedEp> ConnectedAsyncAtomicWriteEpPooled for EP {
+    async unsafe fn atomic_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: AtomicOp,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.atomic_async_impl(buf, desc, None, mem_addr, mapped_key, &mut ctx, op)
+            .await
+    }
+}
+
+impl<EP: AsyncAtomicFetchEpImpl + ConnlessEp> AsyncAtomicFetchEpPooled for EP {
+    #[allow(clippy::too_many_arguments)]
+    async unsafe fn fetch_atomic_from_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        res: &mut [T],
+        res_desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &crate::MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::FetchAtomicOp,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.fetch_atomic_async_impl(
+            buf,
+            desc,
+            res,
+            res_desc,
+            Some(dest_addr),
+            mem_addr,
+            mapped_key,
+            &mut ctx,
+            op,
+        )
+        .await
+    }
+}
+
+impl<EP: AsyncAtomicFetchEpImpl + ConnectedEp> ConnectedAsyncAtomicFetchEpPooled for EP {
+    #[allow(clippy::too_many_arguments)]
+    async unsafe fn fetch_atomic_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        res: &mut [T],
+        res_desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::FetchAtomicOp,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.fetch_atomic_async_impl(
+            buf, desc, res, res_desc, None, mem_addr, mapped_key, &mut ctx, op,
+        )
+        .await
+    }
+}
+
+impl<EP: AsyncAtomicCASImpl + ConnlessEp> AsyncAtomicCASEpPooled for EP {
+    #[allow(clippy::too_many_arguments)]
+    async unsafe fn compare_atomic_to_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        compare: &[T],
+        compare_desc: Option<MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &crate::MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::CompareAtomicOp,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.compare_atomic_async_impl(
+            buf,
+            desc,
+            compare,
+            compare_desc,
+            result,
+            result_desc,
+            Some(dest_addr),
+            mem_addr,
+            mapped_key,
+            &mut ctx,
+            op,
+        )
+        .await
+    }
+}
+
+impl<EP: AsyncAtomicCASImpl + ConnectedEp> ConnectedAsyncAtomicCASEpPooled for EP {
+    #[allow(clippy::too_many_arguments)]
+    async unsafe fn compare_atomic_pooled_async<T: AsFiType, RT: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        compare: &[T],
+        compare_desc: Option<MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        op: crate::enums::CompareAtomicOp,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.compare_atomic_async_impl(
+            buf,
+            desc,
+            compare,
+            compare_desc,
+            result,
+            result_desc,
+            None,
+            mem_addr,
+            mapped_key,
+            &mut ctx,
+            op,
+        )
+        .await
+    }
+}
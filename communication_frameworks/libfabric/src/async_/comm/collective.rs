@@ -1,12 +1,13 @@
 use crate::{
     async_::{
         cq::AsyncCq,
+        ctx_pool::acquire_from,
         ep::{AsyncCmEp, AsyncTxEp},
         eq::AsyncReadEq,
     },
     comm::collective::{CollectiveEp, CollectiveEpImpl},
     cq::SingleCompletion,
-    enums::CollectiveOptions,
+    enums::{CollectiveOptions, TriggerEvent},
     ep::{Connected, Connectionless, EndpointBase, EndpointImplBase},
     infocapsoptions::CollCap,
     mcast::MultiCastGroup,
@@ -75,7 +76,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn broadcast_impl_async<T: AsFiType>(
@@ -100,7 +101,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -128,7 +129,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -158,7 +159,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -185,7 +186,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -215,7 +216,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -247,7 +248,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -277,7 +278,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -307,7 +308,7 @@ trait AsyncCollectiveEpImpl: AsyncTxEp + CollectiveEpImpl {
         })
         .await?;
         // crate::async_::cq::AsyncTransferCq::new(cq, &mut async_ctx as *mut AsyncCtx as usize).await
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -323,8 +324,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         options: CollectiveOptions,
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
-    // fn barrier_triggered_async<T>(&self, mc_group: &MultiCastGroup, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> ;
-    // fn barrier_triggered_with_options_async<T>(&self, mc_group: &MultiCastGroup, options: CollectiveOptions, context : &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> ;
     fn broadcast_async<T: AsFiType>(
         &self,
         buf: &mut [T],
@@ -334,7 +333,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         options: CollectiveOptions,
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
-    // fn broadcast_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, root_mapped_addr: &crate::MappedAddress, options: CollectiveOptions, context : &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
     #[allow(clippy::too_many_arguments)]
     fn alltoall_async<T: AsFiType>(
         &self,
@@ -347,8 +345,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 
-    // #[allow(clippy::too_many_arguments)]
-    // fn alltoall_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, result: &mut [T], result_desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, options: CollectiveOptions, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
     #[allow(clippy::too_many_arguments)]
     fn allreduce_async<T: AsFiType>(
         &self,
@@ -362,8 +358,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 
-    // #[allow(clippy::too_many_arguments)]
-    // fn allreduce_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, result: &mut [T], result_desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, op: crate::enums::ReduceOp,  options: CollectiveOptions, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
     #[allow(clippy::too_many_arguments)]
     fn allgather_async<T: AsFiType>(
         &self,
@@ -376,8 +370,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 
-    // #[allow(clippy::too_many_arguments)]
-    // fn allgather_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, result: &mut [T], result_desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, options: CollectiveOptions, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
     #[allow(clippy::too_many_arguments)]
     fn reduce_scatter_async<T: AsFiType>(
         &self,
@@ -391,7 +383,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 
-    // fn reduce_scatter_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, result: &mut [T], result_desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, op: crate::enums::ReduceOp,  options: CollectiveOptions, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
     #[allow(clippy::too_many_arguments)]
     fn reduce_async<T: AsFiType>(
         &self,
@@ -406,7 +397,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 
-    // fn reduce_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, result: &mut [T], result_desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, root_mapped_addr: &crate::MappedAddress,op: crate::enums::ReduceOp,  options: CollectiveOptions, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
     #[allow(clippy::too_many_arguments)]
     fn scatter_async<T: AsFiType>(
         &self,
@@ -420,7 +410,6 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 
-    // fn scatter_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, result: &mut [T], result_desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, root_mapped_addr: &crate::MappedAddress, options: CollectiveOptions, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
     #[allow(clippy::too_many_arguments)]
     fn gather_async<T: AsFiType>(
         &self,
@@ -434,7 +423,139 @@ pub trait AsyncCollectiveEp: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
         ctx: &mut Context,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 
-    // fn gather_triggered_async<T: AsFiType, T0>(&self, buf: &mut [T], desc: Option<& impl DataDescriptor, result: &mut [T], result_desc: Option<& impl DataDescriptor, mc_group: &MultiCastGroup, root_mapped_addr: &crate::MappedAddress, options: CollectiveOptions, context: &mut TriggeredContext) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error:BorrowedMemoryRegionDesc<'_>>;
+    /// Same as [AsyncCollectiveEp::barrier_async] but the operation is deferred until `event` occurs.
+    fn barrier_triggered_async(
+        &self,
+        mc_group: &MultiCastGroup,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::barrier_with_options_async] but the operation is deferred until `event` occurs.
+    fn barrier_triggered_with_options_async(
+        &self,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::broadcast_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn broadcast_triggered_async<T: AsFiType>(
+        &self,
+        buf: &mut [T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::alltoall_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn alltoall_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::allreduce_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn allreduce_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::allgather_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn allgather_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::reduce_scatter_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_scatter_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::reduce_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::scatter_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn scatter_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncCollectiveEp::gather_async] but the operation is deferred until `event` occurs.
+    #[allow(clippy::too_many_arguments)]
+    fn gather_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 // impl<E, EQ: ?Sized + AsyncReadEq,  CQ: ?Sized + AsyncReadCq> EndpointBase<E, EQ, CQ> {
@@ -608,4 +729,490 @@ impl<EP: AsyncCollectiveEpImpl + AsyncTxEp + AsyncCmEp + SyncSend> AsyncCollecti
             ctx,
         )
     }
+
+    async fn barrier_triggered_async(
+        &self,
+        mc_group: &MultiCastGroup,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.barrier_impl_async(mc_group, None, ctx).await
+    }
+
+    async fn barrier_triggered_with_options_async(
+        &self,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.barrier_impl_async(mc_group, Some(options), ctx)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn broadcast_triggered_async<T: AsFiType>(
+        &self,
+        buf: &mut [T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.broadcast_impl_async(buf, desc, mc_group, Some(root_mapped_addr), options, ctx)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn alltoall_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.alltoall_impl_async(buf, desc, result, result_desc, mc_group, options, ctx)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn allreduce_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.allreduce_impl_async(buf, desc, result, result_desc, mc_group, op, options, ctx)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn allgather_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.allgather_impl_async(buf, desc, result, result_desc, mc_group, options, ctx)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn reduce_scatter_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.reduce_scatter_impl_async(buf, desc, result, result_desc, mc_group, op, options, ctx)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn reduce_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.reduce_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            Some(root_mapped_addr),
+            op,
+            options,
+            ctx,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn scatter_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.scatter_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            Some(root_mapped_addr),
+            options,
+            ctx,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn gather_triggered_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.gather_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            Some(root_mapped_addr),
+            options,
+            ctx,
+        )
+        .await
+    }
+}
+
+/// Versions of the [AsyncCollectiveEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncCollectiveEpPooled: CollectiveEp + AsyncTxEp + AsyncCmEp + SyncSend {
+    /// Pooled version of [AsyncCollectiveEp::barrier_async]
+    fn barrier_pooled_async(
+        &self,
+        mc_group: &MultiCastGroup,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::barrier_with_options_async]
+    fn barrier_with_options_pooled_async(
+        &self,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::broadcast_async]
+    fn broadcast_pooled_async<T: AsFiType>(
+        &self,
+        buf: &mut [T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::alltoall_async]
+    fn alltoall_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::allreduce_async]
+    #[allow(clippy::too_many_arguments)]
+    fn allreduce_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::allgather_async]
+    fn allgather_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::reduce_scatter_async]
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_scatter_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::reduce_async]
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::scatter_async]
+    #[allow(clippy::too_many_arguments)]
+    fn scatter_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Pooled version of [AsyncCollectiveEp::gather_async]
+    #[allow(clippy::too_many_arguments)]
+    fn gather_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+impl<EP: AsyncCollectiveEpImpl + AsyncTxEp + AsyncCmEp + SyncSend> AsyncCollectiveEpPooled for EP {
+    async fn barrier_pooled_async(
+        &self,
+        mc_group: &MultiCastGroup,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.barrier_impl_async(mc_group, None, &mut ctx).await
+    }
+    async fn barrier_with_options_pooled_async(
+        &self,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.barrier_impl_async(mc_group, Some(options), &mut ctx)
+            .await
+    }
+    async fn broadcast_pooled_async<T: AsFiType>(
+        &self,
+        buf: &mut [T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.broadcast_impl_async(
+            buf,
+            desc,
+            mc_group,
+            Some(root_mapped_addr),
+            options,
+            &mut ctx,
+        )
+        .await
+    }
+    async fn alltoall_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.alltoall_impl_async(buf, desc, result, result_desc, mc_group, options, &mut ctx)
+            .await
+    }
+    #[allow(clippy::too_many_arguments)]
+    async fn allreduce_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.allreduce_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            op,
+            options,
+            &mut ctx,
+        )
+        .await
+    }
+    async fn allgather_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.allgather_impl_async(buf, desc, result, result_desc, mc_group, options, &mut ctx)
+            .await
+    }
+    #[allow(clippy::too_many_arguments)]
+    async fn reduce_scatter_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.reduce_scatter_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            op,
+            options,
+            &mut ctx,
+        )
+        .await
+    }
+    #[allow(clippy::too_many_arguments)]
+    async fn reduce_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.reduce_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            Some(root_mapped_addr),
+            op,
+            options,
+            &mut ctx,
+        )
+        .await
+    }
+    #[allow(clippy::too_many_arguments)]
+    async fn scatter_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.scatter_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            Some(root_mapped_addr),
+            options,
+            &mut ctx,
+        )
+        .await
+    }
+    #[allow(clippy::too_many_arguments)]
+    async fn gather_pooled_async<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc<'_>>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc<'_>>,
+        mc_group: &MultiCastGroup,
+        root_mapped_addr: &crate::MappedAddress,
+        options: CollectiveOptions,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.gather_impl_async(
+            buf,
+            desc,
+            result,
+            result_desc,
+            mc_group,
+            Some(root_mapped_addr),
+            options,
+            &mut ctx,
+        )
+        .await
+    }
 }
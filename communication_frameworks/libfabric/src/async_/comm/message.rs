@@ -1,5 +1,9 @@
+use crate::async_::buf::{BufResult, InFlight, IoBuf, IoBufMut};
+use crate::async_::ctx_pool::acquire_from;
 use crate::async_::ep::{AsyncRxEp, AsyncTxEp};
-use crate::async_::xcontext::{RxContext, RxContextImpl, TxContext, TxContextImpl};
+use crate::async_::xcontext::{
+    RxContext, RxContextImpl, SharedRxContext, SharedRxContextImpl, TxContext, TxContextImpl,
+};
 // use crate::async_::xcontext::{RxContext, RxContextImpl, TxContext, TxContextImpl};
 use crate::comm::message::{
     RecvEpImpl, SendEpImpl,
@@ -10,6 +14,7 @@ use crate::ep::{Connected, Connectionless, EndpointImplBase, EpState};
 use crate::infocapsoptions::{MsgCap, RecvMod, SendMod};
 use crate::mr::{MemoryRegionDesc, MemoryRegionSlice, MemoryRegionSliceMut};
 use crate::utils::Either;
+use crate::enums::TriggerEvent;
 use crate::Context;
 use crate::{
     async_::{cq::AsyncCq, eq::AsyncReadEq},
@@ -29,12 +34,12 @@ pub(crate) trait AsyncRecvEpImpl: AsyncRxEp + RecvEpImpl {
         mapped_addr: Option<&MappedAddress>,
         ctx: &mut Context,
     ) -> Result<SingleCompletion, crate::error::Error> {
-        let cq = self.retrieve_rx_cq();
+        let cq = self.retrieve_rx_cq()?;
         while_try_again(cq.as_ref(), || {
             self.recv_impl(buf, desc, mapped_addr, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[inline]
@@ -45,12 +50,12 @@ pub(crate) trait AsyncRecvEpImpl: AsyncRxEp + RecvEpImpl {
         mapped_addr: Option<&MappedAddress>,
         ctx: &mut Context,
     ) -> Result<SingleCompletion, crate::error::Error> {
-        let cq = self.retrieve_rx_cq();
+        let cq = self.retrieve_rx_cq()?;
         while_try_again(cq.as_ref(), || {
             self.recvv_impl(iov, desc, mapped_addr, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn recvmsg_async_impl<'a>(
@@ -63,7 +68,7 @@ pub(crate) trait AsyncRecvEpImpl: AsyncRxEp + RecvEpImpl {
             Either::Right(msg) => Either::Right(&**msg),
         };
 
-        let cq = self.retrieve_rx_cq();
+        let cq = self.retrieve_rx_cq()?;
         while_try_again(cq.as_ref(), || {
             self.recvmsg_impl(imm_msg.to_owned(), options)
         })
@@ -74,7 +79,7 @@ pub(crate) trait AsyncRecvEpImpl: AsyncRxEp + RecvEpImpl {
             Either::Right(msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -104,6 +109,32 @@ pub trait AsyncRecvEp {
         msg: &mut crate::msg::MsgMut,
         options: RecvMsgOptions,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Same as [AsyncRecvEp::recv_from_async] but the receive is deferred until `event` occurs.
+    fn recv_from_triggered_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Same as [AsyncRecvEp::recv_from_any_async] but the receive is deferred until `event` occurs.
+    fn recv_from_any_triggered_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Same as [AsyncRecvEp::recvv_from_async] but the receive is deferred until `event` occurs.
+    fn recvv_from_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVecMut<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait AsyncRecvEpMrSlice: AsyncRecvEp {
@@ -125,6 +156,27 @@ pub trait AsyncRecvEpMrSlice: AsyncRecvEp {
         let desc = mr_slice.desc();
         self.recv_from_any_async(mr_slice.as_mut_slice(), Some(desc), ctx)
     }
+
+    fn recv_mr_slice_from_triggered_async<T>(
+        &self,
+        mr_slice: &mut MemoryRegionSliceMut,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
+        let desc = mr_slice.desc();
+        self.recv_from_triggered_async(mr_slice.as_mut_slice(), Some(desc), mapped_addr, event, ctx)
+    }
+
+    fn recv_mr_slice_from_any_triggered_async<T>(
+        &self,
+        mr_slice: &mut MemoryRegionSliceMut,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
+        let desc = mr_slice.desc();
+        self.recv_from_any_triggered_async(mr_slice.as_mut_slice(), Some(desc), event, ctx)
+    }
 }
 
 impl<EP: AsyncRecvEp> AsyncRecvEpMrSlice for EP {}
@@ -147,6 +199,22 @@ pub trait ConnectedAsyncRecvEp {
         msg: &mut crate::msg::MsgConnectedMut,
         options: RecvMsgOptions,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Same as [ConnectedAsyncRecvEp::recv_async] but the receive is deferred until `event` occurs.
+    fn recv_triggered_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    /// Same as [ConnectedAsyncRecvEp::recvv_async] but the receive is deferred until `event` occurs.
+    fn recvv_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVecMut<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait ConnectedAsyncRecvEpMrSlice: ConnectedAsyncRecvEp {
@@ -158,6 +226,16 @@ pub trait ConnectedAsyncRecvEpMrSlice: ConnectedAsyncRecvEp {
         let desc = mr_slice.desc();
         self.recv_async( mr_slice.as_mut_slice() , Some(desc), ctx)
     }
+
+    fn recv_mr_slice_triggered_async<T>(
+        &self,
+        mr_slice: &mut MemoryRegionSliceMut,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
+        let desc = mr_slice.desc();
+        self.recv_triggered_async(mr_slice.as_mut_slice(), Some(desc), event, ctx)
+    }
 }
 
 impl<EP: ConnectedAsyncRecvEp> ConnectedAsyncRecvEpMrSlice for EP {}
@@ -174,6 +252,10 @@ impl<I: MsgCap + RecvMod, STATE: EpState> AsyncRecvEpImpl for RxContextImpl<I, S
 
 impl<I: MsgCap + RecvMod, STATE: EpState> AsyncRecvEpImpl for RxContext<I, STATE> {}
 
+impl<I: MsgCap + RecvMod> AsyncRecvEpImpl for SharedRxContextImpl<I> {}
+
+impl<I: MsgCap + RecvMod> AsyncRecvEpImpl for SharedRxContext<I> {}
+
 impl<EP: AsyncRecvEpImpl + ConnlessEp> AsyncRecvEp for EP {
     fn recv_from_async<T>(
         &self,
@@ -211,6 +293,41 @@ impl<EP: AsyncRecvEpImpl + ConnlessEp> AsyncRecvEp for EP {
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
         self.recvmsg_async_impl(Either::Left(msg), options)
     }
+
+    async fn recv_from_triggered_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.recv_async_imp(buf, desc, Some(mapped_addr), ctx).await
+    }
+
+    async fn recv_from_any_triggered_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.recv_async_imp(buf, desc, None, ctx).await
+    }
+
+    async fn recvv_from_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVecMut<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.recvv_async_impl(iov, desc, Some(mapped_addr), ctx).await
+    }
 }
 
 impl<EP: AsyncRecvEpImpl + ConnectedEp> ConnectedAsyncRecvEp for EP {
@@ -239,6 +356,28 @@ impl<EP: AsyncRecvEpImpl + ConnectedEp> ConnectedAsyncRecvEp for EP {
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
         self.recvmsg_async_impl(Either::Right(msg), options)
     }
+
+    async fn recv_triggered_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.recv_async_imp(buf, desc, None, ctx).await
+    }
+
+    async fn recvv_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVecMut<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.recvv_async_impl(iov, desc, None, ctx).await
+    }
 }
 
 pub(crate) trait AsyncSendEpImpl: AsyncTxEp + SendEpImpl {
@@ -254,7 +393,7 @@ pub(crate) trait AsyncSendEpImpl: AsyncTxEp + SendEpImpl {
             self.send_impl(buf, desc, mapped_addr, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn inject_async_impl<T>(
@@ -278,7 +417,7 @@ pub(crate) trait AsyncSendEpImpl: AsyncTxEp + SendEpImpl {
             self.sendv_impl(iov, desc, mapped_addr, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn sendmsg_async_impl<'a>(
@@ -302,7 +441,7 @@ pub(crate) trait AsyncSendEpImpl: AsyncTxEp + SendEpImpl {
             Either::Right(msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn senddata_async_impl<T>(
@@ -318,7 +457,7 @@ pub(crate) trait AsyncSendEpImpl: AsyncTxEp + SendEpImpl {
             self.senddata_impl(buf, desc, data, mapped_addr, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn injectdata_async_impl<T>(
@@ -376,6 +515,37 @@ pub trait AsyncSendEp {
         data: u64,
         mapped_addr: &MappedAddress,
     ) -> impl std::future::Future<Output = Result<(), crate::error::Error>>;
+
+    /// Same as [AsyncSendEp::send_to_async] but the send is deferred until `event` occurs.
+    fn send_to_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncSendEp::sendv_to_async] but the send is deferred until `event` occurs.
+    fn sendv_to_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVec<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [AsyncSendEp::senddata_to_async] but the send is deferred until `event` occurs.
+    fn senddata_to_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait ConnectedAsyncSendEp {
@@ -417,6 +587,34 @@ pub trait ConnectedAsyncSendEp {
         buf: &[T],
         data: u64,
     ) -> impl std::future::Future<Output = Result<(), crate::error::Error>>;
+
+    /// Same as [ConnectedAsyncSendEp::send_async] but the send is deferred until `event` occurs.
+    fn send_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [ConnectedAsyncSendEp::sendv_async] but the send is deferred until `event` occurs.
+    fn sendv_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVec<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Same as [ConnectedAsyncSendEp::senddata_async] but the send is deferred until `event` occurs.
+    fn senddata_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait AsyncSendEpMrSlice: AsyncSendEp {
@@ -455,6 +653,27 @@ pub trait AsyncSendEpMrSlice: AsyncSendEp {
     ) -> impl std::future::Future<Output = Result<(), crate::error::Error>> {
         self.injectdata_to_async( mr_slice.as_slice() , data, mapped_addr)
     }
+
+    fn send_mr_slice_to_triggered_async<T>(
+        &self,
+        mr_slice: &MemoryRegionSlice,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
+        self.send_to_triggered_async(mr_slice.as_slice(), Some(mr_slice.desc()), mapped_addr, event, ctx)
+    }
+
+    fn senddata_mr_slice_to_triggered_async<T>(
+        &self,
+        mr_slice: &MemoryRegionSlice,
+        data: u64,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
+        self.senddata_to_triggered_async(mr_slice.as_slice(), Some(mr_slice.desc()), data, mapped_addr, event, ctx)
+    }
 }
 
 impl<EP: AsyncSendEp> AsyncSendEpMrSlice for EP {}
@@ -491,6 +710,25 @@ pub trait ConnectedAsyncSendEpMrSlice: ConnectedAsyncSendEp {
     ) -> impl std::future::Future<Output = Result<(), crate::error::Error>> {
         self.injectdata_async( mr_slice.as_slice() , data)
     }
+
+    fn send_mr_slice_triggered_async<T>(
+        &self,
+        mr_slice: &MemoryRegionSlice,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
+        self.send_triggered_async(mr_slice.as_slice(), Some(mr_slice.desc()), event, ctx)
+    }
+
+    fn senddata_mr_slice_triggered_async<T>(
+        &self,
+        mr_slice: &MemoryRegionSlice,
+        data: u64,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>> {
+        self.senddata_triggered_async(mr_slice.as_slice(), Some(mr_slice.desc()), data, event, ctx)
+    }
 }
 
 impl<EP: ConnectedAsyncSendEp> ConnectedAsyncSendEpMrSlice for EP {}
@@ -557,6 +795,46 @@ impl<EP: AsyncSendEpImpl + ConnlessEp> AsyncSendEp for EP {
         self.injectdata_async_impl(buf, data, Some(mapped_addr))
             .await
     }
+
+    async fn send_to_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.send_async_impl(buf, desc, Some(mapped_addr), ctx)
+            .await
+    }
+
+    async fn sendv_to_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVec<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.sendv_async_impl(iov, desc, Some(mapped_addr), ctx)
+            .await
+    }
+
+    async fn senddata_to_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mapped_addr: &MappedAddress,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.senddata_async_impl(buf, desc, data, Some(mapped_addr), ctx)
+            .await
+    }
 }
 
 impl<EP: AsyncSendEpImpl + ConnectedEp> ConnectedAsyncSendEp for EP {
@@ -603,6 +881,40 @@ impl<EP: AsyncSendEpImpl + ConnectedEp> ConnectedAsyncSendEp for EP {
     async fn injectdata_async<T>(&self, buf: &[T], data: u64) -> Result<(), crate::error::Error> {
         self.injectdata_async_impl(buf, data, None).await
     }
+
+    async fn send_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.send_async_impl(buf, desc, None, ctx).await
+    }
+
+    async fn sendv_triggered_async<'a>(
+        &self,
+        iov: &[crate::iovec::IoVec<'a>],
+        desc: Option<&[MemoryRegionDesc<'_>]>,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.sendv_async_impl(iov, desc, None, ctx).await
+    }
+
+    async fn senddata_triggered_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.senddata_async_impl(buf, desc, data, None, ctx).await
+    }
 }
 
 impl<EP: MsgCap + SendMod, EQ: ?Sized + AsyncReadEq, CQ: AsyncCq + ?Sized> AsyncSendEpImpl
@@ -616,3 +928,354 @@ impl<I: MsgCap + SendMod, STATE: EpState> AsyncSendEpImpl for TxContext<I, STATE
 
 impl<E: AsyncSendEpImpl> AsyncSendEpImpl for EndpointBase<E, Connected> {}
 impl<E: AsyncSendEpImpl> AsyncSendEpImpl for EndpointBase<E, Connectionless> {}
+
+/// Owned-buffer counterpart of [AsyncSendEp].
+///
+/// The buffer and the [Context] are moved into the returned future and handed back in its [BufResult],
+/// so the future is `'static` and can be spawned onto an executor.
+///
+/// The future is not [Send] though, even with the `thread-safe` feature: it refers to the raw handles of the
+/// endpoint and of the completion queue, so it has to be driven by the thread that created it, e.g.,
+/// with `tokio::task::spawn_local` or `async_std::task::spawn_local`.
+///
+/// If the future is dropped before the operation completes, the operation is canceled. When the provider cannot
+/// confirm the cancellation, the buffer is leaked rather than released while the operation might still access it.
+pub trait AsyncSendEpOwned {
+    fn send_to_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        mapped_addr: &MappedAddress,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static;
+    fn senddata_to_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        data: u64,
+        mapped_addr: &MappedAddress,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static;
+}
+
+/// Owned-buffer counterpart of [ConnectedAsyncSendEp].
+///
+/// Like those of [AsyncSendEpOwned], the returned futures are not [Send].
+pub trait ConnectedAsyncSendEpOwned {
+    fn send_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static;
+    fn senddata_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        data: u64,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static;
+}
+
+/// Owned-buffer counterpart of [AsyncRecvEp].
+///
+/// Like those of [AsyncSendEpOwned], the returned futures are not [Send].
+pub trait AsyncRecvEpOwned {
+    fn recv_from_owned<B: IoBufMut>(
+        &self,
+        buf: B,
+        mapped_addr: &MappedAddress,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static;
+    fn recv_from_any_owned<B: IoBufMut>(
+        &self,
+        buf: B,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static;
+}
+
+/// Owned-buffer counterpart of [ConnectedAsyncRecvEp].
+///
+/// Like those of [AsyncSendEpOwned], the returned futures are not [Send].
+pub trait ConnectedAsyncRecvEpOwned {
+    fn recv_owned<B: IoBufMut>(
+        &self,
+        buf: B,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static;
+}
+
+impl<E: AsyncSendEpImpl + 'static> AsyncSendEpOwned for EndpointBase<E, Connectionless> {
+    fn send_to_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        mapped_addr: &MappedAddress,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static {
+        let ep = self.inner.clone();
+        let mapped_addr = mapped_addr.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = ep
+                .send_async_impl(buf.as_slice(), buf.desc(), Some(&mapped_addr), ctx)
+                .await;
+            op.finish(res)
+        }
+    }
+
+    fn senddata_to_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        data: u64,
+        mapped_addr: &MappedAddress,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static {
+        let ep = self.inner.clone();
+        let mapped_addr = mapped_addr.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = ep
+                .senddata_async_impl(buf.as_slice(), buf.desc(), data, Some(&mapped_addr), ctx)
+                .await;
+            op.finish(res)
+        }
+    }
+}
+
+impl<E: AsyncSendEpImpl + 'static> ConnectedAsyncSendEpOwned for EndpointBase<E, Connected> {
+    fn send_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static {
+        let ep = self.inner.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = ep
+                .send_async_impl(buf.as_slice(), buf.desc(), None, ctx)
+                .await;
+            op.finish(res)
+        }
+    }
+
+    fn senddata_owned<B: IoBuf>(
+        &self,
+        buf: B,
+        data: u64,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static {
+        let ep = self.inner.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = ep
+                .senddata_async_impl(buf.as_slice(), buf.desc(), data, None, ctx)
+                .await;
+            op.finish(res)
+        }
+    }
+}
+
+impl<E: AsyncRecvEpImpl + 'static> AsyncRecvEpOwned for EndpointBase<E, Connectionless> {
+    fn recv_from_owned<B: IoBufMut>(
+        &self,
+        buf: B,
+        mapped_addr: &MappedAddress,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static {
+        let ep = self.inner.clone();
+        let mapped_addr = mapped_addr.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let (slice, desc) = buf.as_mut_slice_desc();
+            let res = ep
+                .recv_async_imp(slice, desc, Some(&mapped_addr), ctx)
+                .await;
+            op.finish(res)
+        }
+    }
+
+    fn recv_from_any_owned<B: IoBufMut>(
+        &self,
+        buf: B,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static {
+        let ep = self.inner.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let (slice, desc) = buf.as_mut_slice_desc();
+            let res = ep.recv_async_imp(slice, desc, None, ctx).await;
+            op.finish(res)
+        }
+    }
+}
+
+impl<E: AsyncRecvEpImpl + 'static> ConnectedAsyncRecvEpOwned for EndpointBase<E, Connected> {
+    fn recv_owned<B: IoBufMut>(
+        &self,
+        buf: B,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static {
+        let ep = self.inner.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let (slice, desc) = buf.as_mut_slice_desc();
+            let res = ep.recv_async_imp(slice, desc, None, ctx).await;
+            op.finish(res)
+        }
+    }
+}
+
+/// Versions of the [AsyncSendEp] operations that take their [Context] from the [crate::async_::ctx_pool::ContextPool]
+/// of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncSendEpPooled {
+    fn send_to_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    fn senddata_to_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mapped_addr: &MappedAddress,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncSendEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncSendEpPooled {
+    fn send_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    fn senddata_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [AsyncRecvEp] operations that take their [Context] from the [crate::async_::ctx_pool::ContextPool]
+/// of the receive completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncRecvEpPooled {
+    fn recv_from_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+    fn recv_from_any_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncRecvEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the receive completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncRecvEpPooled {
+    fn recv_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+impl<EP: AsyncSendEpImpl + ConnlessEp> AsyncSendEpPooled for EP {
+    async fn send_to_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.send_async_impl(buf, desc, Some(mapped_addr), &mut ctx)
+            .await
+    }
+
+    async fn senddata_to_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mapped_addr: &MappedAddress,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.senddata_async_impl(buf, desc, data, Some(mapped_addr), &mut ctx)
+            .await
+    }
+}
+
+impl<EP: AsyncSendEpImpl + ConnectedEp> ConnectedAsyncSendEpPooled for EP {
+    async fn send_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.send_async_impl(buf, desc, None, &mut ctx).await
+    }
+
+    async fn senddata_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.senddata_async_impl(buf, desc, data, None, &mut ctx)
+            .await
+    }
+}
+
+impl<EP: AsyncRecvEpImpl + ConnlessEp> AsyncRecvEpPooled for EP {
+    async fn recv_from_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_rx_cq()?.as_ref())?;
+        self.recv_async_imp(buf, desc, Some(mapped_addr), &mut ctx)
+            .await
+    }
+
+    async fn recv_from_any_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_rx_cq()?.as_ref())?;
+        self.recv_async_imp(buf, desc, None, &mut ctx).await
+    }
+}
+
+impl<EP: AsyncRecvEpImpl + ConnectedEp> ConnectedAsyncRecvEpPooled for EP {
+    async fn recv_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_rx_cq()?.as_ref())?;
+        self.recv_async_imp(buf, desc, None, &mut ctx).await
+    }
+}
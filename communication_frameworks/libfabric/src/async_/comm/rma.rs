@@ -1,3 +1,5 @@
+use crate::async_::buf::{BufResult, InFlight, IoBuf, IoBufMut};
+use crate::async_::ctx_pool::acquire_from;
 use crate::async_::ep::AsyncTxEp;
 use crate::async_::xcontext::{TxContext, TxContextImpl};
 // use crate::async_::xcontext::{RxContext, RxContextImpl, TxContext, TxContextImpl};
@@ -12,7 +14,7 @@ use crate::utils::Either;
 use crate::{
     async_::{cq::AsyncCq, eq::AsyncReadEq},
     cq::SingleCompletion,
-    enums::{ReadMsgOptions, WriteMsgOptions},
+    enums::{ReadMsgOptions, TriggerEvent, WriteMsgOptions},
     ep::EndpointBase,
     infocapsoptions::{ReadMod, WriteMod},
     mr::MappedMemoryRegionKey,
@@ -46,7 +48,7 @@ pub(crate) trait AsyncReadEpImpl: AsyncTxEp + ReadEpImpl {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async unsafe fn readv_async_impl<'a>(
@@ -70,7 +72,7 @@ pub(crate) trait AsyncReadEpImpl: AsyncTxEp + ReadEpImpl {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async unsafe fn readmsg_async_impl<'a>(
@@ -93,7 +95,7 @@ pub(crate) trait AsyncReadEpImpl: AsyncTxEp + ReadEpImpl {
             Either::Right(ref mut msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -129,6 +131,21 @@ pub trait AsyncReadEp {
         msg: &mut crate::msg::MsgRmaMut,
         options: ReadMsgOptions,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Async version of [crate::comm::rma::ReadEp::read_from_triggered]
+    /// # Safety
+    /// See [crate::comm::rma::ReadEp::read_from_triggered]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn read_from_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        src_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait AsyncReadEpMrSlice: AsyncReadEp {
@@ -240,6 +257,19 @@ pub trait ConnectedAsyncReadEp {
         msg: &mut crate::msg::MsgRmaConnectedMut,
         options: ReadMsgOptions,
     ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Async version of [crate::comm::rma::ConnectedReadEp::read_triggered]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedReadEp::read_triggered]
+    unsafe fn read_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait ConnectedAsyncReadEpMrSlice: ConnectedAsyncReadEp {
@@ -357,6 +387,21 @@ impl<EP: AsyncReadEpImpl> AsyncReadEp for EP {
     ) -> Result<SingleCompletion, crate::error::Error> {
         self.readmsg_async_impl(Either::Left(msg), options).await
     }
+
+    async unsafe fn read_from_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        src_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.read_async_impl(buf, desc, Some(src_addr), mem_addr, mapped_key, ctx)
+            .await
+    }
 }
 
 impl<EP: AsyncReadEpImpl> ConnectedAsyncReadEp for EP {
@@ -391,6 +436,20 @@ impl<EP: AsyncReadEpImpl> ConnectedAsyncReadEp for EP {
     ) -> Result<SingleCompletion, crate::error::Error> {
         self.readmsg_async_impl(Either::Right(msg), options).await
     }
+
+    async unsafe fn read_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.read_async_impl(buf, desc, None, mem_addr, mapped_key, ctx)
+            .await
+    }
 }
 
 pub(crate) trait AsyncWriteEpImpl: AsyncTxEp + WriteEpImpl {
@@ -416,7 +475,7 @@ pub(crate) trait AsyncWriteEpImpl: AsyncTxEp + WriteEpImpl {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async unsafe fn inject_write_async_impl<T: Copy, RT: Copy>(
@@ -456,7 +515,7 @@ pub(crate) trait AsyncWriteEpImpl: AsyncTxEp + WriteEpImpl {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -483,7 +542,7 @@ pub(crate) trait AsyncWriteEpImpl: AsyncTxEp + WriteEpImpl {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -523,7 +582,7 @@ pub(crate) trait AsyncWriteEpImpl: AsyncTxEp + WriteEpImpl {
             Either::Right(ref mut msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -599,6 +658,37 @@ pub trait AsyncWriteEp {
         mem_addr: RemoteMemoryAddress<RT>,
         mapped_key: &MappedMemoryRegionKey,
     ) -> impl std::future::Future<Output = Result<(), crate::error::Error>>;
+
+    /// Async version of [crate::comm::rma::WriteEp::write_to_triggered]
+    /// # Safety
+    /// See [crate::comm::rma::WriteEp::write_to_triggered]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn write_to_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Async version of [crate::comm::rma::WriteEp::writedata_to_triggered]
+    /// # Safety
+    /// See [crate::comm::rma::WriteEp::writedata_to_triggered]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn writedata_to_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait AsyncWriteRemoteMemAddrSliceEp: AsyncWriteEp {
@@ -794,6 +884,34 @@ pub trait ConnectedAsyncWriteEp: ConnectedWriteEp {
         mem_addr: RemoteMemoryAddress<RT>,
         mapped_key: &MappedMemoryRegionKey,
     ) -> impl std::future::Future<Output = Result<(), crate::error::Error>>;
+
+    /// Async version of [crate::comm::rma::ConnectedWriteEp::write_triggered]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedWriteEp::write_triggered]
+    unsafe fn write_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Async version of [crate::comm::rma::ConnectedWriteEp::writedata_triggered]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedWriteEp::writedata_triggered]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn writedata_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
 }
 
 pub trait ConnectedAsyncWriteRemoteMemAddrSliceEp: ConnectedAsyncWriteEp {
@@ -1301,6 +1419,39 @@ impl<EP: AsyncWriteEpImpl + ConnlessEp> AsyncWriteEp for EP {
         self.inject_writedata_async_impl(buf, data, Some(dest_addr), mem_addr, mapped_key)
             .await
     }
+
+    #[inline]
+    async unsafe fn write_to_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.write_async_impl(buf, desc, Some(dest_addr), mem_addr, mapped_key, ctx)
+            .await
+    }
+
+    #[inline]
+    async unsafe fn writedata_to_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.writedata_async_impl(buf, desc, data, Some(dest_addr), mem_addr, mapped_key, ctx)
+            .await
+    }
 }
 
 impl<EP: AsyncWriteEpImpl + ConnectedEp> ConnectedAsyncWriteEp for EP {
@@ -1375,6 +1526,37 @@ impl<EP: AsyncWriteEpImpl + ConnectedEp> ConnectedAsyncWriteEp for EP {
         self.inject_writedata_async_impl(buf, data, None, mem_addr, mapped_key)
             .await
     }
+
+    #[inline]
+    async unsafe fn write_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.write_async_impl(buf, desc, None, mem_addr, mapped_key, ctx)
+            .await
+    }
+
+    #[inline]
+    async unsafe fn writedata_triggered_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        event: &mut TriggerEvent<'_>,
+        ctx: &mut Context,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        ctx.set_trigger(event);
+        self.writedata_async_impl(buf, desc, data, None, mem_addr, mapped_key, ctx)
+            .await
+    }
 }
 
 impl<EP: AsyncReadEp> AsyncReadRemoteMemAddrSliceEp for EP {}
@@ -1390,3 +1572,479 @@ pub trait AsyncReadWriteRemoteMemAddrSliceEp:
     AsyncReadRemoteMemAddrSliceEp + AsyncWriteRemoteMemAddrSliceEp
 {
 }
+
+/// Versions of the [AsyncReadEp] operations that take their [Context] from the [crate::async_::ctx_pool::ContextPool]
+/// of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncReadEpPooled {
+    /// Pooled version of [AsyncReadEp::read_from_async]
+    /// # Safety
+    /// See [crate::comm::rma::ReadEp::read_from]
+    unsafe fn read_from_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        src_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncReadEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncReadEpPooled {
+    /// Pooled version of [ConnectedAsyncReadEp::read_async]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedReadEp::read]
+    unsafe fn read_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [AsyncWriteEp] operations that take their [Context] from the [crate::async_::ctx_pool::ContextPool]
+/// of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncWriteEpPooled {
+    /// Pooled version of [AsyncWriteEp::write_to_async]
+    /// # Safety
+    /// See [crate::comm::rma::WriteEp::write_to]
+    unsafe fn write_to_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Pooled version of [AsyncWriteEp::writedata_to_async]
+    /// # Safety
+    /// See [crate::comm::rma::WriteEp::writedata_to]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn writedata_to_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncWriteEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncWriteEpPooled {
+    /// Pooled version of [ConnectedAsyncWriteEp::write_async]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedWriteEp::write]
+    unsafe fn write_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+
+    /// Pooled version of [ConnectedAsyncWriteEp::writedata_async]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedWriteEp::writedata]
+    unsafe fn writedata_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+impl<EP: AsyncReadEpImpl + ConnlessEp> AsyncReadEpPooled for EP {
+    async unsafe fn read_from_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        src_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.read_async_impl(buf, desc, Some(src_addr), mem_addr, mapped_key, &mut ctx)
+            .await
+    }
+}
+
+impl<EP: AsyncReadEpImpl + ConnectedEp> ConnectedAsyncReadEpPooled for EP {
+    async unsafe fn read_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.read_async_impl(buf, desc, None, mem_addr, mapped_key, &mut ctx)
+            .await
+    }
+}
+
+impl<EP: AsyncWriteEpImpl + ConnlessEp> AsyncWriteEpPooled for EP {
+    async unsafe fn write_to_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.write_async_impl(buf, desc, Some(dest_addr), mem_addr, mapped_key, &mut ctx)
+            .await
+    }
+
+    async unsafe fn writedata_to_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.writedata_async_impl(
+            buf,
+            desc,
+            data,
+            Some(dest_addr),
+            mem_addr,
+            mapped_key,
+            &mut ctx,
+        )
+        .await
+    }
+}
+
+impl<EP: AsyncWriteEpImpl + ConnectedEp> ConnectedAsyncWriteEpPooled for EP {
+    async unsafe fn write_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.write_async_impl(buf, desc, None, mem_addr, mapped_key, &mut ctx)
+            .await
+    }
+
+    async unsafe fn writedata_pooled_async<T: Copy, RT: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        data: u64,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.writedata_async_impl(buf, desc, data, None, mem_addr, mapped_key, &mut ctx)
+            .await
+    }
+}
+
+/// Owned-buffer counterpart of [AsyncWriteEp].
+///
+/// The buffer and the [Context] are moved into the returned future and handed back in its [BufResult],
+/// so the future is `'static` and can be spawned onto an executor. Like the ones of
+/// [AsyncSendEpOwned](crate::async_::comm::message::AsyncSendEpOwned), it is not [Send] and leaks the buffer
+/// if it is dropped before the operation completes and the provider cannot confirm its cancellation.
+pub trait AsyncWriteEpOwned {
+    /// Owned-buffer version of [AsyncWriteEp::write_to_async]
+    /// # Safety
+    /// See [crate::comm::rma::WriteEp::write_to]
+    unsafe fn write_to_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy;
+
+    /// Owned-buffer version of [AsyncWriteEp::writedata_to_async]
+    /// # Safety
+    /// See [crate::comm::rma::WriteEp::writedata_to]
+    unsafe fn writedata_to_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        data: u64,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy;
+}
+
+/// Owned-buffer counterpart of [ConnectedAsyncWriteEp].
+///
+/// Like those of [AsyncWriteEpOwned], the returned futures are not [Send].
+pub trait ConnectedAsyncWriteEpOwned {
+    /// Owned-buffer version of [ConnectedAsyncWriteEp::write_async]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedWriteEp::write]
+    unsafe fn write_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy;
+
+    /// Owned-buffer version of [ConnectedAsyncWriteEp::writedata_async]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedWriteEp::writedata]
+    unsafe fn writedata_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        data: u64,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy;
+}
+
+/// Owned-buffer counterpart of [AsyncReadEp].
+///
+/// Like those of [AsyncWriteEpOwned], the returned futures are not [Send].
+pub trait AsyncReadEpOwned {
+    /// Owned-buffer version of [AsyncReadEp::read_from_async]
+    /// # Safety
+    /// See [crate::comm::rma::ReadEp::read_from]
+    unsafe fn read_from_owned<B: IoBufMut, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        src_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy;
+}
+
+/// Owned-buffer counterpart of [ConnectedAsyncReadEp].
+///
+/// Like those of [AsyncWriteEpOwned], the returned futures are not [Send].
+pub trait ConnectedAsyncReadEpOwned {
+    /// Owned-buffer version of [ConnectedAsyncReadEp::read_async]
+    /// # Safety
+    /// See [crate::comm::rma::ConnectedReadEp::read]
+    unsafe fn read_owned<B: IoBufMut, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy;
+}
+
+impl<E: AsyncWriteEpImpl + 'static> AsyncWriteEpOwned for EndpointBase<E, Connectionless> {
+    unsafe fn write_to_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy,
+    {
+        let ep = self.inner.clone();
+        let dest_addr = dest_addr.clone();
+        let mapped_key = mapped_key.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = unsafe {
+                ep.write_async_impl(
+                    buf.as_slice(),
+                    buf.desc(),
+                    Some(&dest_addr),
+                    mem_addr,
+                    &mapped_key,
+                    ctx,
+                )
+            }
+            .await;
+            op.finish(res)
+        }
+    }
+
+    unsafe fn writedata_to_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        data: u64,
+        dest_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy,
+    {
+        let ep = self.inner.clone();
+        let dest_addr = dest_addr.clone();
+        let mapped_key = mapped_key.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = unsafe {
+                ep.writedata_async_impl(
+                    buf.as_slice(),
+                    buf.desc(),
+                    data,
+                    Some(&dest_addr),
+                    mem_addr,
+                    &mapped_key,
+                    ctx,
+                )
+            }
+            .await;
+            op.finish(res)
+        }
+    }
+}
+
+impl<E: AsyncWriteEpImpl + 'static> ConnectedAsyncWriteEpOwned for EndpointBase<E, Connected> {
+    unsafe fn write_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy,
+    {
+        let ep = self.inner.clone();
+        let mapped_key = mapped_key.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = unsafe {
+                ep.write_async_impl(buf.as_slice(), buf.desc(), None, mem_addr, &mapped_key, ctx)
+            }
+            .await;
+            op.finish(res)
+        }
+    }
+
+    unsafe fn writedata_owned<B: IoBuf, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        data: u64,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy,
+    {
+        let ep = self.inner.clone();
+        let mapped_key = mapped_key.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let res = unsafe {
+                ep.writedata_async_impl(
+                    buf.as_slice(),
+                    buf.desc(),
+                    data,
+                    None,
+                    mem_addr,
+                    &mapped_key,
+                    ctx,
+                )
+            }
+            .await;
+            op.finish(res)
+        }
+    }
+}
+
+impl<E: AsyncReadEpImpl + 'static> AsyncReadEpOwned for EndpointBase<E, Connectionless> {
+    unsafe fn read_from_owned<B: IoBufMut, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        src_addr: &MappedAddress,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy,
+    {
+        let ep = self.inner.clone();
+        let src_addr = src_addr.clone();
+        let mapped_key = mapped_key.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let (slice, desc) = buf.as_mut_slice_desc();
+            let res = unsafe {
+                ep.read_async_impl(slice, desc, Some(&src_addr), mem_addr, &mapped_key, ctx)
+            }
+            .await;
+            op.finish(res)
+        }
+    }
+}
+
+impl<E: AsyncReadEpImpl + 'static> ConnectedAsyncReadEpOwned for EndpointBase<E, Connected> {
+    unsafe fn read_owned<B: IoBufMut, RT: Copy + 'static>(
+        &self,
+        buf: B,
+        mem_addr: RemoteMemoryAddress<RT>,
+        mapped_key: &MappedMemoryRegionKey,
+        ctx: Context,
+    ) -> impl std::future::Future<Output = BufResult<B>> + 'static
+    where
+        B::Item: Copy,
+    {
+        let ep = self.inner.clone();
+        let mapped_key = mapped_key.clone();
+        async move {
+            let mut op = InFlight::new(buf, ctx);
+            let (buf, ctx) = op.parts_mut();
+            let (slice, desc) = buf.as_mut_slice_desc();
+            let res =
+                unsafe { ep.read_async_impl(slice, desc, None, mem_addr, &mapped_key, ctx) }.await;
+            op.finish(res)
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use crate::async_::ctx_pool::acquire_from;
 use crate::async_::ep::{AsyncRxEp, AsyncTxEp};
-use crate::async_::xcontext::{RxContext, RxContextImpl, TxContext, TxContextImpl};
+use crate::async_::xcontext::{
+    RxContext, RxContextImpl, SharedRxContext, SharedRxContextImpl, TxContext, TxContextImpl,
+};
 // use crate::async_::xcontext::{RxContext, RxContextImpl, TxContext, TxContextImpl};
 use crate::comm::tagged::{TagRecvEpImpl, TagSendEpImpl};
 use crate::conn_ep::ConnectedEp;
@@ -30,12 +33,12 @@ pub(crate) trait AsyncTagRecvEpImpl: AsyncRxEp + TagRecvEpImpl {
         ignore: Option<u64>,
         ctx: &mut Context,
     ) -> Result<SingleCompletion, crate::error::Error> {
-        let cq = self.retrieve_rx_cq();
+        let cq = self.retrieve_rx_cq()?;
         while_try_again(cq.as_ref(), || {
             self.trecv_impl(buf, desc, mapped_addr, tag, ignore, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn trecvv_async_impl<'a>(
@@ -47,7 +50,7 @@ pub(crate) trait AsyncTagRecvEpImpl: AsyncRxEp + TagRecvEpImpl {
         ignore: Option<u64>,
         ctx: &mut Context,
     ) -> Result<SingleCompletion, crate::error::Error> {
-        let cq = self.retrieve_rx_cq();
+        let cq = self.retrieve_rx_cq()?;
         while_try_again(cq.as_ref(), || {
             self.trecvv_impl(
                 iov,
@@ -59,7 +62,7 @@ pub(crate) trait AsyncTagRecvEpImpl: AsyncRxEp + TagRecvEpImpl {
             )
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn trecvmsg_async_impl<'a>(
@@ -75,7 +78,7 @@ pub(crate) trait AsyncTagRecvEpImpl: AsyncRxEp + TagRecvEpImpl {
             Either::Right(msg) => Either::<&MsgTaggedMut, &MsgTaggedConnectedMut>::Right(msg),
         };
 
-        let cq = self.retrieve_rx_cq();
+        let cq = self.retrieve_rx_cq()?;
         while_try_again(cq.as_ref(), || {
             self.trecvmsg_impl(imm_msg.to_owned(), options)
         })
@@ -86,7 +89,7 @@ pub(crate) trait AsyncTagRecvEpImpl: AsyncRxEp + TagRecvEpImpl {
             Either::Right(msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 }
 
@@ -187,6 +190,10 @@ impl<I: TagCap + RecvMod, STATE: EpState> AsyncTagRecvEpImpl for RxContextImpl<I
 
 impl<I: TagCap + RecvMod, STATE: EpState> AsyncTagRecvEpImpl for RxContext<I, STATE> {}
 
+impl<I: TagCap + RecvMod> AsyncTagRecvEpImpl for SharedRxContextImpl<I> {}
+
+impl<I: TagCap + RecvMod> AsyncTagRecvEpImpl for SharedRxContext<I> {}
+
 impl<E: AsyncTagRecvEpImpl> AsyncTagRecvEpImpl for EndpointBase<E, Connected> {}
 impl<E: AsyncTagRecvEpImpl> AsyncTagRecvEpImpl for EndpointBase<E, Connectionless> {}
 
@@ -280,7 +287,7 @@ pub(crate) trait AsyncTagSendEpImpl: AsyncTxEp + TagSendEpImpl {
             self.tsend_impl(buf, desc, mapped_addr, tag, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn tinject_async_impl<T>(
@@ -306,7 +313,7 @@ pub(crate) trait AsyncTagSendEpImpl: AsyncTxEp + TagSendEpImpl {
             self.tsendv_impl(iov, desc, dest_mapped_addr, tag, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn tsendmsg_async_impl<'a>(
@@ -330,7 +337,7 @@ pub(crate) trait AsyncTagSendEpImpl: AsyncTxEp + TagSendEpImpl {
             Either::Right(msg) => msg.context(),
         };
 
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn tsenddata_async_impl<T>(
@@ -347,7 +354,7 @@ pub(crate) trait AsyncTagSendEpImpl: AsyncTxEp + TagSendEpImpl {
             self.tsenddata_impl(buf, desc, data, mapped_addr, tag, Some(ctx.inner_mut()))
         })
         .await?;
-        cq.wait_for_ctx_async(ctx).await
+        cq.wait_for_ctx_async(ctx).cancel_on_drop(self).await
     }
 
     async fn tinjectdata_async_impl<T>(
@@ -669,3 +676,122 @@ impl<EP: AsyncTagSendEpImpl + ConnectedEp> ConnectedAsyncTagSendEp for EP {
         self.tinjectdata_async_impl(buf, data, None, tag).await
     }
 }
+
+/// Versions of the [AsyncTagSendEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncTagSendEpPooled {
+    fn tsend_to_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        tag: u64,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncTagSendEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the transmit completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncTagSendEpPooled {
+    fn tsend_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        tag: u64,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [AsyncTagRecvEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the receive completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait AsyncTagRecvEpPooled {
+    fn trecv_from_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        tag: u64,
+        ignore: Option<u64>,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+/// Versions of the [ConnectedAsyncTagRecvEp] operations that take their [Context] from the
+/// [crate::async_::ctx_pool::ContextPool] of the receive completion queue.
+///
+/// # Errors
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if the completion queue was built without a context pool.
+pub trait ConnectedAsyncTagRecvEpPooled {
+    fn trecv_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        tag: u64,
+        ignore: Option<u64>,
+    ) -> impl std::future::Future<Output = Result<SingleCompletion, crate::error::Error>>;
+}
+
+impl<EP: AsyncTagSendEpImpl + ConnlessEp> AsyncTagSendEpPooled for EP {
+    #[inline]
+    async fn tsend_to_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        tag: u64,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.tsend_async_impl(buf, desc, Some(mapped_addr), tag, &mut ctx)
+            .await
+    }
+}
+
+impl<EP: AsyncTagSendEpImpl + ConnectedEp> ConnectedAsyncTagSendEpPooled for EP {
+    #[inline]
+    async fn tsend_pooled_async<T>(
+        &self,
+        buf: &[T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        tag: u64,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_tx_cq().as_ref())?;
+        self.tsend_async_impl(buf, desc, None, tag, &mut ctx).await
+    }
+}
+
+impl<EP: AsyncTagRecvEpImpl> AsyncTagRecvEpPooled for EP {
+    #[inline]
+    async fn trecv_from_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        mapped_addr: &MappedAddress,
+        tag: u64,
+        ignore: Option<u64>,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_rx_cq()?.as_ref())?;
+        self.trecv_async_impl(buf, desc, Some(mapped_addr), tag, ignore, &mut ctx)
+            .await
+    }
+}
+
+impl<EP: AsyncTagRecvEpImpl + ConnectedEp> ConnectedAsyncTagRecvEpPooled for EP {
+    #[inline]
+    async fn trecv_pooled_async<T>(
+        &self,
+        buf: &mut [T],
+        desc: Option<MemoryRegionDesc<'_>>,
+        tag: u64,
+        ignore: Option<u64>,
+    ) -> Result<SingleCompletion, crate::error::Error> {
+        let mut ctx = acquire_from(self.retrieve_rx_cq()?.as_ref())?;
+        self.trecv_async_impl(buf, desc, None, tag, ignore, &mut ctx)
+            .await
+    }
+}
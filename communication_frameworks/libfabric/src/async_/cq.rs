@@ -5,7 +5,8 @@ use crate::domain::{DomainBase, DomainImplBase};
 use crate::fid::AsTypedFid;
 use crate::fid::BorrowedTypedFid;
 use crate::fid::CqRawFid;
-use crate::ContextType;
+use crate::fid::{AsRawFid, AsRawTypedFid, EpRawFid, TypedFid};
+use crate::progress::Progressable;
 use crate::SyncSend;
 use crate::{
     cq::{
@@ -23,6 +24,8 @@ use std::{future::Future, task::ready};
 #[cfg(feature = "use-tokio")]
 use tokio::io::unix::AsyncFd as Async;
 
+use super::ctx_pool::ContextPool;
+use super::fanout::WakerFanout;
 use super::AsyncFid;
 // macro_rules! alloc_cq_entry {
 //     ($format: expr, $count: expr) => {
@@ -64,6 +67,9 @@ pub trait AsyncCq: ReadCq {
     // fn read_async(&self, count: usize,  ctx: &mut Context) -> CqAsyncReadOwned;
     fn wait_for_ctx_async<'a>(&'a self, ctx: &'a mut Context) -> AsyncTransferCq<'a>;
     fn get(&self) -> &dyn ReadCq;
+
+    /// Returns the [ContextPool] attached to the queue, if any (see [CompletionQueueBuilder::context_pool]).
+    fn context_pool(&self) -> Option<&ContextPool>;
 }
 
 impl CompletionQueue<AsyncCompletionQueueImpl> {
@@ -72,6 +78,7 @@ impl CompletionQueue<AsyncCompletionQueueImpl> {
         attr: CompletionQueueAttr,
         context: Option<&mut Context>,
         default_buff_size: usize,
+        context_pool: Option<ContextPool>,
     ) -> Result<Self, crate::error::Error> {
         let c_void = match context {
             Some(ctx) => ctx.inner_mut(),
@@ -84,6 +91,7 @@ impl CompletionQueue<AsyncCompletionQueueImpl> {
                 attr,
                 c_void,
                 default_buff_size,
+                context_pool,
             )?),
         })
     }
@@ -95,6 +103,7 @@ impl CompletionQueue<AsyncCompletionQueueImpl> {
         attr: CompletionQueueAttr,
         context: Option<&mut Context>,
         default_buff_size: usize,
+        context_pool: Option<ContextPool>,
     ) -> Result<Self, crate::error::Error> {
         let c_void = match context {
             Some(ctx) => ctx.inner_mut(),
@@ -107,6 +116,7 @@ impl CompletionQueue<AsyncCompletionQueueImpl> {
                 attr,
                 c_void,
                 default_buff_size,
+                context_pool,
             )?),
         })
     }
@@ -246,6 +256,8 @@ pub struct AsyncCompletionQueueImpl {
     base: AsyncCompletionQueueImplBase,
     #[allow(dead_code)]
     pub(crate) pending_entries: AtomicUsize,
+    context_pool: Option<ContextPool>,
+    fanout: WakerFanout,
 }
 impl SyncSend for AsyncCompletionQueueImpl {}
 // impl SyncCq for AsyncCompletionQueueImpl{}
@@ -305,6 +317,10 @@ impl AsyncCq for AsyncCompletionQueueImpl {
     fn get(&self) -> &dyn ReadCq {
         self
     }
+
+    fn context_pool(&self) -> Option<&ContextPool> {
+        self.context_pool.as_ref()
+    }
 }
 
 impl AsyncFid for AsyncCompletionQueueImpl {
@@ -349,6 +365,7 @@ impl AsyncCompletionQueueImpl {
         attr: CompletionQueueAttr,
         context: *mut std::ffi::c_void,
         default_buff_size: usize,
+        context_pool: Option<ContextPool>,
     ) -> Result<Self, crate::error::Error> {
         Ok(Self {
             base: AsyncCompletionQueueImplBase::BlockingCq(
@@ -358,9 +375,11 @@ impl AsyncCompletionQueueImpl {
                     context,
                     default_buff_size,
                 )?)
-                .unwrap(),
+                .map_err(crate::error::Error::from_io_err)?,
             ),
             pending_entries: AtomicUsize::new(0),
+            context_pool,
+            fanout: WakerFanout::new(),
         })
     }
 }
@@ -371,6 +390,7 @@ impl AsyncCompletionQueueImpl {
         attr: CompletionQueueAttr,
         context: *mut std::ffi::c_void,
         default_buff_size: usize,
+        context_pool: Option<ContextPool>,
     ) -> Result<Self, crate::error::Error> {
         Ok(Self {
             base: AsyncCompletionQueueImplBase::SpinningCQ(CompletionQueueImpl::new(
@@ -381,12 +401,164 @@ impl AsyncCompletionQueueImpl {
             )?),
 
             pending_entries: AtomicUsize::new(0),
+            context_pool,
+            fanout: WakerFanout::new(),
+        })
+    }
+}
+
+/// Future resolving to the completion of the operation associated with a [Context].
+///
+/// If the future was obtained from one of the asynchronous data transfer calls (e.g., [crate::async_::comm::message::AsyncSendEp::send_to_async])
+/// and is dropped before the operation completes, the operation is canceled (`fi_cancel`) and the drop blocks until
+/// its completion, possibly with `FI_ECANCELED`, has been retrieved from the queue. This guarantees that the provider
+/// no longer accesses the buffers of the operation once they are released.
+///
+/// Providers that cannot cancel operations (i.e., `fi_cancel` fails with anything but `FI_ENOENT`) offer no such
+/// guarantee: rather than blocking forever on an operation that might never complete, the drop returns and leaves the
+/// memory of the context to the provider. The owned-buffer operations (e.g.,
+/// [crate::async_::comm::message::AsyncSendEpOwned::send_to_owned]) leak their buffer as well in that case, whereas
+/// the borrowed buffers of the other operations must be kept alive by the caller.
+impl AsyncCompletionQueueImpl {
+    /// Cancels the operation associated with `ctx` on endpoint `ep` and blocks until its completion is read.
+    ///
+    /// Completions of other operations read in the meantime are handed to their own contexts.
+    fn cancel_and_drain(&self, ep: &TypedFid<EpRawFid>, ctx: &mut Context) {
+        if !ctx.ready() {
+            let err = unsafe { libfabric_sys::inlined_fi_cancel(ep.0.as_raw_fid(), ctx.inner_mut()) };
+            // The operation might have completed already, in which case the call fails with FI_ENOENT
+            // and its completion is simply waiting in the queue
+            if err < 0 && (-err) as u32 != libfabric_sys::FI_ENOENT {
+                ctx.leak();
+                return;
+            }
+        }
+
+        let using_context2 = self.using_context2();
+
+        let mut handed_out = false;
+        while !ctx.ready() {
+            let res = match self.read(1) {
+                Ok(mut completion) => match completion.pop() {
+                    Some(entry) => Some((entry.op_context(), Ok(entry))),
+                    None => None,
+                },
+                Err(error) => match error.kind {
+                    crate::error::ErrorKind::TryAgain => None,
+                    crate::error::ErrorKind::ErrorAvailable => {
+                        let mut err = CompletionError::new();
+                        if self.readerr_in(&mut err, 0).is_err() {
+                            break;
+                        }
+                        Some((
+                            err.c_err.op_context,
+                            Err(Error::from_completion_queue_err(err)),
+                        ))
+                    }
+                    // The queue is unusable, there is nothing left to wait for
+                    _ => break,
+                },
+            };
+
+            match res {
+                Some((op_context, comp)) if !op_context.is_null() => {
+                    handed_out |= op_context as usize != ctx.inner() as usize;
+                    if using_context2 {
+                        unsafe { (*(op_context as *mut crate::Context2)).set_completion_done(comp) }
+                    } else {
+                        unsafe { (*(op_context as *mut crate::Context1)).set_completion_done(comp) }
+                    }
+                }
+                Some(_) => {}
+                None => std::thread::yield_now(),
+            }
+        }
+        if handed_out {
+            self.fanout.wake_all();
+        }
+        if ctx.ready() {
+            ctx.reset();
+        } else {
+            // The queue failed before the completion showed up, the operation might still be in flight
+            ctx.leak();
+        }
+    }
+}
+
+impl AsyncCompletionQueueImpl {
+    // Whether the contexts of the operations are of type Context2, as required by the fabric
+    fn using_context2(&self) -> bool {
+        match &self.base {
+            AsyncCompletionQueueImplBase::BlockingCq(async_cq) => {
+                async_cq.get_ref()._domain_rc.fabric_impl().using_context2
+            }
+            AsyncCompletionQueueImplBase::SpinningCQ(cq) => {
+                cq._domain_rc.fabric_impl().using_context2
+            }
+        }
+    }
+
+    /// Reads every available completion, handing each of them over to the context of its operation
+    /// and waking up the futures parked on the queue.
+    pub(crate) fn dispatch_available(&self) -> Result<usize, Error> {
+        let using_context2 = self.using_context2();
+
+        let mut dispatched = 0;
+        let res = loop {
+            let (op_context, comp) = match self.read(1) {
+                Ok(mut completion) => match completion.pop() {
+                    Some(entry) => (entry.op_context(), Ok(entry)),
+                    None => break Ok(dispatched),
+                },
+                Err(error) => match error.kind {
+                    crate::error::ErrorKind::TryAgain => break Ok(dispatched),
+                    crate::error::ErrorKind::ErrorAvailable => {
+                        let mut err = CompletionError::new();
+                        if let Err(error) = self.readerr_in(&mut err, 0) {
+                            break Err(error);
+                        }
+                        (
+                            err.c_err.op_context,
+                            Err(Error::from_completion_queue_err(err)),
+                        )
+                    }
+                    _ => break Err(error),
+                },
+            };
+
+            // Completions of operations that did not register a context have no owner
+            if !op_context.is_null() {
+                if using_context2 {
+                    unsafe { (*(op_context as *mut crate::Context2)).set_completion_done(comp) }
+                } else {
+                    unsafe { (*(op_context as *mut crate::Context1)).set_completion_done(comp) }
+                }
+                dispatched += 1;
+            }
+        };
+
+        if dispatched > 0 {
+            self.fanout.wake_all();
+        }
+        res
+    }
+}
+
+impl Progressable for CompletionQueue<AsyncCompletionQueueImpl> {
+    fn progress_once(&self) -> Result<usize, Error> {
+        self.inner.dispatch_available()
+    }
+
+    fn progressable_clone(&self) -> Box<dyn Progressable> {
+        Box::new(Self {
+            inner: self.inner.clone(),
         })
     }
 }
 
 pub struct AsyncTransferCq<'a> {
     fut: Pin<Box<CqAsyncReadOwned<'a>>>,
+    in_flight: Option<TypedFid<EpRawFid>>,
 }
 
 impl<'a> AsyncTransferCq<'a> {
@@ -394,6 +566,22 @@ impl<'a> AsyncTransferCq<'a> {
     pub(crate) fn new(cq: &'a AsyncCompletionQueueImpl, ctx: &'a mut Context) -> Self {
         Self {
             fut: Box::pin(CqAsyncReadOwned::new(cq, ctx)),
+            in_flight: None,
+        }
+    }
+
+    /// Cancels the operation on endpoint `ep` if the future is dropped before completion.
+    pub(crate) fn cancel_on_drop(mut self, ep: &(impl AsTypedFid<EpRawFid> + ?Sized)) -> Self {
+        self.in_flight = Some(TypedFid(ep.as_typed_fid().as_raw_typed_fid()));
+        self
+    }
+}
+
+impl Drop for AsyncTransferCq<'_> {
+    fn drop(&mut self) {
+        if let Some(ep) = self.in_flight.take() {
+            let fut = self.fut.as_mut().get_mut();
+            fut.cq.cancel_and_drain(&ep, fut.context);
         }
     }
 }
@@ -407,7 +595,10 @@ impl<'a> Future for AsyncTransferCq<'a> {
     ) -> std::task::Poll<Self::Output> {
         // println!("Polling transfer cq for ctx {} {:x}", self.ctx.0.id(), self.ctx.inner() as usize);
         let mut_self = self.get_mut();
-        ready!(mut_self.fut.as_mut().poll(cx))?;
+        let res = ready!(mut_self.fut.as_mut().poll(cx));
+        // The completion has been retrieved, nothing is left to cancel
+        mut_self.in_flight = None;
+        res?;
         let state = mut_self.fut.context.state().take();
         // mut_self
         //     .fut
@@ -849,18 +1040,98 @@ impl<'a> Future for AsyncTransferCq<'a> {
 impl<'a> CqAsyncReadOwned<'a> {
     pub(crate) fn new(cq: &'a AsyncCompletionQueueImpl, context: &'a mut Context) -> Self {
         Self {
-            buf: None,
             cq,
             fut: None,
             context,
+            reader: false,
+        }
+    }
+
+    /// Hands `comp` over to the context of the operation it belongs to.
+    ///
+    /// Returns `true` if the completion belongs to an operation other than the one awaited by this future.
+    fn dispatch(
+        &mut self,
+        op_context: *mut std::ffi::c_void,
+        comp: Result<SingleCompletion, Error>,
+    ) -> bool {
+        if op_context as usize == self.context.inner() as usize {
+            self.context.set_completion_done(comp);
+            return false;
+        }
+        if self.cq.using_context2() {
+            unsafe { (*(op_context as *mut crate::Context2)).set_completion_done(comp) }
+        } else {
+            unsafe { (*(op_context as *mut crate::Context1)).set_completion_done(comp) }
+        }
+        true
+    }
+
+    /// Reads the queue until it is empty or the awaited operation completes, handing every completion over
+    /// to its context and waking up the futures parked on the queue if any was for them.
+    fn drain(&mut self) -> Result<(), Error> {
+        let mut handed_out = false;
+        let res = loop {
+            if self.context.ready() {
+                break Ok(());
+            }
+            match self.cq.read(1) {
+                Ok(mut completion) => {
+                    if let Some(entry) = completion.pop() {
+                        // A null op_context means the completing op (e.g. an inject-class write) never
+                        // registered a context, so there is no owner to route this completion to
+                        let op_context = entry.op_context();
+                        if !op_context.is_null() {
+                            handed_out |= self.dispatch(op_context, Ok(entry));
+                        }
+                    }
+                }
+                Err(error) => match error.kind {
+                    crate::error::ErrorKind::TryAgain => break Ok(()),
+                    crate::error::ErrorKind::ErrorAvailable => {
+                        let mut err = CompletionError::new();
+                        if let Err(error) = self.cq.readerr_in(&mut err, 0) {
+                            break Err(error);
+                        }
+                        let op_context = err.c_err.op_context;
+                        if op_context.is_null() {
+                            panic!(
+                                "libfabric: dropping anonymous CQ completion error (no owning context): {}",
+                                err.error()
+                            );
+                        }
+                        handed_out |=
+                            self.dispatch(op_context, Err(Error::from_completion_queue_err(err)));
+                    }
+                    _ => break Err(Error::from_err_code(error.c_err)),
+                },
+            }
+        };
+
+        if handed_out {
+            self.cq.fanout.wake_all();
+        }
+        res
+    }
+
+    fn release_reader(&mut self) {
+        if self.reader {
+            self.reader = false;
+            self.fut = None;
+            self.cq.fanout.release_reader();
         }
     }
 }
 
+/// Future resolving once the operation associated with a [Context] has completed.
+///
+/// On queues with a file descriptor wait object, the futures awaiting the same queue elect a single reader
+/// that blocks on the file descriptor (using `fi_trywait` to make sure the queue is empty beforehand) and
+/// hands the completions it reads over to their contexts. The others stay parked until the reader wakes them up.
 pub struct CqAsyncReadOwned<'a> {
-    buf: Option<SingleCompletion>,
     cq: &'a AsyncCompletionQueueImpl,
     context: &'a mut Context,
+    reader: bool,
     #[cfg(feature = "use-async-std")]
     fut: Option<Pin<Box<Readable<'a, CompletionQueueImpl<true, true, true>>>>>,
     #[cfg(feature = "use-tokio")]
@@ -881,6 +1152,12 @@ pub struct CqAsyncReadOwned<'a> {
     >,
 }
 
+impl Drop for CqAsyncReadOwned<'_> {
+    fn drop(&mut self) {
+        self.release_reader();
+    }
+}
+
 impl<'a> Future for CqAsyncReadOwned<'a> {
     type Output = Result<(), Error>;
 
@@ -889,207 +1166,87 @@ impl<'a> Future for CqAsyncReadOwned<'a> {
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Self::Output> {
         let mut_self = self.get_mut();
-        // println!("Polling cq for {} entries", mut_self.num_entries);
-        // let mut first = true;
+        let cq: &'a AsyncCompletionQueueImpl = mut_self.cq;
         loop {
-            // let cq_guard = mut_self.cq.base.get_ref().entry_buff.write();
-            // let mut blocked = mut_self.cq.base.get_ref().block.write();
-            // if *blocked != 0 {
-            //     println!("BLOCKED {}", *blocked);
-            //     assert!(*blocked == 0);
-            // }
-            // mut_self.context.0.set_waker(cx.waker().clone());
             if mut_self.context.ready() {
+                mut_self.release_reader();
                 return std::task::Poll::Ready(Ok(()));
             }
-            // println!("About to block in CQ");
-
-            // TODO: Reenable blocking on the file descriptor becoming readable
-            // Note that we probably need to make sure no one polls the cq before we
-            // actually create the future
-            if mut_self.buf.is_none() {
-                let (err, _guard) = match &mut_self.cq.base {
-                    AsyncCompletionQueueImplBase::BlockingCq(async_cq) => {
-                        let can_wait = mut_self.cq.trywait();
-                        if can_wait.is_err() {
-                            // println!("Cannot block");
-                            let to_ret = (async_cq.get_ref().read(1), None);
-                            to_ret
-                        } else {
-                            // TODO: Do we need to skip calling readable if we were awaken?
-                            // Does being awaken means that readable has returned true?
-                            if mut_self.fut.is_none() {
-                                // println!("Can wait in CQ {}", can_wait.is_ok());
-                                // mut_self.cq.trywait().unwrap();
-                                mut_self.fut = Some(Box::pin(async_cq.readable()))
-                            }
-                            #[allow(clippy::let_unit_value)]
-                            // drop(blocked);
-                            let _guard =
-                                ready!(mut_self.fut.as_mut().unwrap().as_mut().poll(cx)).unwrap();
-
-                            #[allow(clippy::let_underscore_future)]
-                            let _ = mut_self.fut.take().unwrap();
-                            // println!("Did not block");
-                            // let cq_guard = mut_self.cq.base.get_ref().entry_buff.write();
-
-                            (async_cq.get_ref().read(1), Some(_guard))
-                        }
-                    }
-                    AsyncCompletionQueueImplBase::SpinningCQ(cq) => {
-                        let to_ret = (cq.read(1), None);
-                        to_ret
+
+            let async_cq = match &cq.base {
+                AsyncCompletionQueueImplBase::BlockingCq(async_cq) => async_cq,
+                AsyncCompletionQueueImplBase::SpinningCQ(_) => {
+                    mut_self.drain()?;
+                    if mut_self.context.ready() {
+                        return std::task::Poll::Ready(Ok(()));
                     }
-                };
+                    cx.waker().wake_by_ref();
+                    return std::task::Poll::Pending;
+                }
+            };
 
-                match err {
-                    Err(error) => {
-                        if !matches!(error.kind, crate::error::ErrorKind::TryAgain) {
-                            // println!("Found error!");
-                            if matches!(error.kind, crate::error::ErrorKind::ErrorAvailable) {
-                                let mut err = CompletionError::new();
-                                mut_self.cq.readerr_in(&mut err, 0)?;
-                                if err.c_err.op_context as usize
-                                    == mut_self.context.inner() as usize
-                                {
-                                    // println!("Found error in context!");
-                                    // panic!("Error {:?}", err.error());
-                                    return std::task::Poll::Ready(Err(
-                                        Error::from_completion_queue_err(err),
-                                    ));
-                                } else {
-                                    // println!("Found error in context but not for me!");
-                                    // We need to return the error to the context.
-                                    // A null op_context means the erroring op (e.g. an
-                                    // inject-class atomic) never registered a context, so
-                                    // there is no owner to route this error to; drop it
-                                    // instead of dereferencing a null pointer.
-                                    match mut_self.context.0 {
-                                        ContextType::Context1(_) => {
-                                            if let Some(ctx) = unsafe {
-                                                (err.c_err.op_context as *mut std::ffi::c_void
-                                                    as *mut crate::Context1)
-                                                    .as_mut()
-                                            } {
-                                                ctx.set_completion_done(Err(
-                                                    Error::from_completion_queue_err(err),
-                                                ));
-                                            } else {
-                                                panic!(
-                                                    "libfabric: dropping anonymous CQ completion error (no owning context): {}",
-                                                    err.error()
-                                                );
-                                            }
-                                        }
-                                        ContextType::Context2(_) => {
-                                            if let Some(ctx) = unsafe {
-                                                (err.c_err.op_context as *mut std::ffi::c_void
-                                                    as *mut crate::Context2)
-                                                    .as_mut()
-                                            } {
-                                                ctx.set_completion_done(Err(
-                                                    Error::from_completion_queue_err(err),
-                                                ));
-                                            } else {
-                                                panic!(
-                                                    "libfabric: dropping anonymous CQ completion error (no owning context): {}",
-                                                    err.error()
-                                                );
-                                            }
-                                        }
-                                    }
-                                    cx.waker().wake_by_ref();
-                                    return std::task::Poll::Pending;
-                                }
-                            }
-                            else {
-                                return std::task::Poll::Ready(Err(Error::from_err_code(error.c_err)));
-                            }
-                        } else {
-                            // println!("Will continue");
-                            #[cfg(feature = "use-tokio")]
-                            if let Some(mut guard) = _guard {
-                                if mut_self.cq.pending_entries.load(Ordering::SeqCst) == 0 {
-                                    guard.clear_ready()
-                                }
-                            }
-                            cx.waker().wake_by_ref();
-                            return std::task::Poll::Pending;
-                        }
+            if !mut_self.reader {
+                if !cq.fanout.try_acquire_reader() {
+                    cq.fanout.register(cx.waker());
+                    // The reader might have handed out our completion or stepped down in the meantime
+                    if mut_self.context.ready() {
+                        continue;
                     }
-                    Ok(mut completion) => {
-                        mut_self.buf = completion.pop();
+                    if !cq.fanout.try_acquire_reader() {
+                        return std::task::Poll::Pending;
                     }
                 }
+                mut_self.reader = true;
             }
-            let done = match &mut mut_self.buf {
-                Some(d) => unsafe {
-                    // println!("Signaling {:?}", data[0].c_entry.op_context);
-                    let mut done = true;
-                    match mut_self.context.0 {
-                        ContextType::Context1(_) => {
-                            // A null op_context means the completing op (e.g. an
-                            // inject-class write) never registered a context, so
-                            // there is no owner to route this completion to; drop
-                            // it instead of dereferencing a null pointer.
-                            match (d.op_context() as *mut std::ffi::c_void
-                                as *mut crate::Context1)
-                                .as_mut()
-                            {
-                                None => {
-                                    mut_self.buf = None;
-                                    done = false;
-                                }
-                                Some(context) => {
-                                    if d.op_context() as usize != mut_self.context.inner() as usize
-                                    {
-                                        context.set_completion_done(Ok(d.clone()));
-                                        mut_self.buf = None;
-                                        done = false;
-                                    } else {
-                                        context.set_completion_done(Ok(d.clone()));
-                                    }
-                                }
-                            }
-                        }
-                        ContextType::Context2(_) => {
-                            match (d.op_context() as *mut std::ffi::c_void
-                                as *mut crate::Context2)
-                                .as_mut()
-                            {
-                                None => {
-                                    mut_self.buf = None;
-                                    done = false;
-                                }
-                                Some(context) => {
-                                    if d.op_context() as usize != mut_self.context.inner() as usize
-                                    {
-                                        context.set_completion_done(Ok(d.clone()));
-                                        mut_self.buf = None;
-                                        done = false;
-                                    } else {
-                                        context.set_completion_done(Ok(d.clone()));
-                                        mut_self.buf = None;
-                                    }
-                                }
-                            }
-                        }
+
+            if mut_self.fut.is_none() {
+                // The queue must be empty for fi_trywait to succeed
+                if let Err(error) = mut_self.drain() {
+                    mut_self.release_reader();
+                    return std::task::Poll::Ready(Err(error));
+                }
+                if mut_self.context.ready() {
+                    continue;
+                }
+                match cq.trywait() {
+                    Ok(()) => mut_self.fut = Some(Box::pin(async_cq.readable())),
+                    Err(error) if matches!(error.kind, crate::error::ErrorKind::TryAgain) => {
+                        // Completions arrived in the meantime or the provider needs to make progress
+                        cx.waker().wake_by_ref();
+                        return std::task::Poll::Pending;
+                    }
+                    Err(error) => {
+                        mut_self.release_reader();
+                        return std::task::Poll::Ready(Err(error));
                     }
+                }
+            }
 
-                    done
-                },
-                None => {
-                    panic!("Should always have something to read when ready");
+            // The reader is parked too, so that it is woken up if its completion is read by someone else
+            // (e.g., an operation being canceled)
+            cq.fanout.register(cx.waker());
+            #[allow(clippy::let_unit_value)]
+            let _guard = match mut_self.fut.as_mut().unwrap().as_mut().poll(cx) {
+                std::task::Poll::Ready(guard) => guard.unwrap(),
+                std::task::Poll::Pending => {
+                    if mut_self.context.ready() {
+                        continue;
+                    }
+                    return std::task::Poll::Pending;
                 }
             };
-            if done {
-                // If we handled all contexts, we can return Ready
-                // Otherwise we need to poll again to handle the remaining ones
-                // println!("All done");
-                return std::task::Poll::Ready(Ok(()));
-            } else {
-                cx.waker().wake_by_ref();
-                return std::task::Poll::Pending;
+            mut_self.fut = None;
+
+            let res = mut_self.drain();
+            #[cfg(feature = "use-tokio")]
+            if res.is_ok() && !mut_self.context.ready() {
+                // The queue is empty, the next fi_trywait will tell whether new completions arrived
+                let mut guard = _guard;
+                guard.clear_ready();
+            }
+            if let Err(error) = res {
+                mut_self.release_reader();
+                return std::task::Poll::Ready(Err(error));
             }
         }
     }
@@ -1116,6 +1273,7 @@ pub struct CompletionQueueBuilder<'a> {
     cq_attr: CompletionQueueAttr,
     ctx: Option<&'a mut Context>,
     default_buff_size: usize,
+    context_pool: Option<ContextPool>,
 }
 
 impl<'a> CompletionQueueBuilder<'a> {
@@ -1128,6 +1286,7 @@ impl<'a> CompletionQueueBuilder<'a> {
             cq_attr: CompletionQueueAttr::new(),
             ctx: None,
             default_buff_size: 10,
+            context_pool: None,
         }
     }
 }
@@ -1174,11 +1333,26 @@ impl<'a> CompletionQueueBuilder<'a> {
             ctx: Some(ctx),
             cq_attr: self.cq_attr,
             default_buff_size: self.default_buff_size,
+            context_pool: self.context_pool,
         }
     }
 
+    /// Attaches `pool` to the queue, enabling the asynchronous operations that do not take a `ctx` argument
+    /// on the endpoints bound to it.
+    pub fn context_pool(mut self, pool: ContextPool) -> Self {
+        self.context_pool = Some(pool);
+        self
+    }
+
+    /// Constructs a new [CompletionQueue] with the configurations requested so far.
+    ///
+    /// Unless the `async-cqs-spin` feature is enabled, the queue uses a file descriptor wait object
+    /// (see [Self::build_blocking_cq]), falling back to a polled queue (see [Self::build_spinning_cq]) if such a
+    /// queue cannot be created or waited on (i.e., `fi_trywait` fails on the new queue).
+    /// Errors that are not specific to the wait object are reported by the creation of the polled queue.
+    #[allow(unused_mut)]
     pub fn build<EQ: ?Sized + 'static + SyncSend>(
-        self,
+        mut self,
         domain: &'a DomainBase<EQ>,
     ) -> Result<CompletionQueue<AsyncCompletionQueueImpl>, crate::error::Error> {
         #[cfg(feature = "async-cqs-spin")]
@@ -1187,13 +1361,33 @@ impl<'a> CompletionQueueBuilder<'a> {
         }
         #[cfg(not(feature = "async-cqs-spin"))]
         {
-            self.build_blocking_cq(domain)
+            let mut blocking_attr = self.cq_attr.clone();
+            blocking_attr.wait_obj(crate::enums::WaitObj::Fd);
+            match CompletionQueue::<AsyncCompletionQueueImpl>::new_blocking(
+                domain,
+                blocking_attr,
+                self.ctx.as_deref_mut(),
+                self.default_buff_size,
+                self.context_pool.clone(),
+            ) {
+                // The queue is empty, so fi_trywait only fails if the wait object cannot be used
+                Ok(cq) => match cq.inner.trywait() {
+                    Ok(()) => Ok(cq),
+                    Err(err) if matches!(err.kind, crate::error::ErrorKind::TryAgain) => Ok(cq),
+                    Err(_) => {
+                        drop(cq);
+                        self.build_spinning_cq(domain)
+                    }
+                },
+                Err(_) => self.build_spinning_cq(domain),
+            }
         }
     }
 
-    /// Constructs a new [CompletionQueue] with the configurations requested so far.
+    /// Constructs a new [CompletionQueue] with the configurations requested so far, using a file descriptor
+    /// wait object that asynchronous operations block on until completions are available.
     ///
-    /// Corresponds to creating a `fi_cq_attr`, setting its fields to the requested ones,
+    /// Corresponds to creating a `fi_cq_attr`, setting its fields to the requested ones (with `wait_obj` set to `FI_WAIT_FD`),
     /// and passing it to the `fi_cq_open` call with an optional `context`.
     pub fn build_blocking_cq<EQ: ?Sized + 'static + SyncSend>(
         mut self,
@@ -1205,6 +1399,7 @@ impl<'a> CompletionQueueBuilder<'a> {
             self.cq_attr,
             self.ctx,
             self.default_buff_size,
+            self.context_pool,
         )
     }
 
@@ -1221,6 +1416,7 @@ impl<'a> CompletionQueueBuilder<'a> {
             self.cq_attr,
             self.ctx,
             self.default_buff_size,
+            self.context_pool,
         )
     }
 }
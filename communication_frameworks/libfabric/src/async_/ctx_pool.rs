@@ -0,0 +1,177 @@
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{info::InfoEntry, Context, Context1, Context2, ContextType, MyRc};
+
+struct PoolSlot {
+    in_use: AtomicBool,
+    ctx: UnsafeCell<Context>,
+}
+
+pub(crate) struct ContextPoolImpl {
+    slots: Box<[PoolSlot]>,
+    next: AtomicUsize,
+    overflow_id: AtomicUsize,
+    context2: bool,
+}
+
+// Each slot is only accessed by the [PooledContext] that successfully marked it as in use
+unsafe impl Send for ContextPoolImpl {}
+unsafe impl Sync for ContextPoolImpl {}
+
+/// A lock-free pool of recyclable [Context]s.
+///
+/// Attached to an asynchronous completion queue through [crate::async_::cq::CompletionQueueBuilder::context_pool],
+/// it provides the contexts of the asynchronous operations that do not take a `ctx` argument
+/// (e.g., [crate::async_::comm::message::AsyncSendEpPooled::send_to_pooled_async] or the pooled RMA, atomic
+/// and collective operations), saving a heap allocation per operation.
+///
+/// Cloning a `ContextPool` returns a handle to the same pool.
+#[derive(Clone)]
+pub struct ContextPool {
+    pub(crate) inner: MyRc<ContextPoolImpl>,
+}
+
+impl ContextPool {
+    /// Creates a pool of `capacity` contexts suitable for the provider described by `info`
+    /// (i.e., `fi_context2` if the provider requires `FI_CONTEXT2`, `fi_context` otherwise).
+    pub fn new<I>(info: &InfoEntry<I>, capacity: usize) -> Self {
+        let slots = (0..capacity)
+            .map(|_| PoolSlot {
+                in_use: AtomicBool::new(false),
+                ctx: UnsafeCell::new(info.allocate_context()),
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        Self {
+            inner: MyRc::new(ContextPoolImpl {
+                slots,
+                next: AtomicUsize::new(0),
+                overflow_id: AtomicUsize::new(usize::MAX / 2),
+                context2: !info.mode().is_context(),
+            }),
+        }
+    }
+
+    /// Returns the number of contexts held by the pool.
+    pub fn capacity(&self) -> usize {
+        self.inner.slots.len()
+    }
+
+    /// Takes a context from the pool.
+    ///
+    /// The context goes back to the pool when the returned [PooledContext] is dropped. If every context of the
+    /// pool is in use, a new one is allocated and freed on drop instead.
+    pub fn acquire(&self) -> PooledContext {
+        let len = self.inner.slots.len();
+        let start = self.inner.next.fetch_add(1, Ordering::Relaxed);
+        for i in 0..len {
+            let index = (start + i) % len;
+            if self.inner.slots[index]
+                .in_use
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                return PooledContext(PooledContextType::Pooled(self.inner.clone(), index));
+            }
+        }
+
+        PooledContext(PooledContextType::Overflow(self.allocate_overflow()))
+    }
+
+    // Overflow contexts have the same type as the pooled ones, even if the pool is empty
+    fn allocate_overflow(&self) -> Context {
+        let id = self.inner.overflow_id.fetch_add(1, Ordering::Relaxed);
+        if self.inner.context2 {
+            Context(ContextType::Context2(Box::new(Context2::new(id))))
+        } else {
+            Context(ContextType::Context1(Box::new(Context1::new(id))))
+        }
+    }
+}
+
+enum PooledContextType {
+    Pooled(MyRc<ContextPoolImpl>, usize),
+    Overflow(Context),
+}
+
+/// A [Context] borrowed from a [ContextPool], returned to it on drop.
+pub struct PooledContext(PooledContextType);
+
+impl std::ops::Deref for PooledContext {
+    type Target = Context;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            PooledContextType::Pooled(pool, index) => unsafe { &*pool.slots[*index].ctx.get() },
+            PooledContextType::Overflow(ctx) => ctx,
+        }
+    }
+}
+
+impl std::ops::DerefMut for PooledContext {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            PooledContextType::Pooled(pool, index) => unsafe {
+                &mut *pool.slots[*index].ctx.get()
+            },
+            PooledContextType::Overflow(ctx) => ctx,
+        }
+    }
+}
+
+impl Drop for PooledContext {
+    fn drop(&mut self) {
+        if let PooledContextType::Pooled(pool, index) = &self.0 {
+            let slot = &pool.slots[*index];
+            unsafe { (*slot.ctx.get()).reset() };
+            slot.in_use.store(false, Ordering::Release);
+        }
+    }
+}
+
+/// Takes a context from the [ContextPool] attached to `cq`.
+///
+/// Fails with [crate::error::ErrorKind::InvalidArgument] if `cq` was built without a pool.
+pub(crate) fn acquire_from(
+    cq: &(impl crate::async_::cq::AsyncCq + ?Sized),
+) -> Result<PooledContext, crate::error::Error> {
+    match cq.context_pool() {
+        Some(pool) => Ok(pool.acquire()),
+        None => Err(crate::error::Error::from_err_code(
+            libfabric_sys::FI_EINVAL,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::info::Info;
+
+    use super::ContextPool;
+
+    #[test]
+    fn pool_recycles_contexts() {
+        let info = Info::new(&crate::info::libfabric_version()).get().unwrap();
+        let entry = info.into_iter().next().unwrap();
+        let pool = ContextPool::new(&entry, 2);
+        assert_eq!(pool.capacity(), 2);
+
+        let first = pool.acquire();
+        let second = pool.acquire();
+        let first_ptr = first.inner();
+        assert_ne!(first_ptr, second.inner());
+
+        // The pool is exhausted, so the next context is allocated on the side
+        let overflow = pool.acquire();
+        assert_ne!(overflow.inner(), first_ptr);
+        assert_ne!(overflow.inner(), second.inner());
+        drop(overflow);
+
+        drop(first);
+        drop(second);
+        let pooled: Vec<_> = (0..2).map(|_| pool.acquire().inner()).collect();
+        assert!(pooled.contains(&first_ptr));
+    }
+}
@@ -5,7 +5,9 @@ use super::{
     connless_ep::UninitConnectionlessEndpoint,
     cq::{AsyncCq, CompletionQueue},
     eq::AsyncReadEq,
+    xcontext::SharedRxContext,
 };
+use crate::xcontext::SharedTxContext;
 use crate::av::AVSyncMode;
 use crate::{
     av::{AddressVectorBase},
@@ -281,27 +283,12 @@ impl<EP, CQ: ?Sized + ReadCq> EndpointImplBase<EP, dyn AsyncReadEq, CQ> {
             Ok(())
         }
     }
-
-    // pub fn alias(&self, flags: u64) -> Result<Self, crate::error::Error> {
-    //     Ok(
-    //         Self {
-    //             inner: MyRc::new (self.inner.alias(flags)?),
-    //         }
-    //     )
 }
 
 impl<EP> EndpointBase<EndpointImplBase<EP, dyn AsyncReadEq, dyn AsyncCq>, UninitUnconnected> {
     pub fn bind_cntr(&self) -> IncompleteBindCntr<'_, EP, dyn AsyncReadEq, dyn AsyncCq> {
         self.inner.bind_cntr()
     }
-
-    // pub fn alias(&self, flags: u64) -> Result<Self, crate::error::Error> {
-    //     Ok(
-    //         Self {
-    //             inner: MyRc::new (self.inner.alias(flags)?sss),
-    //         }
-    //     )
-    // }
     pub(crate) fn bind_shared_cq<T: AsyncCq + 'static>(
         &self,
         cq: &CompletionQueue<T>,
@@ -324,6 +311,32 @@ impl<EP> EndpointBase<EndpointImplBase<EP, dyn AsyncReadEq, dyn AsyncCq>, Uninit
     ) -> Result<(), crate::error::Error> {
         self.inner.bind_eq(&eq.inner)
     }
+
+    /// Binds a [SharedRxContext] to the endpoint.
+    ///
+    /// Receive operations must then be posted to the shared context instead of the endpoint, and their
+    /// completions are reported to the receive queue of the endpoint.
+    ///
+    /// The endpoint must already be bound to a completion queue, and its receive queue must be the
+    /// same as the one of the other endpoints bound to `srx`. Otherwise an error of kind
+    /// [NoCompletionQueue](crate::error::ErrorKind::NoCompletionQueue) or
+    /// [InvalidArgument](crate::error::ErrorKind::InvalidArgument) is returned. Binding a second shared
+    /// receive context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_srx<I: 'static>(&self, srx: &SharedRxContext<I>) -> Result<(), crate::error::Error> {
+        self.inner.bind_srx_(&srx.inner)
+    }
+
+    /// Binds a [SharedTxContext] to the endpoint.
+    ///
+    /// Transmit operations of the endpoint will then be queued to the shared context. Binding a second
+    /// shared transmit context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_stx(&self, stx: &SharedTxContext) -> Result<(), crate::error::Error> {
+        self.inner.bind_stx_(&stx.inner)
+    }
 }
 
 impl<EP>
@@ -339,14 +352,6 @@ impl<EP>
     ) -> Result<(), crate::error::Error> {
         self.inner.bind_av(av)
     }
-
-    // pub fn alias(&self, flags: u64) -> Result<Self, crate::error::Error> {
-    //     Ok(
-    //         Self {
-    //             inner: MyRc::new (self.inner.alias(flags)?),
-    //         }
-    //     )
-    // }
     pub(crate) fn bind_shared_cq<T: AsyncCq + 'static>(
         &self,
         cq: &CompletionQueue<T>,
@@ -369,6 +374,32 @@ impl<EP>
     ) -> Result<(), crate::error::Error> {
         self.inner.bind_eq(&eq.inner)
     }
+
+    /// Binds a [SharedRxContext] to the endpoint.
+    ///
+    /// Receive operations must then be posted to the shared context instead of the endpoint, and their
+    /// completions are reported to the receive queue of the endpoint.
+    ///
+    /// The endpoint must already be bound to a completion queue, and its receive queue must be the
+    /// same as the one of the other endpoints bound to `srx`. Otherwise an error of kind
+    /// [NoCompletionQueue](crate::error::ErrorKind::NoCompletionQueue) or
+    /// [InvalidArgument](crate::error::ErrorKind::InvalidArgument) is returned. Binding a second shared
+    /// receive context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_srx<I: 'static>(&self, srx: &SharedRxContext<I>) -> Result<(), crate::error::Error> {
+        self.inner.bind_srx_(&srx.inner)
+    }
+
+    /// Binds a [SharedTxContext] to the endpoint.
+    ///
+    /// Transmit operations of the endpoint will then be queued to the shared context. Binding a second
+    /// shared transmit context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_stx(&self, stx: &SharedTxContext) -> Result<(), crate::error::Error> {
+        self.inner.bind_stx_(&stx.inner)
+    }
 }
 
 // impl<'a, EP> IncompleteBindCq<'a, EP> {
@@ -597,7 +628,11 @@ pub trait AsyncTxEp: AsTypedFid<EpRawFid> {
 }
 
 pub trait AsyncRxEp: AsTypedFid<EpRawFid> {
-    fn retrieve_rx_cq(&self) -> &MyRc<impl AsyncCq + ?Sized>;
+    /// Returns the completion queue receives are reported to.
+    ///
+    /// Fails with [crate::error::ErrorKind::NoCompletionQueue] if there is none yet, e.g., for a shared receive
+    /// context that has not been bound to an endpoint.
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error>;
 }
 
 impl<EP, EQ: ?Sized + AsyncReadEq, CQ: ?Sized + AsyncCq> AsyncCmEp
@@ -625,13 +660,10 @@ impl<EP, EQ: ?Sized + AsyncReadEq, CQ: ?Sized + AsyncCq> AsyncTxEp
 impl<EP, EQ: ?Sized + AsyncReadEq, CQ: ?Sized + AsyncCq> AsyncRxEp
     for EndpointImplBase<EP, EQ, CQ>
 {
-    fn retrieve_rx_cq(&self) -> &MyRc<impl AsyncCq + ?Sized> {
-        match self
-            .cq
-            .get()
-            .expect("Endpoint is not bound to Completion Queue(s)")
-        {
-            EpCq::Shared(rx_cq) | EpCq::Separate(_, rx_cq) => rx_cq,
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error> {
+        match self.cq.get() {
+            Some(EpCq::Shared(rx_cq)) | Some(EpCq::Separate(_, rx_cq)) => Ok(rx_cq),
+            None => Err(crate::error::Error::from_err_code(libfabric_sys::FI_ENOCQ)),
         }
     }
 }
@@ -649,7 +681,7 @@ impl<EP: AsyncTxEp> AsyncTxEp for EndpointBase<EP, Connected> {
 }
 
 impl<EP: AsyncRxEp> AsyncRxEp for EndpointBase<EP, Connected> {
-    fn retrieve_rx_cq(&self) -> &MyRc<impl AsyncCq + ?Sized> {
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error> {
         self.inner.retrieve_rx_cq()
     }
 }
@@ -667,7 +699,7 @@ impl<EP: AsyncTxEp> AsyncTxEp for EndpointBase<EP, Connectionless> {
 }
 
 impl<EP: AsyncRxEp> AsyncRxEp for EndpointBase<EP, Connectionless> {
-    fn retrieve_rx_cq(&self) -> &MyRc<impl AsyncCq + ?Sized> {
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error> {
         self.inner.retrieve_rx_cq()
     }
 }
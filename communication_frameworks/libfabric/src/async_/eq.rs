@@ -1,4 +1,7 @@
-use std::{collections::HashMap, future::Future, pin::Pin, sync::atomic::Ordering, task::ready};
+use std::{
+    collections::HashMap, future::Future, ops::ControlFlow, pin::Pin, sync::atomic::Ordering,
+    task::ready,
+};
 
 use crate::{
     async_::{conn_ep::ConnectionPendingEndpoint, ep::PassiveEndpoint}, cq::{ReadCq, WaitObjectRetrieve}, eq::{Event, EventError, EventQueueAttr, EventQueueBase, EventQueueImpl, ReadEq, WriteEq}, error::{Error, ErrorKind}, fid::{AsRawFid, AsTypedFid, BorrowedTypedFid, EqRawFid, Fid}, Context, MyRc, MyRefCell, SyncSend
@@ -10,6 +13,9 @@ use std::sync::atomic::AtomicUsize;
 #[cfg(feature = "use-tokio")]
 use tokio::io::unix::AsyncFd as Async;
 
+use super::fanout::WakerFanout;
+use crate::eq::peek_progress;
+use crate::progress::Progressable;
 use super::AsyncFid;
 
 pub type EventQueue<T> = EventQueueBase<T>;
@@ -139,6 +145,14 @@ impl<'a> EqType<'a> {
         }
     }
 
+    #[inline]
+    pub(crate) fn fanout(&self) -> &WakerFanout {
+        match self {
+            EqType::Write(e) => &e.fanout,
+            EqType::NoWrite(e) => &e.fanout,
+        }
+    }
+
     #[inline]
     pub(crate) fn using_context2(&self) -> bool {
         match self {
@@ -193,6 +207,7 @@ pub struct EqAsyncRead<'a> {
     event: &'a mut u32,
     eq: EqType<'a>,
     fut: Option<FutType<'a>>,
+    reader: bool,
 }
 
 impl<'a> EqAsyncRead<'a> {
@@ -202,6 +217,7 @@ impl<'a> EqAsyncRead<'a> {
             event,
             eq,
             fut: None,
+            reader: false,
         }
     }
 }
@@ -227,103 +243,138 @@ impl<'a> Guard<'a> {
     }
 }
 
-impl<'a> Future for EqAsyncRead<'a> {
-    type Output = Result<usize, Error>;
+/// Turns the outcome of reading `eq` into the output of the read futures, retrieving the error entry if needed.
+fn finish_read(eq: &EqType, buf: &mut [u8], res: Result<usize, Error>) -> Result<usize, Error> {
+    match res {
+        Err(error) if matches!(error.kind, crate::error::ErrorKind::ErrorAvailable) => {
+            let _len = eq.readerr_in(buf)?;
+            let mut err_event = EventError::new();
+            err_event.c_err = unsafe { std::ptr::read(buf.as_ptr().cast()) };
+            Err(Error::from_event_queue_err(err_event))
+        }
+        res => res,
+    }
+}
 
-    fn poll(
-        self: std::pin::Pin<&mut Self>,
-        cx: &mut std::task::Context<'_>,
-    ) -> std::task::Poll<Self::Output> {
-        let ev = self.get_mut();
-        loop {
-            // println!("About to block waiting for event");
-            let (err, _guard) = if ev.eq.trywait().is_err() {
-                (ev.eq.read_in(ev.buf, ev.event), None)
-            } else {
-                if ev.fut.is_none() {
-                    ev.fut = Some(match ev.eq {
+/// Gives up the reader role of `eq`, if held, so that another future can block on the queue.
+fn release_reader(eq: &EqType, fut: &mut Option<FutType>, reader: &mut bool) {
+    if *reader {
+        *reader = false;
+        *fut = None;
+        eq.fanout().release_reader();
+    }
+}
+
+/// Reads the next entry of `eq`.
+///
+/// Only the future holding the reader role of the queue blocks on its file descriptor, after making sure
+/// with `fi_trywait` that the queue is empty. The other futures are parked until the reader steps down.
+fn poll_read_in<'a>(
+    eq: &EqType<'a>,
+    fut: &mut Option<FutType<'a>>,
+    reader: &mut bool,
+    buf: &mut [u8],
+    event: &mut u32,
+    cx: &mut std::task::Context<'_>,
+) -> std::task::Poll<Result<usize, Error>> {
+    loop {
+        if !*reader {
+            let fanout = eq.fanout();
+            if !fanout.try_acquire_reader() {
+                fanout.register(cx.waker());
+                // The reader might have stepped down in the meantime
+                if !fanout.try_acquire_reader() {
+                    return std::task::Poll::Pending;
+                }
+            }
+            *reader = true;
+        }
+
+        if fut.is_none() {
+            // The queue must be empty for fi_trywait to succeed
+            match eq.read_in(buf, event) {
+                Err(error) if matches!(error.kind, crate::error::ErrorKind::TryAgain) => {}
+                res => return std::task::Poll::Ready(finish_read(eq, buf, res)),
+            }
+            match eq.trywait() {
+                Ok(()) => {
+                    *fut = Some(match *eq {
                         EqType::Write(e) => FutType::Write(Box::pin(e.base.readable())),
                         EqType::NoWrite(e) => FutType::NoWrite(Box::pin(e.base.readable())),
                     })
                 }
-
-                // Tokio returns something we need, async_std returns ()
-                #[allow(clippy::unit_arg)]
-                let _guard = match ev.fut.as_mut().unwrap() {
-                    FutType::Write(e) => Guard::Write(ready!(e.as_mut().poll(cx)).unwrap()),
-                    FutType::NoWrite(e) => Guard::NoWrite(ready!(e.as_mut().poll(cx)).unwrap()),
-                };
-
-                // We only need to reset the option to none
-                #[allow(clippy::let_underscore_future)]
-                let _ = ev.fut.take().unwrap();
-                (ev.eq.read_in(ev.buf, ev.event), Some(_guard))
-            };
-            match err {
+                Err(error) if matches!(error.kind, crate::error::ErrorKind::TryAgain) => {
+                    // Events arrived in the meantime
+                    cx.waker().wake_by_ref();
+                    return std::task::Poll::Pending;
+                }
                 Err(error) => {
-                    if !matches!(error.kind, crate::error::ErrorKind::TryAgain) {
-                        if matches!(error.kind, crate::error::ErrorKind::ErrorAvailable) {
-                            let _len = ev.eq.readerr_in(ev.buf)?;
-                            let mut err_event = EventError::new();
-                            err_event.c_err = unsafe { std::ptr::read(ev.buf.as_ptr().cast()) };
-                            return std::task::Poll::Ready(Err(Error::from_event_queue_err(
-                                err_event,
-                            )));
-                        }
-                    } else {
-                        // println!("Will continue");
-
-                        #[cfg(feature = "use-tokio")]
-                        if let Some(mut guard) = _guard {
-                            match ev.eq {
-                                #[cfg(feature = "thread-safe")]
-                                EqType::Write(e) => {
-                                    if e.pending_cm_entries.read().is_empty()
-                                        && e.pending_entries.load(Ordering::SeqCst) == 0
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                                #[cfg(not(feature = "thread-safe"))]
-                                EqType::Write(e) => {
-                                    if e.pending_cm_entries.borrow().is_empty()
-                                        && e.pending_entries.borrow().is_empty()
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                                #[cfg(feature = "thread-safe")]
-                                EqType::NoWrite(e) => {
-                                    if e.pending_cm_entries.read().is_empty()
-                                        && e.pending_entries.load(Ordering::SeqCst) == 0
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                                #[cfg(not(feature = "thread-safe"))]
-                                EqType::NoWrite(e) => {
-                                    if e.pending_cm_entries.borrow().is_empty()
-                                        && e.pending_entries.borrow().is_empty()
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                            }
-                        }
-                        continue;
-                    }
+                    release_reader(eq, fut, reader);
+                    return std::task::Poll::Ready(Err(error));
+                }
+            }
+        }
+
+        // Tokio returns something we need, async_std returns ()
+        #[allow(clippy::unit_arg)]
+        let _guard = match fut.as_mut().unwrap() {
+            FutType::Write(e) => Guard::Write(ready!(e.as_mut().poll(cx)).unwrap()),
+            FutType::NoWrite(e) => Guard::NoWrite(ready!(e.as_mut().poll(cx)).unwrap()),
+        };
+        *fut = None;
+
+        match eq.read_in(buf, event) {
+            Err(error) if matches!(error.kind, crate::error::ErrorKind::TryAgain) => {
+                // The queue is empty, the next fi_trywait will tell whether new events arrived
+                #[cfg(feature = "use-tokio")]
+                {
+                    let mut guard = _guard;
+                    guard.clear_ready();
                 }
-                Ok(len) => return std::task::Poll::Ready(Ok(len)),
             }
+            res => return std::task::Poll::Ready(finish_read(eq, buf, res)),
         }
     }
 }
 
+impl<'a> Future for EqAsyncRead<'a> {
+    type Output = Result<usize, Error>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let ev = self.get_mut();
+        let res = ready!(poll_read_in(
+            &ev.eq,
+            &mut ev.fut,
+            &mut ev.reader,
+            ev.buf,
+            ev.event,
+            cx
+        ));
+        release_reader(&ev.eq, &mut ev.fut, &mut ev.reader);
+        std::task::Poll::Ready(res)
+    }
+}
+
+impl Drop for EqAsyncRead<'_> {
+    fn drop(&mut self) {
+        release_reader(&self.eq, &mut self.fut, &mut self.reader);
+    }
+}
+
+/// Reads the entries of an event queue on behalf of [AsyncEventEq].
+///
+/// Unlike [EqAsyncRead], the reader role is kept once an entry is read, until [EqAsyncReadOwned::release_reader]
+/// is called or the future is dropped, so that the entry can be handed over to its recipient before the parked
+/// futures are woken up.
 struct EqAsyncReadOwned<'a> {
     buf: Vec<u8>,
     event: u32,
     eq: EqType<'a>,
     fut: Option<FutType<'a>>,
+    reader: bool,
 }
 
 impl<'a> EqAsyncReadOwned<'a> {
@@ -333,8 +384,19 @@ impl<'a> EqAsyncReadOwned<'a> {
             event: 0,
             eq,
             fut: None,
+            reader: false,
         }
     }
+
+    fn release_reader(&mut self) {
+        release_reader(&self.eq, &mut self.fut, &mut self.reader);
+    }
+}
+
+impl Drop for EqAsyncReadOwned<'_> {
+    fn drop(&mut self) {
+        self.release_reader();
+    }
 }
 
 impl<'a> Future for EqAsyncReadOwned<'a> {
@@ -345,92 +407,14 @@ impl<'a> Future for EqAsyncReadOwned<'a> {
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Self::Output> {
         let ev = self.get_mut();
-        loop {
-            // println!("About to block waiting for event");
-            let (err, _guard) = if ev.eq.trywait().is_err() {
-                // println!("Could not block");
-                (ev.eq.read_in(&mut ev.buf, &mut ev.event), None)
-            } else {
-                if ev.fut.is_none() {
-                    ev.fut = Some(match ev.eq {
-                        EqType::Write(e) => FutType::Write(Box::pin(e.base.readable())),
-                        EqType::NoWrite(e) => FutType::NoWrite(Box::pin(e.base.readable())),
-                    })
-                }
-
-                // Tokio returns something we need, async_std returns ()
-                #[allow(clippy::unit_arg)]
-                let _guard = match ev.fut.as_mut().unwrap() {
-                    FutType::Write(e) => Guard::Write(ready!(e.as_mut().poll(cx)).unwrap()),
-                    FutType::NoWrite(e) => Guard::NoWrite(ready!(e.as_mut().poll(cx)).unwrap()),
-                };
-
-                // println!("Did not block");
-
-                // We only need to reset the option to none
-                #[allow(clippy::let_underscore_future)]
-                let _ = ev.fut.take().unwrap();
-                (ev.eq.read_in(&mut ev.buf, &mut ev.event), Some(_guard))
-            };
-            match err {
-                Err(error) => {
-                    if !matches!(error.kind, crate::error::ErrorKind::TryAgain) {
-                        if matches!(error.kind, crate::error::ErrorKind::ErrorAvailable) {
-                            let _len = ev.eq.readerr_in(&mut ev.buf)?;
-                            let mut err_event = EventError::new();
-                            err_event.c_err = unsafe { std::ptr::read(ev.buf.as_ptr().cast()) };
-                            return std::task::Poll::Ready(Err(Error::from_event_queue_err(
-                                err_event,
-                            )));
-                        } else {
-                            return std::task::Poll::Ready(Err(error));
-                        }
-                    } else {
-                        // println!("Will continue");
-                        #[cfg(feature = "use-tokio")]
-                        match _guard {
-                            Some(mut guard) => match ev.eq {
-                                #[cfg(feature = "thread-safe")]
-                                EqType::Write(e) => {
-                                    if e.pending_cm_entries.read().is_empty()
-                                        && e.pending_entries.load(Ordering::SeqCst) == 0
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                                #[cfg(not(feature = "thread-safe"))]
-                                EqType::Write(e) => {
-                                    if e.pending_cm_entries.borrow().is_empty()
-                                        && e.pending_entries.borrow().is_empty()
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                                #[cfg(feature = "thread-safe")]
-                                EqType::NoWrite(e) => {
-                                    if e.pending_cm_entries.borrow().is_empty()
-                                        && e.pending_entries.borrow().is_empty()
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                                #[cfg(not(feature = "thread-safe"))]
-                                EqType::NoWrite(e) => {
-                                    if e.pending_cm_entries.read().is_empty()
-                                        && e.pending_entries.load(Ordering::SeqCst) == 0
-                                    {
-                                        guard.clear_ready()
-                                    }
-                                }
-                            },
-                            None => {}
-                        }
-                        continue;
-                    }
-                }
-                Ok(len) => return std::task::Poll::Ready(Ok(len)),
-            }
-        }
+        poll_read_in(
+            &ev.eq,
+            &mut ev.fut,
+            &mut ev.reader,
+            &mut ev.buf,
+            &mut ev.event,
+            cx,
+        )
     }
 }
 
@@ -451,6 +435,18 @@ impl<const WRITE: bool> EventQueue<AsyncEventQueueImpl<WRITE>> {
     }
 }
 
+impl<const WRITE: bool> Progressable for EventQueue<AsyncEventQueueImpl<WRITE>> {
+    fn progress_once(&self) -> Result<usize, Error> {
+        peek_progress(self.inner.as_ref())
+    }
+
+    fn progressable_clone(&self) -> Box<dyn Progressable> {
+        Box::new(Self {
+            inner: self.inner.clone(),
+        })
+    }
+}
+
 pub trait AsyncWaitEq{
     fn async_wait_conn_req<'a, T>(
         &'a self,
@@ -598,6 +594,7 @@ pub struct AsyncEventQueueImpl<const WRITE: bool> {
     pending_err_entries: MyRefCell<HashMap<Fid, Vec<Result<Event, Error>>>>,
     #[allow(clippy::type_complexity)]
     pending_cm_entries: MyRefCell<HashMap<(u32, Fid), Vec<Result<Event, Error>>>>,
+    fanout: WakerFanout,
 }
 
 impl<const WRITE: bool> SyncSend for AsyncEventQueueImpl<WRITE> {}
@@ -609,10 +606,12 @@ impl<const WRITE: bool> AsyncEventQueueImpl<WRITE> {
         context: *mut std::ffi::c_void,
     ) -> Result<Self, crate::error::Error> {
         Ok(Self {
-            base: Async::new(EventQueueImpl::new(fabric, attr, context)?).unwrap(),
+            base: Async::new(EventQueueImpl::new(fabric, attr, context)?)
+                .map_err(crate::error::Error::from_io_err)?,
             pending_entries: AtomicUsize::new(0),
             pending_cm_entries: MyRefCell::new(HashMap::new()),
             pending_err_entries: MyRefCell::new(HashMap::new()),
+            fanout: WakerFanout::new(),
         })
     }
 
@@ -624,21 +623,13 @@ impl<const WRITE: bool> AsyncEventQueueImpl<WRITE> {
         entry: Result<Event, Error>,
     ) {
         #[cfg(feature = "thread-safe")]
-        let mut vec = self.pending_cm_entries.write();
+        let mut entries = self.pending_cm_entries.write();
         #[cfg(not(feature = "thread-safe"))]
-        let mut vec = self.pending_cm_entries.borrow_mut();
-        if let Some(vec) = vec.get_mut(&(event_type, *req_fid)) {
-            vec.push(entry);
-        } else {
-            #[cfg(feature = "thread-safe")]
-            self.pending_cm_entries
-                .write()
-                .insert((event_type, *req_fid), vec![entry]);
-            #[cfg(not(feature = "thread-safe"))]
-            self.pending_cm_entries
-                .borrow_mut()
-                .insert((event_type, *req_fid), vec![entry]);
-        }
+        let mut entries = self.pending_cm_entries.borrow_mut();
+        entries
+            .entry((event_type, *req_fid))
+            .or_default()
+            .push(entry);
     }
 
     // #[inline]
@@ -803,6 +794,51 @@ impl<'a> AsyncEventEq<'a> {
     }
 }
 
+impl<'a> AsyncEventEq<'a> {
+    /// Looks for an entry handed over to this future by the reader of the queue.
+    ///
+    /// Breaks with the entry if there is one, otherwise continues with the id of the awaited context (0 if none).
+    fn take_ready(&mut self) -> ControlFlow<Result<Event, Error>, usize> {
+        match &mut self.ctx {
+            None => {
+                if self.event_type == libfabric_sys::FI_CONNREQ
+                    || self.event_type == libfabric_sys::FI_CONNECTED
+                    || self.event_type == libfabric_sys::FI_SHUTDOWN
+                {
+                    if let Some(entry) = self.fut.eq.remove_cm_entry(self.event_type, &self.req_fid) {
+                        return ControlFlow::Break(entry);
+                    } else if let Some(err_entry) = self.fut.eq.remove_err_entry(&self.req_fid) {
+                        return ControlFlow::Break(err_entry);
+                    }
+                }
+                ControlFlow::Continue(0)
+            }
+            Some(ctx) => {
+                if let Some(entry) = self.fut.eq.remove_cm_entry(self.event_type, &self.req_fid) {
+                    return ControlFlow::Break(entry);
+                }
+                if ctx.ready() {
+                    let state = ctx.state().take();
+                    ctx.reset();
+                    self.fut.eq.remove_pending_entry();
+                    match state {
+                        Some(state) => match state {
+                            crate::ContextState::Cq(_) => {
+                                panic!("Should never find completions here")
+                            }
+                            crate::ContextState::Eq(event) => return ControlFlow::Break(event),
+                        },
+                        None => {
+                            panic!("Should always be set when context is ready")
+                        }
+                    }
+                }
+                ControlFlow::Continue(ctx.inner() as usize)
+            }
+        }
+    }
+}
+
 impl<'a> Future for AsyncEventEq<'a> {
     type Output = Result<Event, Error>;
 
@@ -811,52 +847,36 @@ impl<'a> Future for AsyncEventEq<'a> {
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Self::Output> {
         let ev = self.get_mut();
+        let res = ev.poll_event(cx);
+        if res.is_ready() {
+            // Any entry read by this future has reached its recipient by now, let another future read the queue
+            ev.fut.release_reader();
+        }
+        res
+    }
+}
+
+impl<'a> AsyncEventEq<'a> {
+    fn poll_event(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<Event, Error>> {
+        let ev = self;
         let using_ctx2 = ev.fut.eq.using_context2();
         loop {
-            let ctx_id = match &mut ev.ctx {
-                None => {
-                    if ev.event_type == libfabric_sys::FI_CONNREQ
-                        || ev.event_type == libfabric_sys::FI_CONNECTED
-                        || ev.event_type == libfabric_sys::FI_SHUTDOWN
-                    {
-                        if let Some(entry) = ev.fut.eq.remove_cm_entry(ev.event_type, &ev.req_fid) {
-                            return std::task::Poll::Ready(entry);
-                        } else if let Some(err_entry) = ev.fut.eq.remove_err_entry(&ev.req_fid) {
-                            return std::task::Poll::Ready(err_entry);
-                        }
-                    }
-                    0
-                }
-                Some(ctx) => {
-                    if let Some(entry) = ev.fut.eq.remove_cm_entry(ev.event_type, &ev.req_fid) {
-                        return std::task::Poll::Ready(entry);
-                    }
-                    if ctx.ready() {
-                        let state = ctx.state().take();
-                        ctx.reset();
-                        ev.fut.eq.remove_pending_entry();
-                        match state {
-                            Some(state) => match state {
-                                crate::ContextState::Cq(_) => {
-                                    panic!("Should never find completions here")
-                                }
-                                crate::ContextState::Eq(event) => {
-                                    return std::task::Poll::Ready(event)
-                                }
-                            },
-                            None => {
-                                panic!("Should always be set when context is ready")
-                            }
-                        }
-                    }
-                    ctx.inner() as usize
-                }
+            let ctx_id = match ev.take_ready() {
+                ControlFlow::Break(entry) => return std::task::Poll::Ready(entry),
+                ControlFlow::Continue(ctx_id) => ctx_id,
             };
 
             let ready = ev.fut.as_mut().poll(cx);
             let res = match ready {
                 std::task::Poll::Ready(res) => res,
                 std::task::Poll::Pending => {
+                    // The reader might have handed over our entry before we got parked
+                    if let ControlFlow::Break(entry) = ev.take_ready() {
+                        return std::task::Poll::Ready(entry);
+                    }
                     if let Some(cq) = &ev.cq {
                         cx.waker().wake_by_ref();
                         let cq_read = cq.read(0);
@@ -891,8 +911,19 @@ impl<'a> Future for AsyncEventEq<'a> {
                     match entry {
                         // crate::eq::Event::Notify(entry) |
                         crate::eq::Event::MrComplete(ref e) => {
-                            if e.c_entry.context as usize == ctx_id {
+                            if e.c_entry.context as usize == ctx_id
+                                || (ev.event_type == libfabric_sys::FI_MR_COMPLETE
+                                    && ev.req_fid.0 == e.c_entry.fid as usize)
+                            {
                                 return std::task::Poll::Ready(Ok(entry));
+                            } else if e.c_entry.context.is_null() {
+                                // Providers that do not report the registration context can
+                                // only be matched by the fid of the memory region
+                                ev.fut.eq.insert_cm_entry(
+                                    libfabric_sys::FI_MR_COMPLETE,
+                                    &Fid(e.c_entry.fid as usize),
+                                    Ok(entry),
+                                );
                             } else {
                                 ev.fut.eq.insert_pending_entry();
 
@@ -1070,6 +1101,8 @@ impl<'a> Future for AsyncEventEq<'a> {
                 },
             }
 
+            // Dropping the previous reader wakes up the futures parked on the queue, including the recipient
+            // of the entry
             ev.fut = Box::pin(EqAsyncReadOwned::new(ev.fut.eq.clone()));
         }
     }
@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::Waker;
+
+use crate::MyRefCell;
+
+/// Coordinates the futures waiting on the same asynchronous queue.
+///
+/// Only one future at a time, the reader, waits for the file descriptor of the queue to become readable and
+/// reads its entries. The other futures park their waker here and are woken up every time the reader hands
+/// out entries or steps down, so that a single readiness notification serves every outstanding future.
+pub(crate) struct WakerFanout {
+    reader: AtomicBool,
+    waiters: MyRefCell<Vec<Waker>>,
+}
+
+impl WakerFanout {
+    pub(crate) fn new() -> Self {
+        Self {
+            reader: AtomicBool::new(false),
+            waiters: MyRefCell::new(Vec::new()),
+        }
+    }
+
+    /// Tries to make the caller the reader of the queue.
+    pub(crate) fn try_acquire_reader(&self) -> bool {
+        self.reader
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// Gives up the reader role and wakes up the parked futures so that one of them can take it over.
+    pub(crate) fn release_reader(&self) {
+        self.reader.store(false, Ordering::Release);
+        self.wake_all();
+    }
+
+    /// Parks `waker` until the next call to [WakerFanout::wake_all].
+    ///
+    /// Callers must re-check their completion status and try to acquire the reader role after parking,
+    /// otherwise a wakeup issued in between would be lost.
+    pub(crate) fn register(&self, waker: &Waker) {
+        #[cfg(feature = "thread-safe")]
+        let mut waiters = self.waiters.write();
+        #[cfg(not(feature = "thread-safe"))]
+        let mut waiters = self.waiters.borrow_mut();
+
+        if !waiters.iter().any(|w| w.will_wake(waker)) {
+            waiters.push(waker.clone());
+        }
+    }
+
+    /// Wakes up every parked future.
+    pub(crate) fn wake_all(&self) {
+        #[cfg(feature = "thread-safe")]
+        let waiters = std::mem::take(&mut *self.waiters.write());
+        #[cfg(not(feature = "thread-safe"))]
+        let waiters = std::mem::take(&mut *self.waiters.borrow_mut());
+
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
+
+impl Default for WakerFanout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    use super::WakerFanout;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn single_reader_wakes_parked_futures() {
+        let fanout = WakerFanout::new();
+        let counters: Vec<_> = (0..3)
+            .map(|_| Arc::new(CountingWaker(AtomicUsize::new(0))))
+            .collect();
+        let wakers: Vec<Waker> = counters.iter().map(|c| Waker::from(c.clone())).collect();
+
+        assert!(fanout.try_acquire_reader());
+        assert!(!fanout.try_acquire_reader());
+
+        // The same future parking twice is only woken up once
+        fanout.register(&wakers[1]);
+        fanout.register(&wakers[1]);
+        fanout.register(&wakers[2]);
+        fanout.wake_all();
+        let woken: Vec<_> = counters.iter().map(|c| c.0.load(Ordering::SeqCst)).collect();
+        assert_eq!(woken, vec![0, 1, 1]);
+
+        // Stepping down wakes up the futures parked since the last wakeup
+        fanout.register(&wakers[2]);
+        fanout.release_reader();
+        assert_eq!(counters[2].0.load(Ordering::SeqCst), 2);
+        assert!(fanout.try_acquire_reader());
+    }
+}
@@ -1,13 +1,16 @@
 use crate::error::Error;
 
 pub mod av;
+pub mod buf;
 pub mod comm;
 pub mod conn_ep;
 pub mod connless_ep;
 pub mod cq;
+pub mod ctx_pool;
 pub mod domain;
 pub mod ep;
 pub mod eq;
+pub(crate) mod fanout;
 pub mod mcast;
 pub mod mr;
 pub mod xcontext;
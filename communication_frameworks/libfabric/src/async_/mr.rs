@@ -1,8 +1,11 @@
 use crate::domain::DomainBase;
 use crate::enums::{MrMode, MrRegOpt};
 use crate::eq::Event;
-use crate::fid::{AsRawTypedFid, AsTypedFid, Fid, OwnedMrFid};
-use crate::mr::{mr_key, MemoryRegionAttr, MemoryRegionBuilder, OwnedMemoryRegionDesc};
+use crate::fid::{AsRawFid, AsRawTypedFid, AsTypedFid, Fid, OwnedMrFid};
+use crate::mr::{
+    mr_key, DisabledMemoryRegion, EpBindingMemoryRegion, MRBackingBuf, MaybeDisabledMemoryRegion,
+    MemoryRegionAttr, MemoryRegionBuilder, OwnedMemoryRegionDesc,
+};
 use crate::{
     enums::MrAccess,
     mr::{MemoryRegion, MemoryRegionImpl},
@@ -76,14 +79,20 @@ impl MemoryRegionImpl {
         ))
     }
 
-    #[allow(dead_code)]
     pub(crate) async fn from_attr_async(
         domain: &MyRc<crate::async_::domain::AsyncDomainImpl>,
         mut attr: MemoryRegionAttr,
         flags: MrRegOpt,
         ctx: &mut Context,
     ) -> Result<(Event, Self), crate::error::Error> {
-        // [TODO] Add context version
+        let eq = match domain._eq_rc.get() {
+            Some((eq, true)) => eq.clone(),
+            _ => {
+                return Err(crate::error::Error::from_err_code(
+                    libfabric_sys::FI_ENOEQ,
+                ))
+            }
+        };
 
         attr.c_attr.context = ctx.inner_mut();
 
@@ -98,43 +107,45 @@ impl MemoryRegionImpl {
             )
         };
 
-        if err == 0 {
-            if let Some((eq, mem_reg)) = domain._eq_rc.get() {
-                if !mem_reg {
-                    panic!("Domain has to be bound with async_mem_reg to an event queue to use async memory registration");
-                } else {
-                    // let res = crate::async_::eq::EventQueueFut::<{libfabric_sys::FI_MR_COMPLETE}>::new(std::ptr::null_mut(), eq.clone(), attr.c_attr.context as usize).await?;
-                    let res = eq
-                        .async_event_wait(libfabric_sys::FI_MR_COMPLETE, Fid(0), Some(ctx), None)
-                        .await?;
+        if err != 0 {
+            return Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ));
+        }
 
-                    let c_desc = unsafe { libfabric_sys::inlined_fi_mr_desc(c_mr) };
-                    if c_desc.is_null() {
-                        panic!("Got nullptr for MemoryRegion descriptor");
-                    }
+        // Take ownership of the fid before waiting so that it is closed if the future is dropped
+        let mr = Self {
+            #[cfg(not(feature = "threading-domain"))]
+            c_mr: OwnedMrFid::from(c_mr),
+            #[cfg(feature = "threading-domain")]
+            c_mr: OwnedMrFid::from(c_mr, domain.c_domain.domain.clone()),
+            _domain_rc: domain.clone(),
+            bound_cntr: MyOnceCell::new(),
+            bound_ep: MyOnceCell::new(),
+            mr_desc: OwnedMemoryRegionDesc::from_raw(std::ptr::null_mut()),
+            key: Err(crate::error::Error::from_err_code(libfabric_sys::FI_ENOKEY)),
+        };
 
-                    return Ok((
-                        res,
-                        Self {
-                            #[cfg(not(feature = "threading-domain"))]
-                            c_mr: OwnedMrFid::from(c_mr),
-                            #[cfg(feature = "threading-domain")]
-                            c_mr: OwnedMrFid::from(c_mr, domain.c_domain.domain.clone()),
-                            _domain_rc: domain.clone(),
-                            bound_cntr: MyOnceCell::new(),
-                            bound_ep: MyOnceCell::new(),
-                            mr_desc: OwnedMemoryRegionDesc::from_raw(c_desc),
-                            key: mr_key(c_mr, domain.as_ref()),
-                        },
-                    ));
-                }
-            } else {
-                panic!("Domain has to be bound with async_mem_reg to an event queue to use async memory registration");
-            }
-        }
+        // Some providers report FI_MR_COMPLETE without the context passed through fi_mr_attr,
+        // so the event is also matched against the fid of the new memory region.
+        let res = eq
+            .async_event_wait(
+                libfabric_sys::FI_MR_COMPLETE,
+                Fid(mr.as_typed_fid().as_raw_fid() as usize),
+                Some(ctx),
+                None,
+            )
+            .await?;
 
-        Err(crate::error::Error::from_err_code(
-            (-err).try_into().unwrap(),
+        // The descriptor and key are only guaranteed to be valid once the registration has completed
+        let c_desc = unsafe { libfabric_sys::inlined_fi_mr_desc(c_mr) };
+        Ok((
+            res,
+            Self {
+                mr_desc: OwnedMemoryRegionDesc::from_raw(c_desc),
+                key: mr_key(c_mr, domain.as_ref()),
+                ..mr
+            },
         ))
     }
 
@@ -284,22 +295,38 @@ impl MemoryRegion {
 }
 
 impl<'a> MemoryRegionBuilder<'a> {
-    /// Constructs a new [MemoryRegion] with the configurations requested so far.
+    /// Constructs a new [MemoryRegion] with the configurations requested so far, without blocking
+    /// the calling thread while the provider registers the memory.
+    ///
+    /// The returned future resolves once the `FI_MR_COMPLETE` event of the registration is retrieved
+    /// from the event queue `domain` was bound to with `async_mem_reg` set.
+    ///
+    /// Returns an error of kind [crate::error::ErrorKind::NoEventQueue] if `domain` is not bound to an
+    /// event queue for asynchronous memory registration.
     ///
     /// Corresponds to creating a `fi_mr_attr`, setting its fields to the requested ones,
     /// and passign it to `fi_mr_regattr`.
-    #[allow(unreachable_code, unused)]
     pub async fn build_async(
-        self,
-        domain: &DomainBase<dyn AsyncReadEq>,
+        mut self,
+        domain: &'a DomainBase<dyn AsyncReadEq>,
         ctx: &mut Context,
-    ) -> Result<(Event, MemoryRegion), crate::error::Error> {
-        panic!("Async memory registration is currently not supported due to a potential bug in libfabric");
-        match &self.backing_buf {
-            crate::mr::MRBackingBuf::IoVs(vec) => self.mr_attr.iov(vec),
-            crate::mr::MRBackingBuf::DmaBuf(dmabuf) => self.mr_attr.dmabuf(dmabuf),
+    ) -> Result<MaybeDisabledMemoryRegion, crate::error::Error> {
+        match &mut self.backing_buf {
+            MRBackingBuf::IoVs(vec) => self.mr_attr.iov(vec),
+            MRBackingBuf::DmaBuf(dmabuf) => self.mr_attr.dmabuf(dmabuf),
         };
-        MemoryRegion::from_attr_async(domain, self.mr_attr, self.flags, ctx).await
+
+        let mr = MemoryRegion::from_attr_async(domain, self.mr_attr, self.flags, ctx)
+            .await?
+            .1;
+
+        if domain.mr_mode().is_endpoint() {
+            Ok(MaybeDisabledMemoryRegion::Disabled(
+                DisabledMemoryRegion::EpBind(EpBindingMemoryRegion { mr }),
+            ))
+        } else {
+            Ok(MaybeDisabledMemoryRegion::Enabled(mr))
+        }
     }
 }
 
@@ -9,14 +9,17 @@ use super::{
 };
 use crate::{
     cntr::{Counter, ReadCntr},
+    domain::DomainBase,
     enums::TransferOptions,
     ep::{Connected, Connectionless, EndpointBase, EndpointImplBase, EpState},
     fid::{AsRawFid, AsRawTypedFid, AsTypedFid, OwnedEpFid},
+    info::InfoEntry,
     xcontext::{
-        MsgOrder, Receive, RxAttr, RxCompOrder, RxContextBase, Transmit, TxAttr, TxCompOrder,
-        TxContextBase, XContextBase, XContextBaseImpl,
+        MsgOrder, Receive, RxAttr, RxCompOrder, RxContextBase, SharedRxContextBase,
+        SharedRxContextImplBase, Transmit, TxAttr, TxCompOrder, TxContextBase, XContextBase,
+        XContextBaseImpl,
     },
-    Context, MyOnceCell, MyRc,
+    Context, MyOnceCell, MyRc, SyncSend,
 };
 pub(crate) type TxContextImplBase<I, STATE, CQ> = XContextBaseImpl<Transmit, I, STATE, CQ>;
 pub(crate) type TxContextImpl<I, STATE> = XContextBaseImpl<Transmit, I, STATE, dyn AsyncCq>;
@@ -99,8 +102,8 @@ impl<I, STATE: EpState> AsyncTxEp for TxContext<I, STATE> {
 }
 
 impl<I, STATE: EpState> AsyncRxEp for RxContext<I, STATE> {
-    fn retrieve_rx_cq(&self) -> &MyRc<impl AsyncCq + ?Sized> {
-        self.inner.inner.cq.get().unwrap()
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error> {
+        self.inner.inner.retrieve_rx_cq()
     }
 }
 
@@ -330,8 +333,10 @@ impl<EP, STATE: EpState> RxContext<EP, STATE> {
 }
 
 impl<EP, STATE: EpState> AsyncRxEp for RxContextImpl<EP, STATE> {
-    fn retrieve_rx_cq(&self) -> &MyRc<impl AsyncCq + ?Sized> {
-        self.cq.get().unwrap()
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error> {
+        self.cq
+            .get()
+            .ok_or_else(|| crate::error::Error::from_err_code(libfabric_sys::FI_ENOCQ))
     }
 }
 
@@ -551,3 +556,124 @@ impl<'a, I: 'static, STATE: EpState> RxIncompleteBindCntr<'a, I, STATE> {
         self.ep.bind_cntr_(&cntr.inner, self.flags)
     }
 }
+
+pub type SharedRxContext<I> = SharedRxContextBase<I, dyn AsyncCq>;
+pub(crate) type SharedRxContextImpl<I> = SharedRxContextImplBase<I, dyn AsyncCq>;
+
+impl<I> AsyncRxEp for SharedRxContext<I> {
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error> {
+        self.inner.retrieve_rx_cq()
+    }
+}
+
+impl<I> AsyncRxEp for SharedRxContextImpl<I> {
+    // The completion queue is only known once the context is bound to an endpoint
+    fn retrieve_rx_cq(&self) -> Result<&MyRc<impl AsyncCq + ?Sized>, crate::error::Error> {
+        self.cq
+            .get()
+            .ok_or_else(|| crate::error::Error::from_err_code(libfabric_sys::FI_ENOCQ))
+    }
+}
+
+/// Builder for the asynchronous [SharedRxContext].
+///
+/// Asynchronous receives can only be posted to the shared context once it is bound to an endpoint with
+/// `bind_srx`, since their completions are reported to the receive queue of that endpoint. Until then they
+/// fail with [NoCompletionQueue](crate::error::ErrorKind::NoCompletionQueue).
+pub struct SharedRxContextBuilder<'a, I> {
+    pub(crate) rx_attr: RxAttr,
+    pub(crate) ctx: Option<&'a mut Context>,
+    iphantom: PhantomData<fn() -> I>,
+}
+
+impl<'a> SharedRxContextBuilder<'a, ()> {
+    /// Initiates the creation of a new [SharedRxContext] using the receive attributes of `info`
+    /// as defaults.
+    pub fn new<I>(info: &InfoEntry<I>) -> SharedRxContextBuilder<'a, I> {
+        SharedRxContextBuilder::<I> {
+            rx_attr: info.rx_attr().clone(),
+            ctx: None,
+            iphantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, I> SharedRxContextBuilder<'a, I> {
+    /// Sets the mode for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::mode`.
+    pub fn mode(mut self, mode: crate::enums::Mode) -> Self {
+        self.rx_attr.set_mode(mode);
+        self
+    }
+
+    /// Sets the message order for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::msg_order`.
+    pub fn msg_order(mut self, msg_order: MsgOrder) -> Self {
+        self.rx_attr.set_msg_order(msg_order);
+        self
+    }
+
+    /// Sets the completion order for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::comp_order`.
+    pub fn comp_order(mut self, comp_order: RxCompOrder) -> Self {
+        self.rx_attr.set_comp_order(comp_order);
+        self
+    }
+
+    /// Sets the total buffered receive size for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::total_buffered_recv`.
+    pub fn total_buffered_recv(mut self, total_buffered_recv: usize) -> Self {
+        self.rx_attr.set_total_buffered_recv(total_buffered_recv);
+        self
+    }
+
+    /// Sets the number of receive operations that can be posted to the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::size`.
+    pub fn size(mut self, size: usize) -> Self {
+        self.rx_attr.set_size(size);
+        self
+    }
+
+    /// Sets the I/O vector limit for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::iov_limit`.
+    pub fn iov_limit(mut self, iov_limit: usize) -> Self {
+        self.rx_attr.set_iov_limit(iov_limit);
+        self
+    }
+
+    /// Sets the receive options for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::op_flags`.
+    pub fn set_receive_options(mut self, ops: TransferOptions) -> Self {
+        ops.recv();
+        self.rx_attr.set_op_flags(ops);
+        self
+    }
+
+    /// Sets the context for the shared receive context.
+    ///
+    /// Corresponds to the context passed to `fi_srx_context`.
+    pub fn context(self, ctx: &'a mut Context) -> SharedRxContextBuilder<'a, I> {
+        SharedRxContextBuilder {
+            rx_attr: self.rx_attr,
+            ctx: Some(ctx),
+            iphantom: PhantomData,
+        }
+    }
+
+    /// Builds the shared receive context.
+    ///
+    /// Corresponds to calling `fi_srx_context`.
+    pub fn build<EQ: ?Sized + 'static + SyncSend>(
+        self,
+        domain: &DomainBase<EQ>,
+    ) -> Result<SharedRxContext<I>, crate::error::Error> {
+        SharedRxContext::new(domain, self.rx_attr, self.ctx)
+    }
+}
@@ -37,6 +37,7 @@ pub struct Block {}
 pub struct NoBlock {}
 
 #[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents an authorization key as the one returned by the `fi_av_lookup_auth_key` function.
 pub struct AuthKey {
     pub(crate) auth_key: Vec<u8>,
@@ -73,6 +74,17 @@ impl<'a> From<(&'a str, &'a str)> for AvInAddress<'a> {
     }
 }
 
+impl AvInAddress<'_> {
+    // The number of addresses resulting from the insertion
+    pub(crate) fn count(&self) -> usize {
+        match self {
+            AvInAddress::String(_) | AvInAddress::Service(_) => 1,
+            AvInAddress::Encoded(addresses) => addresses.len(),
+            AvInAddress::Symmetric((_, nodecnt, _, svccnt)) => nodecnt * svccnt,
+        }
+    }
+}
+
 impl<'a> From<(&'a str, usize, &'a str, usize)> for AvInAddress<'a> {
     fn from(value: (&'a str, usize, &'a str, usize)) -> Self {
         AvInAddress::Symmetric(value)
@@ -166,7 +178,8 @@ impl<Mode: AVSyncMode, EQ: ReadEq + ?Sized + 'static> AddressVectorBase<Mode, EQ
 }
 
 impl<EQ: ReadEq + ?Sized + 'static> AddressVectorBase<Block, EQ> {
-    /// Insert one or more addresses into the [AddressVector] and return a [Vec] of [MappedAddress]es, one for each input address and wait for the operation to complete.
+    /// Insert one or more addresses into the [AddressVector] and return the result of the insertion of each input
+    /// address, at the respective index, and wait for the operation to complete.
     /// Addresses can be of types:
     /// - A single string ([AvInAddress::String]) that provides both a node and a service
     /// - A slice of [Address] ([AvInAddress::Encoded])
@@ -174,7 +187,10 @@ impl<EQ: ReadEq + ?Sized + 'static> AddressVectorBase<Block, EQ> {
     /// - A node followed by a count of increments, a service followed by a count of increments ([AvInAddress::Symmetric])
     ///
     /// The operation can be modified using the requested `options` as defined in [AVOptions].
-    /// For address(es) that could not be mapped a [None] value will be returned at the respective index.
+    /// The insertion is performed with `FI_SYNC_ERR` (see [AVOptions::sync_err]), so that an address that could not
+    /// be mapped is reported with the error returned by the provider for it, while the other ones are still mapped.
+    /// If the provider does not support `FI_SYNC_ERR`, such addresses are reported with
+    /// [crate::error::ErrorKind::AddrNotAvailalble].
     ///
     /// This method corresponds to a call to:
     /// - `fi_av_insert` if `addr` == [AvInAddress::Encoded]
@@ -184,79 +200,82 @@ impl<EQ: ReadEq + ?Sized + 'static> AddressVectorBase<Block, EQ> {
         &self,
         addr: AvInAddress,
         options: AVOptions,
-    ) -> Result<Vec<Option<MappedAddress>>, crate::error::Error> {
-        let fi_addresses = match addr {
-            AvInAddress::String(str_addr) => {
-                self.inner.insertsvc_str(str_addr, options.as_raw(), None)?
-            }
-            AvInAddress::Encoded(addresses) => {
-                self.inner.insert::<()>(addresses, options.as_raw(), None)?
-            }
-            AvInAddress::Service((node, svc)) => {
-                self.inner
-                    .insertsvc::<()>(node, svc, options.as_raw(), None)?
-                // vec![mapped_addr]
-            }
-            AvInAddress::Symmetric((node, nodecnt, svc, svccnt)) => {
-                self.inner
-                    .insertsym::<()>(node, nodecnt, svc, svccnt, options.as_raw(), None)?
+    ) -> Result<Vec<Result<MappedAddress, crate::error::Error>>, crate::error::Error> {
+        let mut statuses = vec![0i32; addr.count()];
+        let res = self.insert_sync(&addr, options.sync_err(), Some(statuses.as_mut_ptr()));
+        let (fi_addresses, statuses) = match res {
+            Ok(fi_addresses) => (fi_addresses, statuses),
+            Err(err) if matches!(err.kind, crate::error::ErrorKind::BadFlags) => {
+                (self.insert_sync(&addr, options, None)?, Vec::new())
             }
+            Err(err) => return Err(err),
         };
 
-        Ok(fi_addresses
-            .into_iter()
-            .map(|fi_addr| {
-                if fi_addr == FI_ADDR_NOTAVAIL {
-                    None
-                } else {
-                    Some(MappedAddress::from_raw_addr(
-                        RawMappedAddress::from_raw(self.inner.type_, fi_addr),
-                        AddressSource::Av(self.inner.clone()),
-                    ))
-                }
-            })
-            .collect::<Vec<_>>())
+        let av: MyRc<dyn AddressVectorImplT> = self.inner.clone();
+        Ok(insert_results(&av, fi_addresses, &statuses))
     }
 
     /// Same as [Self::insert] but with an extra argument to provide a context
     ///
+    /// As the context is passed to the provider, `FI_SYNC_ERR` cannot be used and addresses that could not be
+    /// mapped are reported with [crate::error::ErrorKind::AddrNotAvailalble].
     pub fn insert_with_context<T>(
         &self,
         addr: AvInAddress,
         options: AVOptions,
         ctx: &mut Context,
-    ) -> Result<Vec<Option<MappedAddress>>, crate::error::Error> {
+    ) -> Result<Vec<Result<MappedAddress, crate::error::Error>>, crate::error::Error> {
         let fi_addresses = match addr {
             AvInAddress::String(str_addr) => {
                 self.inner
                     .insertsvc_str(str_addr, options.as_raw(), Some(ctx.inner_mut()))?
             }
             AvInAddress::Encoded(addresses) => {
-                self.inner.insert(addresses, options.as_raw(), Some(ctx))?
+                self.inner
+                    .insert(addresses, options.as_raw(), Some(ctx.inner_mut()))?
             }
             AvInAddress::Service((node, svc)) => {
                 self.inner
-                    .insertsvc(node, svc, options.as_raw(), Some(ctx))?
+                    .insertsvc(node, svc, options.as_raw(), Some(ctx.inner_mut()))?
+            }
+            AvInAddress::Symmetric((node, nodecnt, svc, svccnt)) => self.inner.insertsym(
+                node,
+                nodecnt,
+                svc,
+                svccnt,
+                options.as_raw(),
+                Some(ctx.inner_mut()),
+            )?,
+        };
+
+        let av: MyRc<dyn AddressVectorImplT> = self.inner.clone();
+        Ok(insert_results(&av, fi_addresses, &[]))
+    }
+
+    // With FI_SYNC_ERR, `statuses` points to an array receiving the status of each address
+    fn insert_sync(
+        &self,
+        addr: &AvInAddress,
+        options: AVOptions,
+        statuses: Option<*mut i32>,
+    ) -> Result<Vec<libfabric_sys::fi_addr_t>, crate::error::Error> {
+        let statuses = statuses.map(|statuses| statuses.cast());
+        match *addr {
+            AvInAddress::String(str_addr) => {
+                self.inner
+                    .insertsvc_str(str_addr, options.as_raw(), statuses)
+            }
+            AvInAddress::Encoded(addresses) => {
+                self.inner.insert(addresses, options.as_raw(), statuses)
+            }
+            AvInAddress::Service((node, svc)) => {
+                self.inner.insertsvc(node, svc, options.as_raw(), statuses)
             }
             AvInAddress::Symmetric((node, nodecnt, svc, svccnt)) => {
                 self.inner
-                    .insertsym(node, nodecnt, svc, svccnt, options.as_raw(), Some(ctx))?
+                    .insertsym(node, nodecnt, svc, svccnt, options.as_raw(), statuses)
             }
-        };
-
-        Ok(fi_addresses
-            .into_iter()
-            .map(|fi_addr| {
-                if fi_addr == FI_ADDR_NOTAVAIL {
-                    None
-                } else {
-                    Some(MappedAddress::from_raw_addr(
-                        RawMappedAddress::from_raw(self.inner.type_, fi_addr),
-                        AddressSource::Av(self.inner.clone()),
-                    ))
-                }
-            })
-            .collect::<Vec<_>>())
+        }
     }
 }
 
@@ -272,16 +291,15 @@ impl<EQ: ReadEq + ?Sized + 'static> AddressVectorBase<NoBlock, EQ> {
                 self.inner.insertsvc_str(str_addr, options.as_raw(), None)?
             }
             AvInAddress::Encoded(addresses) => {
-                self.inner.insert::<()>(addresses, options.as_raw(), None)?
+                self.inner.insert(addresses, options.as_raw(), None)?
             }
             AvInAddress::Service((node, svc)) => {
-                self.inner
-                    .insertsvc::<()>(node, svc, options.as_raw(), None)?
+                self.inner.insertsvc(node, svc, options.as_raw(), None)?
                 // vec![mapped_addr]
             }
             AvInAddress::Symmetric((node, nodecnt, svc, svccnt)) => {
                 self.inner
-                    .insertsym::<()>(node, nodecnt, svc, svccnt, options.as_raw(), None)?
+                    .insertsym(node, nodecnt, svc, svccnt, options.as_raw(), None)?
             }
         };
 
@@ -304,16 +322,21 @@ impl<EQ: ReadEq + ?Sized + 'static> AddressVectorBase<NoBlock, EQ> {
                     .insertsvc_str(str_addr, options.as_raw(), Some(ctx.inner_mut()))?
             }
             AvInAddress::Encoded(addresses) => {
-                self.inner.insert(addresses, options.as_raw(), Some(ctx))?
-            }
-            AvInAddress::Service((node, svc)) => {
                 self.inner
-                    .insertsvc(node, svc, options.as_raw(), Some(ctx))?
+                    .insert(addresses, options.as_raw(), Some(ctx.inner_mut()))?
             }
-            AvInAddress::Symmetric((node, nodecnt, svc, svccnt)) => {
+            AvInAddress::Service((node, svc)) => {
                 self.inner
-                    .insertsym(node, nodecnt, svc, svccnt, options.as_raw(), Some(ctx))?
+                    .insertsvc(node, svc, options.as_raw(), Some(ctx.inner_mut()))?
             }
+            AvInAddress::Symmetric((node, nodecnt, svc, svccnt)) => self.inner.insertsym(
+                node,
+                nodecnt,
+                svc,
+                svccnt,
+                options.as_raw(),
+                Some(ctx.inner_mut()),
+            )?,
         };
 
         Ok(PendingAVTranslation {
@@ -540,42 +563,64 @@ pub struct PendingAVTranslation {
 }
 
 impl PendingAVTranslation {
-    /// Completes a pending AddressVector insert operation, returning the resulting MappedAddresses.
-    pub fn av_complete(self, event: AVCompleteEvent) -> Vec<MappedAddress> {
+    /// Completes a pending AddressVector insert operation, returning the result of the insertion of each address.
+    ///
+    /// Addresses that could not be mapped are reported with [crate::error::ErrorKind::AddrNotAvailalble]. The
+    /// provider reports the cause of each failure as an error entry in the [EventQueueBase] (see
+    /// [crate::eq::EventError::data] for the index of the address).
+    pub fn av_complete(
+        self,
+        event: AVCompleteEvent,
+    ) -> Vec<Result<MappedAddress, crate::error::Error>> {
         assert_eq!(event.fid(), &self.av.as_typed_fid().as_raw_fid());
-        self.fi_addresses
-            .into_iter()
-            .map(|fi_addr| {
-                MappedAddress::from_raw_addr(
-                    RawMappedAddress::from_raw(self.av.type_(), fi_addr),
-                    AddressSource::Av(self.av.clone()),
-                )
-            })
-            .collect::<Vec<_>>()
+        insert_results(&self.av, self.fi_addresses, &[])
     }
 
     /// Completes a pending AddressVector insert operation without checking the event target fid.
-    pub fn av_complete_unchecked(self, _event: AVCompleteEvent) -> Vec<MappedAddress> {
-        self.fi_addresses
-            .into_iter()
-            .map(|fi_addr| {
-                MappedAddress::from_raw_addr(
-                    RawMappedAddress::from_raw(self.av.type_(), fi_addr),
-                    AddressSource::Av(self.av.clone()),
-                )
-            })
-            .collect::<Vec<_>>()
+    pub fn av_complete_unchecked(
+        self,
+        _event: AVCompleteEvent,
+    ) -> Vec<Result<MappedAddress, crate::error::Error>> {
+        insert_results(&self.av, self.fi_addresses, &[])
     }
 }
 
+// Maps the addresses resulting from an insertion, given the status of each one (a fabric errno, 0 on success)
+// when known
+pub(crate) fn insert_results(
+    av: &MyRc<dyn AddressVectorImplT>,
+    fi_addresses: Vec<libfabric_sys::fi_addr_t>,
+    statuses: &[i32],
+) -> Vec<Result<MappedAddress, crate::error::Error>> {
+    fi_addresses
+        .into_iter()
+        .enumerate()
+        .map(|(i, fi_addr)| match statuses.get(i).copied().unwrap_or(0) {
+            0 if fi_addr != FI_ADDR_NOTAVAIL => Ok(MappedAddress::from_raw_addr(
+                RawMappedAddress::from_raw(av.type_(), fi_addr),
+                AddressSource::Av(av.clone()),
+            )),
+            0 => Err(crate::error::Error::from_err_code(
+                libfabric_sys::FI_EADDRNOTAVAIL,
+            )),
+            err => Err(crate::error::Error::from_err_code(err.unsigned_abs())),
+        })
+        .collect()
+}
+
 pub(crate) trait AddressVectorImplT: SyncSend + AsTypedFid<AvRawFid> {
     fn type_(&self) -> AddressVectorType;
+    fn using_context2(&self) -> bool;
 }
 
 impl<EQ: ?Sized + SyncSend + ReadEq> AddressVectorImplT for AddressVectorImplBase<EQ> {
     fn type_(&self) -> AddressVectorType {
         self.type_
     }
+
+    fn using_context2(&self) -> bool {
+        self._domain_rc.fabric_impl().using_context2
+    }
 }
 
 impl<EQ: ?Sized + SyncSend> SyncSend for AddressVectorImplBase<EQ> {}
@@ -678,42 +723,33 @@ impl<EQ: ?Sized + ReadEq> AddressVectorImplBase<EQ> {
 }
 
 impl<EQ: ?Sized + ReadEq> AddressVectorImplBase<EQ> {
-    fn insert<T>(
+    fn insert(
         &self,
         addr: &[Address],
         flags: u64,
-        ctx: Option<&mut T>,
+        ctx: Option<*mut std::ffi::c_void>,
     ) -> Result<Vec<libfabric_sys::fi_addr_t>, crate::error::Error> {
-        // [TODO] //[TODO] Handle flags, handle context
         let mut fi_addresses = vec![0u64; addr.len()];
         let total_size = addr.iter().fold(0, |acc, addr| acc + addr.as_bytes().len());
         let mut serialized: Vec<u8> = Vec::with_capacity(total_size);
         for a in addr {
             serialized.extend(a.as_bytes().iter())
         }
-
-        let err = if let Some(ctx) = ctx {
-            unsafe {
-                libfabric_sys::inlined_fi_av_insert(
-                    self.as_typed_fid_mut().as_raw_typed_fid(),
-                    serialized.as_ptr().cast(),
-                    fi_addresses.len(),
-                    fi_addresses.as_mut_ptr().cast(),
-                    flags,
-                    (ctx as *mut T).cast(),
-                )
-            }
+        let ctx = if let Some(ctx) = ctx {
+            ctx
         } else {
-            unsafe {
-                libfabric_sys::inlined_fi_av_insert(
-                    self.as_typed_fid_mut().as_raw_typed_fid(),
-                    serialized.as_ptr().cast(),
-                    fi_addresses.len(),
-                    fi_addresses.as_mut_ptr().cast(),
-                    flags,
-                    std::ptr::null_mut(),
-                )
-            }
+            std::ptr::null_mut()
+        };
+
+        let err = unsafe {
+            libfabric_sys::inlined_fi_av_insert(
+                self.as_typed_fid_mut().as_raw_typed_fid(),
+                serialized.as_ptr().cast(),
+                fi_addresses.len(),
+                fi_addresses.as_mut_ptr().cast(),
+                flags,
+                ctx,
+            )
         };
 
         if err < 0 {
@@ -726,16 +762,16 @@ impl<EQ: ?Sized + ReadEq> AddressVectorImplBase<EQ> {
         }
     }
 
-    pub(crate) fn insertsvc<T>(
+    pub(crate) fn insertsvc(
         &self,
         node: &str,
         service: &str,
         flags: u64,
-        ctx: Option<&mut T>,
+        ctx: Option<*mut std::ffi::c_void>,
     ) -> Result<Vec<libfabric_sys::fi_addr_t>, crate::error::Error> {
         let mut fi_addresses = vec![0u64; 1];
         let ctx = if let Some(ctx) = ctx {
-            ctx as *mut T
+            ctx
         } else {
             std::ptr::null_mut()
         };
@@ -793,22 +829,21 @@ impl<EQ: ?Sized + ReadEq> AddressVectorImplBase<EQ> {
         }
     }
 
-    pub(crate) fn insertsym<T>(
+    pub(crate) fn insertsym(
         &self,
         node: &str,
         nodecnt: usize,
         service: &str,
         svccnt: usize,
         flags: u64,
-        ctx: Option<&mut T>,
+        ctx: Option<*mut std::ffi::c_void>,
     ) -> Result<Vec<libfabric_sys::fi_addr_t>, crate::error::Error> {
-        // [TODO] Handle case where operation partially failed
         let total_cnt = nodecnt * svccnt;
         let mut fi_addresses = vec![0u64; total_cnt];
         let c_node_str = CString::new(node).unwrap();
         let c_svc_str = CString::new(service).unwrap();
         let ctx = if let Some(ctx) = ctx {
-            ctx as *mut T
+            ctx
         } else {
             std::ptr::null_mut()
         };
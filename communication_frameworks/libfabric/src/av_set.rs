@@ -6,7 +6,7 @@ use crate::{
         AVSetRawFid, AsRawTypedFid, AsTypedFid, BorrowedTypedFid, MutBorrowedTypedFid,
         OwnedAVSetFid,
     },
-    AddressSource, Context, MappedAddress, MyRc, RawMappedAddress, FI_ADDR_NOTAVAIL,
+    AddressSource, Context, MappedAddress, MyRc, MyRefCell, RawMappedAddress, FI_ADDR_NOTAVAIL,
 };
 
 //================== AddressVectorSet Public ==================//
@@ -89,6 +89,17 @@ impl AddressVectorSet {
         self.inner.remove(mapped_addr)
     }
 
+    /// Returns the addresses of the members of the [AddressVectorSet], in the order they were added.
+    ///
+    /// Membership is tracked by this crate, as libfabric does not provide a way to list the members of a set.
+    pub fn members(&self) -> Vec<crate::MappedAddress> {
+        self.inner.members()
+    }
+
+    pub(crate) fn using_context2(&self) -> bool {
+        self.inner._av_rc.using_context2()
+    }
+
     /// Retrieves an address associated with the [AddressVectorSet].
     ///
     /// Corresponds to `fi_av_set_addr`
@@ -281,6 +292,7 @@ impl AsTypedFid<AVSetRawFid> for AddressVectorSet {
 
 pub(crate) struct AddressVectorSetImpl {
     pub(crate) c_set: OwnedAVSetFid,
+    members: MyRefCell<Vec<MappedAddress>>,
     pub(crate) _av_rc: MyRc<dyn AddressVectorImplT>,
 }
 
@@ -306,13 +318,46 @@ impl AddressVectorSetImpl {
                 (-err).try_into().unwrap(),
             ))
         } else {
+            let members = if attr.c_attr.start_addr != FI_ADDR_NOTAVAIL
+                && attr.c_attr.end_addr != FI_ADDR_NOTAVAIL
+            {
+                (attr.c_attr.start_addr..=attr.c_attr.end_addr)
+                    .step_by(attr.c_attr.stride.max(1))
+                    .map(|addr| {
+                        MappedAddress::from_raw_addr(
+                            RawMappedAddress::from_raw(av.inner.type_(), addr),
+                            AddressSource::Av(av.inner.clone()),
+                        )
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
             Ok(Self {
                 c_set: OwnedAVSetFid::from(c_set),
+                members: MyRefCell::new(members),
                 _av_rc: av.inner.clone(),
             })
         }
     }
 
+    pub(crate) fn members(&self) -> Vec<MappedAddress> {
+        #[cfg(feature = "thread-safe")]
+        let members = self.members.read();
+        #[cfg(not(feature = "thread-safe"))]
+        let members = self.members.borrow();
+        members.clone()
+    }
+
+    fn update_members(&self, f: impl FnOnce(&mut Vec<MappedAddress>)) {
+        #[cfg(feature = "thread-safe")]
+        let mut members = self.members.write();
+        #[cfg(not(feature = "thread-safe"))]
+        let mut members = self.members.borrow_mut();
+        f(&mut members)
+    }
+
     pub(crate) fn union(&self, other: &AddressVectorSetImpl) -> Result<(), crate::error::Error> {
         let err = unsafe {
             libfabric_sys::inlined_fi_av_set_union(
@@ -326,6 +371,14 @@ impl AddressVectorSetImpl {
                 (-err).try_into().unwrap(),
             ))
         } else {
+            let others = other.members();
+            self.update_members(|members| {
+                for addr in others {
+                    if !members.iter().any(|m| m.raw_addr() == addr.raw_addr()) {
+                        members.push(addr);
+                    }
+                }
+            });
             Ok(())
         }
     }
@@ -346,6 +399,10 @@ impl AddressVectorSetImpl {
                 (-err).try_into().unwrap(),
             ))
         } else {
+            let others = other.members();
+            self.update_members(|members| {
+                members.retain(|m| others.iter().any(|o| o.raw_addr() == m.raw_addr()))
+            });
             Ok(())
         }
     }
@@ -363,6 +420,10 @@ impl AddressVectorSetImpl {
                 (-err).try_into().unwrap(),
             ))
         } else {
+            let others = other.members();
+            self.update_members(|members| {
+                members.retain(|m| !others.iter().any(|o| o.raw_addr() == m.raw_addr()))
+            });
             Ok(())
         }
     }
@@ -383,6 +444,14 @@ impl AddressVectorSetImpl {
                 (-err).try_into().unwrap(),
             ))
         } else {
+            self.update_members(|members| {
+                if !members
+                    .iter()
+                    .any(|m| m.raw_addr() == mapped_addr.raw_addr())
+                {
+                    members.push(mapped_addr.clone());
+                }
+            });
             Ok(())
         }
     }
@@ -403,6 +472,9 @@ impl AddressVectorSetImpl {
                 (-err).try_into().unwrap(),
             ))
         } else {
+            self.update_members(|members| {
+                members.retain(|m| m.raw_addr() != mapped_addr.raw_addr())
+            });
             Ok(())
         }
     }
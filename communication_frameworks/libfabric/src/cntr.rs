@@ -1,5 +1,6 @@
 
 use std::os::fd::{AsFd, BorrowedFd};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::fid::{AsTypedFid, BorrowedTypedFid};
 #[allow(unused_imports)]
@@ -9,8 +10,9 @@ use crate::{
     domain::DomainImplT,
     enums::WaitObjType,
     fid::{self, AsRawFid, AsRawTypedFid, CntrRawFid, OwnedCntrFid, RawFid},
+    progress::Progressable,
     utils::check_error,
-    Context, MyRc, SyncSend,
+    Context, MyRc, SyncSend, Waitable,
 };
 
 //================== Public Counter ==================//
@@ -252,6 +254,7 @@ pub struct CounterImpl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> {
     pub(crate) c_cntr: OwnedCntrFid,
     wait_obj: Option<libfabric_sys::fi_wait_obj>,
     _domain_rc: MyRc<dyn DomainImplT>,
+    _context_token: Box<u8>,
 }
 
 impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> CounterImpl<WAIT, RETRIEVE, FD> {
@@ -261,6 +264,13 @@ impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> CounterImpl<WAIT, R
         context: *mut std::ffi::c_void,
     ) -> Result<Self, crate::error::Error> {
         let mut c_cntr: CntrRawFid = std::ptr::null_mut();
+        // Counters opened without a context get a unique one, which is how poll sets report them
+        let context_token = Box::new(0u8);
+        let context = if context.is_null() {
+            (&*context_token as *const u8).cast_mut().cast()
+        } else {
+            context
+        };
 
         let err = unsafe {
             libfabric_sys::inlined_fi_cntr_open(
@@ -283,6 +293,7 @@ impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> CounterImpl<WAIT, R
                 c_cntr: OwnedCntrFid::from(c_cntr),
                 wait_obj: Some(attr.c_attr.wait_obj),
                 _domain_rc: domain.clone() as MyRc<dyn DomainImplT>,
+                _context_token: context_token,
             })
         }
     }
@@ -334,6 +345,61 @@ impl<T: AsRawTypedFid<Output = CntrRawFid>> AsRawTypedFid for Counter<T> {
     }
 }
 
+// Remembers the value read in the previous round to report the events counted since then
+struct CounterProgress<T> {
+    cntr: Counter<T>,
+    last: AtomicU64,
+}
+
+impl<T: ReadCntr> SyncSend for CounterProgress<T> {}
+
+impl<T: ReadCntr + 'static> Progressable for CounterProgress<T> {
+    fn progress_once(&self) -> Result<usize, crate::error::Error> {
+        let value = self.cntr.inner.read().wrapping_add(self.cntr.inner.readerr());
+        let last = self.last.swap(value, Ordering::Relaxed);
+        // The counter may have been set back by the user
+        Ok(value.saturating_sub(last) as usize)
+    }
+
+    fn progressable_clone(&self) -> Box<dyn Progressable> {
+        Box::new(Self {
+            cntr: Counter {
+                inner: self.cntr.inner.clone(),
+            },
+            last: AtomicU64::new(self.last.load(Ordering::Relaxed)),
+        })
+    }
+}
+
+impl<T: ReadCntr + 'static> Progressable for Counter<T> {
+    // A ProgressEngine drives a CounterProgress instead, which can tell whether the counter moved
+    fn progress_once(&self) -> Result<usize, crate::error::Error> {
+        self.inner.read();
+        Ok(0)
+    }
+
+    fn progressable_clone(&self) -> Box<dyn Progressable> {
+        Box::new(CounterProgress {
+            cntr: Counter {
+                inner: self.inner.clone(),
+            },
+            last: AtomicU64::new(self.inner.read().wrapping_add(self.inner.readerr())),
+        })
+    }
+}
+
+impl<T: ReadCntr + 'static> Waitable for Counter<T> {
+    fn waitable_fid(&self) -> RawFid {
+        self.inner.as_typed_fid().as_raw_fid()
+    }
+
+    fn waitable_clone(&self) -> Box<dyn Waitable> {
+        Box::new(Self {
+            inner: self.inner.clone(),
+        })
+    }
+}
+
 
 impl<const RETRIEVE: bool, const FD: bool> WaitCntr for CounterImpl<true, RETRIEVE, FD> {}
 
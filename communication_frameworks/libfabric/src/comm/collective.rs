@@ -22,6 +22,8 @@ use crate::AsFiType;
 use crate::Context;
 
 pub(crate) trait CollectiveEpImpl: AsTypedFid<EpRawFid> {
+    fn using_context2(&self) -> bool;
+
     fn barrier_impl(
         &self,
         mc_group: &MultiCastGroup,
@@ -1152,10 +1154,22 @@ impl<EP: CollectiveEpImpl> CollectiveEp for EP {
 impl<EP: CollCap, EQ: ?Sized + ReadEq, CQ: ?Sized + ReadCq> CollectiveEpImpl
     for EndpointImplBase<EP, EQ, CQ>
 {
+    fn using_context2(&self) -> bool {
+        EndpointImplBase::using_context2(self)
+    }
 }
 
-impl<E: CollectiveEpImpl> CollectiveEpImpl for EndpointBase<E, Connected> {}
-impl<E: CollectiveEpImpl> CollectiveEpImpl for EndpointBase<E, Connectionless> {}
+impl<E: CollectiveEpImpl> CollectiveEpImpl for EndpointBase<E, Connected> {
+    fn using_context2(&self) -> bool {
+        self.inner.using_context2()
+    }
+}
+
+impl<E: CollectiveEpImpl> CollectiveEpImpl for EndpointBase<E, Connectionless> {
+    fn using_context2(&self) -> bool {
+        self.inner.using_context2()
+    }
+}
 
 pub struct CollectiveAttr<T> {
     pub(crate) c_attr: libfabric_sys::fi_collective_attr,
@@ -1202,3 +1216,542 @@ impl<T: AsFiType> Default for CollectiveAttr<T> {
         Self::new()
     }
 }
+
+type PostCollective<'a, T> =
+    Box<dyn FnMut(&mut [T], *mut std::ffi::c_void) -> Result<(), crate::error::Error> + 'a>;
+
+/// A handle to a collective operation that has been issued without blocking, similar to an MPI request.
+///
+/// The operation is posted when the request is created. If the provider cannot accept it yet (i.e., `FI_EAGAIN`),
+/// posting is retried every time the request is tested. Completion is tracked through the completion queue given
+/// at creation, whose entries are read by [CollectiveRequest::test]. Completions belonging to other operations
+/// are handed over to their own [Context], so several requests can be outstanding on the same queue.
+///
+/// A request can be awaited, in which case the queue is polled every time the future is polled.
+///
+/// Dropping a request whose operation has been posted blocks until the operation completes, so that the
+/// provider no longer accesses its buffers once they are released.
+pub struct CollectiveRequest<'a, T> {
+    post: Option<PostCollective<'a, T>>,
+    result: Option<&'a mut [T]>,
+    cq: &'a dyn ReadCq,
+    context: &'a mut Context,
+    using_context2: bool,
+    completed: bool,
+}
+
+impl<'a, T> CollectiveRequest<'a, T> {
+    fn new(
+        result: &'a mut [T],
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+        using_context2: bool,
+        post: PostCollective<'a, T>,
+    ) -> Result<Self, crate::error::Error> {
+        let mut req = Self {
+            post: Some(post),
+            result: Some(result),
+            cq,
+            context,
+            using_context2,
+            completed: false,
+        };
+        req.try_post()?;
+        Ok(req)
+    }
+
+    fn try_post(&mut self) -> Result<(), crate::error::Error> {
+        if let Some(post) = self.post.as_mut() {
+            match post(self.result.as_mut().unwrap(), self.context.inner_mut()) {
+                Ok(()) => self.post = None,
+                Err(err) if matches!(err.kind, crate::error::ErrorKind::TryAgain) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    // Reads the queue until it is empty or the operation completes, handing every other completion over to its context
+    fn drain(&mut self) -> Result<(), crate::error::Error> {
+        while !self.context.ready() {
+            let (op_context, comp) = match self.cq.read(1) {
+                Ok(mut completion) => match completion.pop() {
+                    Some(entry) => (entry.op_context(), Ok(entry)),
+                    None => return Ok(()),
+                },
+                Err(err) => match err.kind {
+                    crate::error::ErrorKind::TryAgain => return Ok(()),
+                    crate::error::ErrorKind::ErrorAvailable => {
+                        let err = self.cq.readerr(0)?;
+                        (
+                            err.c_err.op_context,
+                            Err(crate::error::Error::from_completion_queue_err(err)),
+                        )
+                    }
+                    _ => return Err(err),
+                },
+            };
+
+            // Completions of operations that did not register a context have no owner
+            if op_context.is_null() {
+                continue;
+            }
+            if op_context as usize == self.context.inner() as usize {
+                self.context.set_completion_done(comp);
+                continue;
+            }
+            // Other operations may use a different kind of context than this request, the fabric decides
+            if self.using_context2 {
+                unsafe { (*(op_context as *mut crate::Context2)).set_completion_done(comp) }
+            } else {
+                unsafe { (*(op_context as *mut crate::Context1)).set_completion_done(comp) }
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes progress on the operation, returning `true` once it has completed.
+    ///
+    /// Returns the error of the operation if it completed with one.
+    pub fn test(&mut self) -> Result<bool, crate::error::Error> {
+        if self.completed {
+            return Ok(true);
+        }
+
+        self.try_post()?;
+        self.drain()?;
+        if self.post.is_some() || !self.context.ready() {
+            return Ok(false);
+        }
+
+        let state = self.context.state().take();
+        self.context.reset();
+        self.completed = true;
+        match state {
+            Some(crate::ContextState::Cq(comp)) => comp.map(|_| true),
+            _ => panic!("Should always have a completion to read when ready"),
+        }
+    }
+
+    /// Blocks until the operation completes, returning the result buffer of the operation.
+    pub fn wait(mut self) -> Result<&'a mut [T], crate::error::Error> {
+        while !self.test()? {
+            std::thread::yield_now();
+        }
+        Ok(self.result.take().unwrap())
+    }
+
+    /// Returns the result buffer of the operation.
+    ///
+    /// Its content is only meaningful once the operation has completed.
+    pub fn result(&self) -> &[T] {
+        self.result.as_ref().unwrap()
+    }
+}
+
+impl<'a, T> std::future::Future for CollectiveRequest<'a, T> {
+    type Output = Result<&'a mut [T], crate::error::Error>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let mut_self = self.get_mut();
+        match mut_self.test() {
+            Ok(true) => std::task::Poll::Ready(Ok(mut_self.result.take().unwrap())),
+            Ok(false) => {
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+            Err(err) => std::task::Poll::Ready(Err(err)),
+        }
+    }
+}
+
+impl<T> Drop for CollectiveRequest<'_, T> {
+    fn drop(&mut self) {
+        if self.post.is_none() && !self.completed {
+            // Errors of other operations or of the queue itself must not release the buffers early
+            while !self.context.ready() {
+                let _ = self.drain();
+                std::thread::yield_now();
+            }
+            self.context.reset();
+        }
+    }
+}
+
+/// Non-blocking versions of the operations of [CollectiveEp], returning a [CollectiveRequest] that tracks
+/// the completion of the operation through `cq`.
+///
+/// `cq` must be the completion queue the transmit operations of the endpoint are reported to.
+pub trait CollectiveRequestEp {
+    fn barrier_request<'a>(
+        &'a self,
+        mc_group: &'a MultiCastGroup,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, ()>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn broadcast_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a mut [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn alltoall_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn allreduce_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn allgather_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_scatter_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn scatter_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+    #[allow(clippy::too_many_arguments)]
+    fn gather_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error>;
+}
+
+impl<EP: CollectiveEpImpl> CollectiveRequestEp for EP {
+    fn barrier_request<'a>(
+        &'a self,
+        mc_group: &'a MultiCastGroup,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, ()>, crate::error::Error> {
+        CollectiveRequest::new(
+            &mut [],
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |_, ctx| self.barrier_impl(mc_group, Some(ctx), Some(options))),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn broadcast_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a mut [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            buf,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |buf, ctx| {
+                self.broadcast_impl(
+                    buf,
+                    desc,
+                    mc_group,
+                    Some(root_mapped_addr),
+                    options,
+                    Some(ctx),
+                )
+            }),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn alltoall_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            result,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |result, ctx| {
+                self.alltoall_impl(buf, desc, result, result_desc, mc_group, options, Some(ctx))
+            }),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn allreduce_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            result,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |result, ctx| {
+                self.allreduce_impl(
+                    buf,
+                    desc,
+                    result,
+                    result_desc,
+                    mc_group,
+                    op,
+                    options,
+                    Some(ctx),
+                )
+            }),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn allgather_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            result,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |result, ctx| {
+                self.allgather_impl(buf, desc, result, result_desc, mc_group, options, Some(ctx))
+            }),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_scatter_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            result,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |result, ctx| {
+                self.reduce_scatter_impl(
+                    buf,
+                    desc,
+                    result,
+                    result_desc,
+                    mc_group,
+                    op,
+                    options,
+                    Some(ctx),
+                )
+            }),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn reduce_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        op: crate::enums::ReduceOp,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            result,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |result, ctx| {
+                self.reduce_impl(
+                    buf,
+                    desc,
+                    result,
+                    result_desc,
+                    mc_group,
+                    Some(root_mapped_addr),
+                    op,
+                    options,
+                    Some(ctx),
+                )
+            }),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn scatter_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            result,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |result, ctx| {
+                self.scatter_impl(
+                    buf,
+                    desc,
+                    result,
+                    result_desc,
+                    mc_group,
+                    Some(root_mapped_addr),
+                    options,
+                    Some(ctx),
+                )
+            }),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn gather_request<'a, T: AsFiType + 'a>(
+        &'a self,
+        buf: &'a [T],
+        desc: Option<&'a MemoryRegionDesc>,
+        result: &'a mut [T],
+        result_desc: Option<&'a MemoryRegionDesc>,
+        mc_group: &'a MultiCastGroup,
+        root_mapped_addr: &'a crate::MappedAddress,
+        options: CollectiveOptions,
+        cq: &'a dyn ReadCq,
+        context: &'a mut Context,
+    ) -> Result<CollectiveRequest<'a, T>, crate::error::Error> {
+        CollectiveRequest::new(
+            result,
+            cq,
+            context,
+            self.using_context2(),
+            Box::new(move |result, ctx| {
+                self.gather_impl(
+                    buf,
+                    desc,
+                    result,
+                    result_desc,
+                    mc_group,
+                    Some(root_mapped_addr),
+                    options,
+                    Some(ctx),
+                )
+            }),
+        )
+    }
+}
@@ -10,7 +10,10 @@ use crate::{
     mr::MemoryRegionDesc,
     trigger::TriggeredContext,
     utils::{check_error, Either},
-    xcontext::{RxContextBase, RxContextImplBase, TxContextBase, TxContextImplBase},
+    xcontext::{
+        RxContextBase, RxContextImplBase, SharedRxContextBase, SharedRxContextImplBase,
+        TxContextBase, TxContextImplBase,
+    },
     Context, MappedAddress, FI_ADDR_UNSPEC,
 };
 
@@ -572,6 +575,9 @@ impl<EP: MsgCap + RecvMod, STATE: EpState, CQ: ?Sized + ReadCq> RecvEpImpl
 {
 }
 
+impl<EP: MsgCap + RecvMod, CQ: ?Sized + ReadCq> RecvEpImpl for SharedRxContextBase<EP, CQ> {}
+impl<EP: MsgCap + RecvMod, CQ: ?Sized + ReadCq> RecvEpImpl for SharedRxContextImplBase<EP, CQ> {}
+
 impl<EP: ConnectedRecvEp> ConnectedRecvEpMrSlice for EP {}
 impl<EP: RecvEp> RecvEpMrSlice for EP {}
 
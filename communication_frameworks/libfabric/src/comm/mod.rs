@@ -2,5 +2,6 @@ pub mod atomic;
 pub mod collective;
 pub mod message;
 pub mod rma;
+pub mod software_collective;
 pub mod tagged;
 // pub mod asynchronous;
@@ -0,0 +1,886 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    av_set::AddressVectorSet,
+    comm::tagged::{TagRecvEp, TagSendEp},
+    cq::{ReadCq, SingleCompletion},
+    enums::{CollectiveOptions, ReduceOp},
+    ep::ActiveEndpoint,
+    error::{Error, ErrorKind},
+    mr::MemoryRegionDesc,
+    AsFiType, Context, Context1, Context2, ContextType, MappedAddress,
+};
+
+const RANK_BITS: u32 = 20;
+const SEQ_BITS: u32 = 28;
+const TAG_BASE_SHIFT: u32 = RANK_BITS + SEQ_BITS;
+const DEFAULT_TAG_BASE: u64 = 0xc011 << TAG_BASE_SHIFT;
+
+/// A collective engine implemented in software on top of tagged messages.
+///
+/// Providers that do not support `FI_COLLECTIVE` cannot run the operations of [crate::comm::collective::CollectiveEp].
+/// A `SoftwareCollective` runs them instead over [TagSendEp]/[TagRecvEp], among the members of an [AddressVectorSet]
+/// (see [AddressVectorSet::members]). The rank of each member is its position in the set, so every member must build
+/// its set in the same order.
+///
+/// Each operation blocks until it has completed locally, reading the completions of its messages from the completion
+/// queues of the endpoint. Completions of other operations read in the meantime are handed over to their own
+/// [Context], as [crate::comm::collective::CollectiveRequest] does, while those of operations posted without a context
+/// are discarded. If an operation fails, the messages it still has in flight
+/// are canceled (`fi_cancel`) and their completions are read before it returns, so that the provider no longer
+/// accesses its buffers. Messages are exchanged with tags whose 16 most significant bits are set to the
+/// engine's tag base (see [SoftwareCollective::tag_base]), which must not collide with the application's own tags.
+///
+/// Reductions are always applied in rank order, so that every member gets the same result, including for floating
+/// point types. Besides the operations of [ReduceOp], reductions can be defined by the application over its own types
+/// (see [ReduceFn]), which no provider supports natively.
+pub struct SoftwareCollective<'a, E, CQ: ?Sized> {
+    ep: &'a E,
+    tx_cq: &'a CQ,
+    rx_cq: &'a CQ,
+    members: Vec<MappedAddress>,
+    rank: usize,
+    tag_base: u64,
+    seq: AtomicU64,
+    using_context2: bool,
+}
+
+/// A user-defined reduction, combining `other` into `acc`.
+///
+/// Implemented for every closure `Fn(&mut T, &T)`. The reduction must be associative, but does not need to be
+/// commutative since contributions are always combined in rank order.
+///
+/// The elements are exchanged as raw bytes, hence the [Pod] bound of the operations taking a `ReduceFn`.
+pub trait ReduceFn<T> {
+    fn reduce(&self, acc: &mut T, other: &T);
+}
+
+impl<T, F: Fn(&mut T, &T)> ReduceFn<T> for F {
+    fn reduce(&self, acc: &mut T, other: &T) {
+        self(acc, other)
+    }
+}
+
+/// Types whose values can be rebuilt from the raw bytes received from another member of the group.
+///
+/// Implemented for the primitive integer and floating point types, and arrays of `Pod` types.
+///
+/// # Safety
+///
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid value of `Self`. The type must be `#[repr(C)]`
+/// (or `#[repr(transparent)]`), without padding, and only made of `Pod` fields: no references, pointers,
+/// `bool`, `char` nor enums. It must also be laid out identically on every member of the group.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($type_: ty),*) => {
+        $(unsafe impl Pod for $type_ {})*
+    };
+}
+
+impl_pod!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+impl<'a, E: TagSendEp + TagRecvEp + ActiveEndpoint, CQ: ReadCq + ?Sized>
+    SoftwareCollective<'a, E, CQ>
+{
+    /// Creates an engine running collectives among the members of `av_set` on behalf of `ep`, whose address is
+    /// `self_addr`.
+    ///
+    /// `tx_cq` and `rx_cq` are the completion queues bound to the transmit and receive operations of `ep`,
+    /// which can be the same queue.
+    ///
+    /// Fails with [ErrorKind::InvalidArgument] if `self_addr` is not a member of the set.
+    pub fn new(
+        ep: &'a E,
+        tx_cq: &'a CQ,
+        rx_cq: &'a CQ,
+        av_set: &AddressVectorSet,
+        self_addr: &MappedAddress,
+    ) -> Result<Self, Error> {
+        let members = av_set.members();
+        if members.len() > 1 << RANK_BITS {
+            return Err(Error::from_err_code(libfabric_sys::FI_EINVAL));
+        }
+
+        let rank = members
+            .iter()
+            .position(|m| m.raw_addr() == self_addr.raw_addr())
+            .ok_or_else(|| Error::from_err_code(libfabric_sys::FI_EINVAL))?;
+
+        Ok(Self {
+            ep,
+            tx_cq,
+            rx_cq,
+            members,
+            rank,
+            tag_base: DEFAULT_TAG_BASE,
+            seq: AtomicU64::new(0),
+            using_context2: av_set.using_context2(),
+        })
+    }
+
+    /// Sets the tag base of the engine. Only its 16 most significant bits are used.
+    pub fn tag_base(mut self, tag_base: u64) -> Self {
+        self.tag_base = tag_base & !((1 << TAG_BASE_SHIFT) - 1);
+        self
+    }
+
+    /// Returns the rank of the local endpoint in the group.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Returns the number of members of the group.
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Synchronizes all the members of the group.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::barrier].
+    pub fn barrier(&self) -> Result<(), Error> {
+        let seq = self.next_seq();
+        let mut pending = self.pending();
+        for peer in self.peers() {
+            self.post_recv::<u8>(&mut pending, &mut [], None, seq, peer)?;
+        }
+        for peer in self.peers() {
+            self.post_send::<u8>(&mut pending, &[], None, seq, peer)?;
+        }
+        self.wait_all(&mut pending)
+    }
+
+    /// Sends the content of `buf` at the root to every other member of the group.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::broadcast].
+    pub fn broadcast<T: AsFiType>(
+        &self,
+        buf: &mut [T],
+        desc: Option<&MemoryRegionDesc>,
+        root_mapped_addr: &MappedAddress,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        let root = self.rank_of(root_mapped_addr)?;
+        let seq = self.next_seq();
+        let mut pending = self.pending();
+        if root == self.rank {
+            for peer in self.peers() {
+                self.post_send(&mut pending, buf, desc, seq, peer)?;
+            }
+        } else {
+            self.post_recv(&mut pending, buf, desc, seq, root)?;
+        }
+        self.wait_all(&mut pending)
+    }
+
+    /// Sends the `i`-th chunk of `buf` to the member of rank `i`, and stores the chunk received from the member of
+    /// rank `i` in the `i`-th chunk of `result`.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::alltoall].
+    pub fn alltoall<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc>,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        let chunk = self.chunk_len(buf.len())?;
+        check_len(result.len(), buf.len())?;
+        // Nothing to exchange, and zero-length messages would only be matched by chance
+        if chunk == 0 {
+            return Ok(());
+        }
+
+        let seq = self.next_seq();
+        let mut pending = self.pending();
+        for (peer, res) in result.chunks_mut(chunk).enumerate() {
+            if peer == self.rank {
+                res.copy_from_slice(&buf[peer * chunk..(peer + 1) * chunk]);
+            } else {
+                self.post_recv(&mut pending, res, result_desc, seq, peer)?;
+            }
+        }
+        for peer in self.peers() {
+            self.post_send(
+                &mut pending,
+                &buf[peer * chunk..(peer + 1) * chunk],
+                desc,
+                seq,
+                peer,
+            )?;
+        }
+        self.wait_all(&mut pending)
+    }
+
+    /// Combines the content of `buf` across the group with `op`, storing the outcome in `result` on every member.
+    ///
+    /// Fails with `FI_EOPNOTSUPP` if `op` is not defined for `T`. Reductions defined by the application are
+    /// run by [SoftwareCollective::allreduce_with] instead.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::allreduce].
+    #[allow(clippy::too_many_arguments)]
+    pub fn allreduce<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        _result_desc: Option<&MemoryRegionDesc>,
+        op: ReduceOp,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        reduce_into::<T>(op, &mut [], &[])?;
+        self.allreduce_by(buf, desc, result, |acc, other| {
+            // The operation has already been validated for T
+            reduce_into(op, acc, other).unwrap()
+        })
+    }
+
+    /// Same as [SoftwareCollective::allreduce], combining the elements of `buf` with the user-defined reduction `op`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn allreduce_with<T: Pod>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        _result_desc: Option<&MemoryRegionDesc>,
+        op: &impl ReduceFn<T>,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        self.allreduce_by(buf, desc, result, |acc, other| apply_custom(op, acc, other))
+    }
+
+    /// Gathers the content of `buf` from every member of the group into `result`, ordered by rank.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::allgather].
+    pub fn allgather<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc>,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        check_len(result.len(), buf.len() * self.size())?;
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let seq = self.next_seq();
+        let mut pending = self.pending();
+        for (peer, res) in result.chunks_mut(buf.len()).enumerate() {
+            if peer == self.rank {
+                res.copy_from_slice(buf);
+            } else {
+                self.post_recv(&mut pending, res, result_desc, seq, peer)?;
+            }
+        }
+        for peer in self.peers() {
+            self.post_send(&mut pending, buf, desc, seq, peer)?;
+        }
+        self.wait_all(&mut pending)
+    }
+
+    /// Combines the `i`-th chunk of `buf` across the group with `op`, storing the outcome in `result` on the
+    /// member of rank `i`.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::reduce_scatter].
+    #[allow(clippy::too_many_arguments)]
+    pub fn reduce_scatter<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        _result_desc: Option<&MemoryRegionDesc>,
+        op: ReduceOp,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        reduce_into::<T>(op, &mut [], &[])?;
+        self.reduce_scatter_by(buf, desc, result, |acc, other| {
+            reduce_into(op, acc, other).unwrap()
+        })
+    }
+
+    /// Same as [SoftwareCollective::reduce_scatter], combining the elements of `buf` with the user-defined
+    /// reduction `op`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reduce_scatter_with<T: Pod>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        _result_desc: Option<&MemoryRegionDesc>,
+        op: &impl ReduceFn<T>,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        self.reduce_scatter_by(buf, desc, result, |acc, other| apply_custom(op, acc, other))
+    }
+
+    /// Combines the content of `buf` across the group with `op`, storing the outcome in `result` at the root.
+    ///
+    /// `result` is left untouched on the other members.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::reduce].
+    #[allow(clippy::too_many_arguments)]
+    pub fn reduce<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        _result_desc: Option<&MemoryRegionDesc>,
+        root_mapped_addr: &MappedAddress,
+        op: ReduceOp,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        reduce_into::<T>(op, &mut [], &[])?;
+        self.reduce_by(buf, desc, result, root_mapped_addr, |acc, other| {
+            reduce_into(op, acc, other).unwrap()
+        })
+    }
+
+    /// Same as [SoftwareCollective::reduce], combining the elements of `buf` with the user-defined reduction `op`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reduce_with<T: Pod>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        _result_desc: Option<&MemoryRegionDesc>,
+        root_mapped_addr: &MappedAddress,
+        op: &impl ReduceFn<T>,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        self.reduce_by(buf, desc, result, root_mapped_addr, |acc, other| {
+            apply_custom(op, acc, other)
+        })
+    }
+
+    /// Sends the `i`-th chunk of `buf` at the root to the member of rank `i`, which stores it in `result`.
+    ///
+    /// `buf` is only read at the root.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::scatter].
+    #[allow(clippy::too_many_arguments)]
+    pub fn scatter<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc>,
+        root_mapped_addr: &MappedAddress,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        let root = self.rank_of(root_mapped_addr)?;
+        let chunk = result.len();
+
+        let seq = self.next_seq();
+        let mut pending = self.pending();
+        if root == self.rank {
+            check_len(buf.len(), chunk * self.size())?;
+            result.copy_from_slice(&buf[root * chunk..(root + 1) * chunk]);
+            for peer in self.peers() {
+                self.post_send(
+                    &mut pending,
+                    &buf[peer * chunk..(peer + 1) * chunk],
+                    desc,
+                    seq,
+                    peer,
+                )?;
+            }
+        } else {
+            self.post_recv(&mut pending, result, result_desc, seq, root)?;
+        }
+        self.wait_all(&mut pending)
+    }
+
+    /// Gathers the content of `buf` from every member of the group into `result` at the root, ordered by rank.
+    ///
+    /// `result` is left untouched on the other members.
+    ///
+    /// Counterpart of [crate::comm::collective::CollectiveEp::gather].
+    #[allow(clippy::too_many_arguments)]
+    pub fn gather<T: AsFiType>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        result_desc: Option<&MemoryRegionDesc>,
+        root_mapped_addr: &MappedAddress,
+        _options: CollectiveOptions,
+    ) -> Result<(), Error> {
+        let root = self.rank_of(root_mapped_addr)?;
+        if root == self.rank {
+            check_len(result.len(), buf.len() * self.size())?;
+        }
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let seq = self.next_seq();
+        let mut pending = self.pending();
+        if root == self.rank {
+            for (peer, res) in result.chunks_mut(buf.len()).enumerate() {
+                if peer == self.rank {
+                    res.copy_from_slice(buf);
+                } else {
+                    self.post_recv(&mut pending, res, result_desc, seq, peer)?;
+                }
+            }
+        } else {
+            self.post_send(&mut pending, buf, desc, seq, root)?;
+        }
+        self.wait_all(&mut pending)
+    }
+
+    fn allreduce_by<T: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        combine: impl FnMut(&mut [T], &[T]),
+    ) -> Result<(), Error> {
+        check_len(result.len(), buf.len())?;
+
+        let mut contributions = buf.repeat(self.size());
+        self.exchange(buf, desc, &mut contributions, |_| 0..buf.len())?;
+        reduce_contributions(&contributions, buf.len(), result, combine);
+        Ok(())
+    }
+
+    fn reduce_scatter_by<T: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        combine: impl FnMut(&mut [T], &[T]),
+    ) -> Result<(), Error> {
+        let chunk = self.chunk_len(buf.len())?;
+        check_len(result.len(), chunk)?;
+
+        let mut contributions = buf[..chunk].repeat(self.size());
+        self.exchange(buf, desc, &mut contributions, |peer| {
+            peer * chunk..(peer + 1) * chunk
+        })?;
+        reduce_contributions(&contributions, chunk, result, combine);
+        Ok(())
+    }
+
+    fn reduce_by<T: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        result: &mut [T],
+        root_mapped_addr: &MappedAddress,
+        combine: impl FnMut(&mut [T], &[T]),
+    ) -> Result<(), Error> {
+        let root = self.rank_of(root_mapped_addr)?;
+        if root == self.rank {
+            check_len(result.len(), buf.len())?;
+        }
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        let seq = self.next_seq();
+        if root == self.rank {
+            let mut contributions = buf.repeat(self.size());
+            // Declared after the receive buffers, so that they outlive the operations
+            let mut pending = self.pending();
+            for (peer, contrib) in contributions.chunks_mut(buf.len()).enumerate() {
+                if peer != self.rank {
+                    self.post_recv(&mut pending, contrib, None, seq, peer)?;
+                }
+            }
+            self.wait_all(&mut pending)?;
+            reduce_contributions(&contributions, buf.len(), result, combine);
+        } else {
+            let mut pending = self.pending();
+            self.post_send(&mut pending, buf, desc, seq, root)?;
+            self.wait_all(&mut pending)?;
+        }
+        Ok(())
+    }
+
+    fn next_seq(&self) -> u64 {
+        self.seq.fetch_add(1, Ordering::Relaxed) & ((1 << SEQ_BITS) - 1)
+    }
+
+    fn tag(&self, seq: u64, src: usize) -> u64 {
+        self.tag_base | (seq << RANK_BITS) | src as u64
+    }
+
+    fn peers(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.size()).filter(move |peer| *peer != self.rank)
+    }
+
+    fn rank_of(&self, mapped_addr: &MappedAddress) -> Result<usize, Error> {
+        self.members
+            .iter()
+            .position(|m| m.raw_addr() == mapped_addr.raw_addr())
+            .ok_or_else(|| Error::from_err_code(libfabric_sys::FI_EINVAL))
+    }
+
+    fn chunk_len(&self, len: usize) -> Result<usize, Error> {
+        if len % self.size() != 0 {
+            return Err(Error::from_err_code(libfabric_sys::FI_EINVAL));
+        }
+        Ok(len / self.size())
+    }
+
+    // Sends `buf[range(peer)]` to every peer and receives theirs in the respective chunk of `contributions`
+    fn exchange<T: Copy>(
+        &self,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        contributions: &mut [T],
+        range: impl Fn(usize) -> std::ops::Range<usize>,
+    ) -> Result<(), Error> {
+        let chunk = range(self.rank).len();
+        if chunk == 0 {
+            return Ok(());
+        }
+
+        let seq = self.next_seq();
+        let mut pending = self.pending();
+        for (peer, contrib) in contributions.chunks_mut(chunk).enumerate() {
+            if peer == self.rank {
+                contrib.copy_from_slice(&buf[range(peer)]);
+            } else {
+                self.post_recv(&mut pending, contrib, None, seq, peer)?;
+            }
+        }
+        for peer in self.peers() {
+            self.post_send(&mut pending, &buf[range(peer)], desc, seq, peer)?;
+        }
+        self.wait_all(&mut pending)
+    }
+
+    fn post_send<T>(
+        &self,
+        pending: &mut Vec<Context>,
+        buf: &[T],
+        desc: Option<&MemoryRegionDesc>,
+        seq: u64,
+        dest: usize,
+    ) -> Result<(), Error> {
+        let tag = self.tag(seq, self.rank);
+        self.post(pending, |ctx| {
+            self.ep
+                .tsend_to_with_context(buf, desc.copied(), &self.members[dest], tag, ctx)
+        })
+    }
+
+    fn post_recv<T>(
+        &self,
+        pending: &mut Vec<Context>,
+        buf: &mut [T],
+        desc: Option<&MemoryRegionDesc>,
+        seq: u64,
+        src: usize,
+    ) -> Result<(), Error> {
+        let tag = self.tag(seq, src);
+        self.post(pending, |ctx| {
+            self.ep
+                .trecv_from_any_with_context(&mut *buf, desc.copied(), tag, None, ctx)
+        })
+    }
+
+    fn post(
+        &self,
+        pending: &mut Vec<Context>,
+        mut op: impl FnMut(&mut Context) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut ctx = Context(ContextType::Context2(Box::new(Context2::new(0))));
+        loop {
+            match op(&mut ctx) {
+                Ok(()) => break,
+                Err(err) if matches!(err.kind, ErrorKind::TryAgain) => self.progress(pending)?,
+                Err(err) => return Err(err),
+            }
+        }
+        pending.push(ctx);
+        Ok(())
+    }
+
+    fn progress(&self, pending: &mut Vec<Context>) -> Result<(), Error> {
+        self.progress_cq(self.tx_cq, pending)?;
+        if !std::ptr::eq(self.tx_cq, self.rx_cq) {
+            self.progress_cq(self.rx_cq, pending)?;
+        }
+        Ok(())
+    }
+
+    fn progress_cq(&self, cq: &CQ, pending: &mut Vec<Context>) -> Result<(), Error> {
+        match cq.read(pending.len().max(1)) {
+            Ok(mut completions) => {
+                while let Some(comp) = completions.pop() {
+                    self.complete(pending, comp.op_context(), Ok(comp))?;
+                }
+                Ok(())
+            }
+            Err(err) if matches!(err.kind, ErrorKind::TryAgain) => Ok(()),
+            Err(err) if matches!(err.kind, ErrorKind::ErrorAvailable) => {
+                let err = cq.readerr(0)?;
+                self.complete(pending, err.c_err.op_context, Err(err.error()))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    // Removes the operation of `op_context` from `pending`, returning its error if any. Completions of other
+    // operations are handed over to their own context, whose kind is decided by the fabric.
+    fn complete(
+        &self,
+        pending: &mut Vec<Context>,
+        op_context: *mut std::ffi::c_void,
+        comp: Result<SingleCompletion, Error>,
+    ) -> Result<(), Error> {
+        // Completions of operations that did not register a context have no owner
+        if op_context.is_null() {
+            return Ok(());
+        }
+        if let Some(pos) = pending
+            .iter()
+            .position(|ctx| std::ptr::eq(ctx.inner(), op_context))
+        {
+            // The operation is over, the provider no longer accesses its buffer
+            pending.swap_remove(pos);
+            return comp.map(|_| ());
+        }
+        if self.using_context2 {
+            unsafe { (*(op_context as *mut Context2)).set_completion_done(comp) }
+        } else {
+            unsafe { (*(op_context as *mut Context1)).set_completion_done(comp) }
+        }
+        Ok(())
+    }
+
+    fn wait_all(&self, pending: &mut Vec<Context>) -> Result<(), Error> {
+        while !pending.is_empty() {
+            self.progress(pending)?;
+        }
+        Ok(())
+    }
+
+    fn pending(&self) -> Pending<'_, 'a, E, CQ> {
+        Pending {
+            coll: self,
+            ctxs: Vec::new(),
+        }
+    }
+
+    // Cancels the pending operations and waits for all of them to complete, whatever the outcome.
+    //
+    // Operations that already completed are not found by `fi_cancel`, and providers that cannot cancel
+    // operations leave them running: in both cases their completion is still awaited, as their buffers are
+    // released once this returns.
+    fn abort(&self, pending: &mut Vec<Context>) {
+        for ctx in pending.iter_mut() {
+            let _ = self.ep.cancel(ctx);
+        }
+        while !pending.is_empty() {
+            if self.progress(pending).is_err() {
+                std::thread::yield_now();
+            }
+        }
+    }
+}
+
+// The operations posted by a collective, canceled and drained if it returns before they complete
+struct Pending<'c, 'a, E: TagSendEp + TagRecvEp + ActiveEndpoint, CQ: ReadCq + ?Sized> {
+    coll: &'c SoftwareCollective<'a, E, CQ>,
+    ctxs: Vec<Context>,
+}
+
+impl<E: TagSendEp + TagRecvEp + ActiveEndpoint, CQ: ReadCq + ?Sized> std::ops::Deref
+    for Pending<'_, '_, E, CQ>
+{
+    type Target = Vec<Context>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ctxs
+    }
+}
+
+impl<E: TagSendEp + TagRecvEp + ActiveEndpoint, CQ: ReadCq + ?Sized> std::ops::DerefMut
+    for Pending<'_, '_, E, CQ>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.ctxs
+    }
+}
+
+impl<E: TagSendEp + TagRecvEp + ActiveEndpoint, CQ: ReadCq + ?Sized> Drop
+    for Pending<'_, '_, E, CQ>
+{
+    fn drop(&mut self) {
+        if !self.ctxs.is_empty() {
+            self.coll.abort(&mut self.ctxs);
+        }
+    }
+}
+
+// Combines the chunks of `contributions` into `result`, in rank order
+fn reduce_contributions<T: Copy>(
+    contributions: &[T],
+    chunk: usize,
+    result: &mut [T],
+    mut combine: impl FnMut(&mut [T], &[T]),
+) {
+    result.copy_from_slice(&contributions[..chunk]);
+    for contrib in contributions.chunks(chunk.max(1)).skip(1) {
+        combine(result, contrib);
+    }
+}
+
+fn apply_custom<T>(op: &impl ReduceFn<T>, acc: &mut [T], other: &[T]) {
+    for (a, b) in acc.iter_mut().zip(other) {
+        op.reduce(a, b);
+    }
+}
+
+fn check_len(len: usize, expected: usize) -> Result<(), Error> {
+    if len != expected {
+        return Err(Error::from_err_code(libfabric_sys::FI_EINVAL));
+    }
+    Ok(())
+}
+
+macro_rules! reduce_int {
+    ($type_: ty, $op: expr, $acc: expr, $other: expr) => {{
+        let f: fn($type_, $type_) -> $type_ = match $op {
+            ReduceOp::Min => |a, b| a.min(b),
+            ReduceOp::Max => |a, b| a.max(b),
+            ReduceOp::Sum => |a, b| a.wrapping_add(b),
+            ReduceOp::Prod => |a, b| a.wrapping_mul(b),
+            ReduceOp::Lor => |a, b| (a != 0 || b != 0) as $type_,
+            ReduceOp::Land => |a, b| (a != 0 && b != 0) as $type_,
+            ReduceOp::Lxor => |a, b| ((a != 0) ^ (b != 0)) as $type_,
+            ReduceOp::Bor => |a, b| a | b,
+            ReduceOp::Band => |a, b| a & b,
+            ReduceOp::Bxor => |a, b| a ^ b,
+            _ => return Err(Error::from_err_code(libfabric_sys::FI_EOPNOTSUPP)),
+        };
+        apply::<$type_, _>(f, $acc, $other);
+        Ok(())
+    }};
+}
+
+macro_rules! reduce_float {
+    ($type_: ty, $op: expr, $acc: expr, $other: expr) => {{
+        let f: fn($type_, $type_) -> $type_ = match $op {
+            ReduceOp::Min => |a, b| a.min(b),
+            ReduceOp::Max => |a, b| a.max(b),
+            ReduceOp::Sum => |a, b| a + b,
+            ReduceOp::Prod => |a, b| a * b,
+            ReduceOp::Lor => |a, b| if a != 0. || b != 0. { 1. } else { 0. },
+            ReduceOp::Land => |a, b| if a != 0. && b != 0. { 1. } else { 0. },
+            ReduceOp::Lxor => |a, b| if (a != 0.) ^ (b != 0.) { 1. } else { 0. },
+            _ => return Err(Error::from_err_code(libfabric_sys::FI_EOPNOTSUPP)),
+        };
+        apply::<$type_, _>(f, $acc, $other);
+        Ok(())
+    }};
+}
+
+fn apply<P: Copy, T: AsFiType>(f: fn(P, P) -> P, acc: &mut [T], other: &[T]) {
+    debug_assert_eq!(std::mem::size_of::<P>(), std::mem::size_of::<T>());
+    // T and P share the same libfabric datatype, hence the same representation
+    let acc = unsafe { std::slice::from_raw_parts_mut(acc.as_mut_ptr().cast::<P>(), acc.len()) };
+    let other = unsafe { std::slice::from_raw_parts(other.as_ptr().cast::<P>(), other.len()) };
+    for (a, b) in acc.iter_mut().zip(other) {
+        *a = f(*a, *b);
+    }
+}
+
+// Combines `other` into `acc` element-wise, failing if `op` is not a reduction defined for `T`
+fn reduce_into<T: AsFiType>(op: ReduceOp, acc: &mut [T], other: &[T]) -> Result<(), Error> {
+    match T::as_fi_datatype() {
+        libfabric_sys::fi_datatype_FI_INT8 => reduce_int!(i8, op, acc, other),
+        libfabric_sys::fi_datatype_FI_INT16 => reduce_int!(i16, op, acc, other),
+        libfabric_sys::fi_datatype_FI_INT32 => reduce_int!(i32, op, acc, other),
+        libfabric_sys::fi_datatype_FI_INT64 => reduce_int!(i64, op, acc, other),
+        libfabric_sys::fi_datatype_FI_INT128 => reduce_int!(i128, op, acc, other),
+        libfabric_sys::fi_datatype_FI_UINT8 => reduce_int!(u8, op, acc, other),
+        libfabric_sys::fi_datatype_FI_UINT16 => reduce_int!(u16, op, acc, other),
+        libfabric_sys::fi_datatype_FI_UINT32 => reduce_int!(u32, op, acc, other),
+        libfabric_sys::fi_datatype_FI_UINT64 => reduce_int!(u64, op, acc, other),
+        libfabric_sys::fi_datatype_FI_UINT128 => reduce_int!(u128, op, acc, other),
+        libfabric_sys::fi_datatype_FI_FLOAT => reduce_float!(f32, op, acc, other),
+        libfabric_sys::fi_datatype_FI_DOUBLE => reduce_float!(f64, op, acc, other),
+        _ => Err(Error::from_err_code(libfabric_sys::FI_EOPNOTSUPP)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enums::ReduceOp;
+
+    use super::{apply_custom, reduce_contributions, reduce_into};
+
+    #[test]
+    fn reductions_follow_the_datatype() {
+        let mut acc = [1i32, -4, 6];
+        reduce_into(ReduceOp::Max, &mut acc, &[3, -5, 6]).unwrap();
+        assert_eq!(acc, [3, -4, 6]);
+
+        let mut acc = [0b1100u8, 0b0001];
+        reduce_into(ReduceOp::Bor, &mut acc, &[0b0011, 0b0001]).unwrap();
+        assert_eq!(acc, [0b1111, 0b0001]);
+
+        let mut acc = [1.5f64, 2.0];
+        reduce_into(ReduceOp::Sum, &mut acc, &[0.5, -2.0]).unwrap();
+        assert_eq!(acc, [2.0, 0.0]);
+
+        // Bitwise operations are not defined on floating point values
+        assert!(reduce_into(ReduceOp::Bxor, &mut [1.0f32], &[2.0]).is_err());
+        assert!(reduce_into(ReduceOp::AtomicWrite, &mut [1u64], &[2]).is_err());
+    }
+
+    #[test]
+    fn custom_reductions_apply_in_rank_order() {
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct MinLoc {
+            value: f64,
+            index: u64,
+        }
+
+        // Ties are broken by the contribution of the lowest rank
+        let min_loc = |acc: &mut MinLoc, other: &MinLoc| {
+            if other.value < acc.value {
+                *acc = *other;
+            }
+        };
+        let contributions = [
+            MinLoc {
+                value: 2.0,
+                index: 0,
+            },
+            MinLoc {
+                value: 1.0,
+                index: 1,
+            },
+            MinLoc {
+                value: 1.0,
+                index: 2,
+            },
+        ];
+        let mut result = [MinLoc {
+            value: 0.0,
+            index: 0,
+        }];
+        reduce_contributions(&contributions, 1, &mut result, |acc, other| {
+            apply_custom(&min_loc, acc, other)
+        });
+        assert_eq!(
+            result,
+            [MinLoc {
+                value: 1.0,
+                index: 1
+            }]
+        );
+    }
+}
@@ -22,6 +22,8 @@ use crate::utils::check_error;
 use crate::utils::Either;
 use crate::xcontext::RxContextBase;
 use crate::xcontext::RxContextImplBase;
+use crate::xcontext::SharedRxContextBase;
+use crate::xcontext::SharedRxContextImplBase;
 use crate::xcontext::TxContextBase;
 use crate::xcontext::TxContextImplBase;
 use crate::Context;
@@ -711,6 +713,9 @@ impl<I: TagCap + RecvMod, STATE: EpState, CQ: ?Sized + ReadCq> TagRecvEpImpl
 {
 }
 
+impl<I: TagCap + RecvMod, CQ: ?Sized + ReadCq> TagRecvEpImpl for SharedRxContextBase<I, CQ> {}
+impl<I: TagCap + RecvMod, CQ: ?Sized + ReadCq> TagRecvEpImpl for SharedRxContextImplBase<I, CQ> {}
+
 impl<E: TagRecvEpImpl> TagRecvEpImpl for EndpointBase<E, Connected> {}
 impl<E: TagRecvEpImpl> TagRecvEpImpl for EndpointBase<E, Connectionless> {}
 
@@ -3,7 +3,8 @@ use crate::UnspecMappedAddress;
 use crate::{
     domain::{DomainBase, DomainImplT},
     fid::AsTypedFid,
-    Context, MyRc, MyRefCell, SyncSend,
+    progress::{progress_result, Progressable},
+    Context, MyRc, MyRefCell, SyncSend, Waitable,
 };
 use crate::{
     enums::{CompletionFlags, WaitObjType},
@@ -11,6 +12,7 @@ use crate::{
     fid::{AsRawFid, AsRawTypedFid, CqRawFid, OwnedCqFid, RawFid},
     MappedAddress, RawMappedAddress,
 };
+use std::marker::PhantomData;
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
 //================== CompletionQueue (fi_cq) ==================//
 
@@ -45,12 +47,34 @@ macro_rules! read_cq_entry {
 pub trait EntryFormat: Clone {}
 pub trait SyncCq {}
 
+/// An [EntryFormat] known at compile time.
+///
+/// Completion queues built with one of these formats (see [CompletionQueueBuilder::format_ctx] and siblings)
+/// return their entries directly as a `Vec<CompletionEntry<F>>` instead of the runtime [Completion] enum.
+pub trait TypedEntryFormat: EntryFormat {
+    #[doc(hidden)]
+    const FORMAT: crate::enums::CqFormat;
+}
+
 impl EntryFormat for libfabric_sys::fi_cq_entry {}
 impl EntryFormat for libfabric_sys::fi_cq_msg_entry {}
 impl EntryFormat for libfabric_sys::fi_cq_data_entry {}
 impl EntryFormat for libfabric_sys::fi_cq_tagged_entry {}
 impl EntryFormat for () {}
 
+impl TypedEntryFormat for libfabric_sys::fi_cq_entry {
+    const FORMAT: crate::enums::CqFormat = crate::enums::CqFormat::Context;
+}
+impl TypedEntryFormat for libfabric_sys::fi_cq_msg_entry {
+    const FORMAT: crate::enums::CqFormat = crate::enums::CqFormat::Msg;
+}
+impl TypedEntryFormat for libfabric_sys::fi_cq_data_entry {
+    const FORMAT: crate::enums::CqFormat = crate::enums::CqFormat::Data;
+}
+impl TypedEntryFormat for libfabric_sys::fi_cq_tagged_entry {
+    const FORMAT: crate::enums::CqFormat = crate::enums::CqFormat::Tagged;
+}
+
 /// A single completion entry returned from a completion queue.
 ///
 /// Corresponds to `fi_cq_entry` in libfabric.
@@ -151,16 +175,30 @@ impl SingleCompletion {
     }
 }
 
-pub struct CompletionQueueImpl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> {
+/// The implementation of a [CompletionQueue].
+///
+/// `F` is the format of the entries of the queue when known at compile time, or [UnspecEntry] when the format is
+/// selected at runtime through [CompletionQueueBuilder::format].
+pub struct CompletionQueueImpl<
+    const WAIT: bool,
+    const RETRIEVE: bool,
+    const FD: bool,
+    F: EntryFormat = UnspecEntry,
+> {
     pub(crate) c_cq: OwnedCqFid,
     pub(crate) entry_buff: MyRefCell<Completion>,
     pub(crate) error_buff: MyRefCell<CompletionError>,
     #[allow(dead_code)]
     pub(crate) wait_obj: Option<libfabric_sys::fi_wait_obj>,
     pub(crate) _domain_rc: MyRc<dyn DomainImplT>,
+    _context_token: Box<u8>,
+    phantom: PhantomData<fn() -> F>,
 }
 
-impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> SyncCq for CompletionQueueImpl<WAIT, RETRIEVE, FD>{}
+impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: EntryFormat> SyncCq
+    for CompletionQueueImpl<WAIT, RETRIEVE, FD, F>
+{
+}
 
 /// Owned wrapper around a libfabric `fid_cq`.
 ///
@@ -225,8 +263,9 @@ pub trait WaitCq: AsTypedFid<CqRawFid> {
         timeout: i32,
     ) -> Result<Option<MappedAddress>, crate::error::Error> {
         let p_cond = cond as *const usize as *const std::ffi::c_void;
-        let mut address = 0;
-        let p_address = &mut address;
+        // One source address is written per entry read, only the first one is returned
+        let mut addresses = vec![crate::FI_ADDR_NOTAVAIL; count.max(1)];
+        let p_address = addresses.as_mut_ptr();
         let err = read_cq_entry!(
             libfabric_sys::inlined_fi_cq_sreadfrom,
             self.as_typed_fid_mut().as_raw_typed_fid(),
@@ -237,13 +276,7 @@ pub trait WaitCq: AsTypedFid<CqRawFid> {
             timeout
         );
 
-        let address = if address == crate::FI_ADDR_NOTAVAIL {
-            None
-        } else {
-            Some(MappedAddress::from_raw_addr_no_av(
-                RawMappedAddress::Unspec(address),
-            ))
-        };
+        let address = source_address(addresses[0]);
 
         if err < 0 {
             Err(crate::error::Error::from_err_code(
@@ -353,8 +386,9 @@ pub trait ReadCq: AsTypedFid<CqRawFid> + SyncSend {
         count: usize,
         buffer: &mut Completion,
     ) -> Result<Option<MappedAddress>, crate::error::Error> {
-        let mut address = 0;
-        let p_address = &mut address as *mut libfabric_sys::fi_addr_t;
+        // One source address is written per entry read, only the first one is returned
+        let mut addresses = vec![crate::FI_ADDR_NOTAVAIL; count.max(1)];
+        let p_address = addresses.as_mut_ptr();
         let err = read_cq_entry!(
             libfabric_sys::inlined_fi_cq_readfrom,
             self.as_typed_fid_mut().as_raw_typed_fid(),
@@ -362,13 +396,7 @@ pub trait ReadCq: AsTypedFid<CqRawFid> + SyncSend {
             buffer,
             p_address
         );
-        let address = if address == crate::FI_ADDR_NOTAVAIL {
-            None
-        } else {
-            Some(MappedAddress::from_raw_addr_no_av(
-                RawMappedAddress::Unspec(address),
-            ))
-        };
+        let address = source_address(addresses[0]);
         if err < 0 {
             Err(crate::error::Error::from_err_code(
                 (-err).try_into().unwrap(),
@@ -445,13 +473,13 @@ pub trait ReadCq: AsTypedFid<CqRawFid> + SyncSend {
     fn readerr(&self, flags: u64) -> Result<CompletionError, crate::error::Error>;
 }
 
-impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> SyncSend
-    for CompletionQueueImpl<WAIT, RETRIEVE, FD>
+impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: EntryFormat> SyncSend
+    for CompletionQueueImpl<WAIT, RETRIEVE, FD, F>
 {
 }
 
-impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> ReadCq
-    for CompletionQueueImpl<WAIT, RETRIEVE, FD>
+impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: EntryFormat> ReadCq
+    for CompletionQueueImpl<WAIT, RETRIEVE, FD, F>
 {
     fn read(&self, count: usize) -> Result<Completion, crate::error::Error> {
         #[cfg(feature = "thread-safe")]
@@ -488,7 +516,9 @@ impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> ReadCq
     }
 }
 
-impl<const RETRIEVE: bool, const FD: bool> WaitCq for CompletionQueueImpl<true, RETRIEVE, FD> {
+impl<const RETRIEVE: bool, const FD: bool, F: EntryFormat> WaitCq
+    for CompletionQueueImpl<true, RETRIEVE, FD, F>
+{
     fn sread_with_cond(
         &self,
         count: usize,
@@ -518,8 +548,8 @@ impl<const RETRIEVE: bool, const FD: bool> WaitCq for CompletionQueueImpl<true,
     }
 }
 
-impl<'a, const WAIT: bool, const FD: bool> WaitObjectRetrieve<'a>
-    for CompletionQueueImpl<WAIT, true, FD>
+impl<'a, const WAIT: bool, const FD: bool, F: EntryFormat> WaitObjectRetrieve<'a>
+    for CompletionQueueImpl<WAIT, true, FD, F>
 {
     fn wait_object(&self) -> Result<WaitObjType<'a>, crate::error::Error> {
         if let Some(wait) = self.wait_obj {
@@ -581,8 +611,8 @@ pub trait WaitObjectRetrieve<'a> {
     fn wait_object(&self) -> Result<WaitObjType<'a>, crate::error::Error>;
 }
 
-impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool>
-    CompletionQueueImpl<WAIT, RETRIEVE, FD>
+impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: EntryFormat>
+    CompletionQueueImpl<WAIT, RETRIEVE, FD, F>
 {
     pub(crate) fn new<EQ: ?Sized + 'static + SyncSend>(
         domain: MyRc<DomainImplBase<EQ>>,
@@ -591,6 +621,13 @@ impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool>
         default_buff_size: usize,
     ) -> Result<Self, crate::error::Error> {
         let mut c_cq: CqRawFid = std::ptr::null_mut();
+        // Queues opened without a context get a unique one, which is how poll sets report them
+        let context_token = Box::new(0u8);
+        let context = if context.is_null() {
+            (&*context_token as *const u8).cast_mut().cast()
+        } else {
+            context
+        };
 
         let err = unsafe {
             libfabric_sys::inlined_fi_cq_open(
@@ -630,13 +667,15 @@ impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool>
                 },
 
                 error_buff: MyRefCell::new(CompletionError::new()),
+                _context_token: context_token,
+                phantom: PhantomData,
             })
         }
     }
 }
 
-impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool>
-    CompletionQueue<CompletionQueueImpl<WAIT, RETRIEVE, FD>>
+impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: EntryFormat>
+    CompletionQueue<CompletionQueueImpl<WAIT, RETRIEVE, FD, F>>
 {
     // pub(crate) fn new<EQ: AsFid + 'static, T0>(_options: T, domain: &crate::domain::DomainBase<EQ>, attr: CompletionQueueAttr, context: Option<&mut T0>, default_buff_size: usize) -> Result<Self, crate::error::Error> {
     pub(crate) fn new<EQ: ?Sized + 'static + SyncSend>(
@@ -704,6 +743,166 @@ impl<T: WaitCq + SyncCq> WaitCq for CompletionQueue<T> {
     }
 }
 
+impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: TypedEntryFormat>
+    CompletionQueue<CompletionQueueImpl<WAIT, RETRIEVE, FD, F>>
+{
+    /// Reads up to `count` completions from a completion queue whose format is known at compile time.
+    ///
+    /// Contrary to [ReadCq::read], the entries are returned directly instead of being wrapped in a [Completion].
+    ///
+    /// Corresponds to `fi_cq_read`
+    pub fn read(&self, count: usize) -> Result<Vec<CompletionEntry<F>>, crate::error::Error> {
+        let mut entries = Vec::new();
+        let err = read_cq_entry_!(
+            libfabric_sys::inlined_fi_cq_read,
+            self.as_typed_fid_mut().as_raw_typed_fid(),
+            count,
+            entries,
+        );
+        if err < 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            Ok(entries)
+        }
+    }
+
+    /// Similar to [Self::read] with the exception that it also returns the source address of each entry
+    ///
+    /// Entries without a source address are paired with None
+    ///
+    /// Corresponds to `fi_cq_readfrom`
+    pub fn readfrom(
+        &self,
+        count: usize,
+    ) -> Result<Vec<(CompletionEntry<F>, Option<MappedAddress>)>, crate::error::Error> {
+        let mut entries = Vec::new();
+        let mut addresses = vec![crate::FI_ADDR_NOTAVAIL; count];
+        let p_address = addresses.as_mut_ptr();
+        let err = read_cq_entry_!(
+            libfabric_sys::inlined_fi_cq_readfrom,
+            self.as_typed_fid_mut().as_raw_typed_fid(),
+            count,
+            entries,
+            p_address
+        );
+        if err < 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            Ok(with_source_addresses(entries, addresses))
+        }
+    }
+}
+
+impl<const RETRIEVE: bool, const FD: bool, F: TypedEntryFormat>
+    CompletionQueue<CompletionQueueImpl<true, RETRIEVE, FD, F>>
+{
+    /// Blocking version of [Self::read]
+    ///
+    /// Corresponds to `fi_cq_sread` with `cond` set to `NULL`.
+    pub fn sread(
+        &self,
+        count: usize,
+        timeout: i32,
+    ) -> Result<Vec<CompletionEntry<F>>, crate::error::Error> {
+        self.sread_with_cond(count, 0, timeout)
+    }
+
+    /// Similar to [Self::sread] with the ability to set a condition to unblock
+    ///
+    /// Corresponds to `fi_cq_sread`
+    pub fn sread_with_cond(
+        &self,
+        count: usize,
+        cond: usize,
+        timeout: i32,
+    ) -> Result<Vec<CompletionEntry<F>>, crate::error::Error> {
+        let mut entries = Vec::new();
+        let p_cond = cond as *const usize as *const std::ffi::c_void;
+        let err = read_cq_entry_!(
+            libfabric_sys::inlined_fi_cq_sread,
+            self.as_typed_fid_mut().as_raw_typed_fid(),
+            count,
+            entries,
+            p_cond,
+            timeout
+        );
+        if err < 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            Ok(entries)
+        }
+    }
+
+    /// Blocking version of [Self::readfrom]
+    ///
+    /// Corresponds to `fi_cq_sreadfrom` with `cond` set to `NULL`.
+    pub fn sreadfrom(
+        &self,
+        count: usize,
+        timeout: i32,
+    ) -> Result<Vec<(CompletionEntry<F>, Option<MappedAddress>)>, crate::error::Error> {
+        self.sreadfrom_with_cond(count, 0, timeout)
+    }
+
+    /// Similar to [Self::sreadfrom] with the ability to set a condition to unblock
+    ///
+    /// Corresponds to `fi_cq_sreadfrom`
+    pub fn sreadfrom_with_cond(
+        &self,
+        count: usize,
+        cond: usize,
+        timeout: i32,
+    ) -> Result<Vec<(CompletionEntry<F>, Option<MappedAddress>)>, crate::error::Error> {
+        let mut entries = Vec::new();
+        let p_cond = cond as *const usize as *const std::ffi::c_void;
+        let mut addresses = vec![crate::FI_ADDR_NOTAVAIL; count];
+        let p_address = addresses.as_mut_ptr();
+        let err = read_cq_entry_!(
+            libfabric_sys::inlined_fi_cq_sreadfrom,
+            self.as_typed_fid_mut().as_raw_typed_fid(),
+            count,
+            entries,
+            p_address,
+            p_cond,
+            timeout
+        );
+        if err < 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            Ok(with_source_addresses(entries, addresses))
+        }
+    }
+}
+
+fn with_source_addresses<F>(
+    entries: Vec<CompletionEntry<F>>,
+    addresses: Vec<libfabric_sys::fi_addr_t>,
+) -> Vec<(CompletionEntry<F>, Option<MappedAddress>)> {
+    entries
+        .into_iter()
+        .zip(addresses)
+        .map(|(entry, address)| (entry, source_address(address)))
+        .collect()
+}
+
+fn source_address(address: libfabric_sys::fi_addr_t) -> Option<MappedAddress> {
+    if address == crate::FI_ADDR_NOTAVAIL {
+        None
+    } else {
+        Some(MappedAddress::from_raw_addr_no_av(
+            RawMappedAddress::Unspec(address),
+        ))
+    }
+}
+
 impl<'a, T: WaitObjectRetrieve<'a>> CompletionQueue<T> {
     //[TODO] Make this a method of the trait ?
 
@@ -730,8 +929,8 @@ impl<'a, T: WaitObjectRetrieve<'a>> CompletionQueue<T> {
 //     }
 // }
 
-impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool> AsTypedFid<CqRawFid>
-    for CompletionQueueImpl<WAIT, RETRIEVE, FD>
+impl<const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: EntryFormat> AsTypedFid<CqRawFid>
+    for CompletionQueueImpl<WAIT, RETRIEVE, FD, F>
 {
     fn as_typed_fid(&self) -> crate::fid::BorrowedTypedFid<'_, CqRawFid> {
         self.c_cq.as_typed_fid()
@@ -779,7 +978,32 @@ impl<T: AsRawTypedFid<Output = CqRawFid>> AsRawTypedFid for CompletionQueue<T> {
     }
 }
 
-impl AsFd for CompletionQueueImpl<true, true, true> {
+impl<T: ReadCq + 'static> Waitable for CompletionQueue<T> {
+    fn waitable_fid(&self) -> RawFid {
+        self.inner.as_typed_fid().as_raw_fid()
+    }
+
+    fn waitable_clone(&self) -> Box<dyn Waitable> {
+        Box::new(Self {
+            inner: self.inner.clone(),
+        })
+    }
+}
+
+impl<T: ReadCq + SyncCq + 'static> Progressable for CompletionQueue<T> {
+    fn progress_once(&self) -> Result<usize, crate::error::Error> {
+        // Reading zero entries makes progress without consuming any completion
+        progress_result(self.inner.read(0))
+    }
+
+    fn progressable_clone(&self) -> Box<dyn Progressable> {
+        Box::new(Self {
+            inner: self.inner.clone(),
+        })
+    }
+}
+
+impl<F: EntryFormat> AsFd for CompletionQueueImpl<true, true, true, F> {
     fn as_fd(&self) -> BorrowedFd<'_> {
         if let WaitObjType::Fd(fd) = self.wait_object().unwrap() {
             fd
@@ -789,7 +1013,7 @@ impl AsFd for CompletionQueueImpl<true, true, true> {
     }
 }
 
-impl AsRawFd for CompletionQueueImpl<true, true, true> {
+impl<F: EntryFormat> AsRawFd for CompletionQueueImpl<true, true, true, F> {
     fn as_raw_fd(&self) -> RawFd {
         if let WaitObjType::Fd(fd) = self.wait_object().unwrap() {
             fd.as_raw_fd()
@@ -811,11 +1035,18 @@ impl<T: AsFd> AsFd for CompletionQueue<T> {
 /// `CompletionQueueBuilder` is used to configure and build a new `CompletionQueue`.
 /// It encapsulates an incremental configuration of the address vector, as provided by a `fi_cq_attr`,
 /// followed by a call to `fi_cq_open`  
-pub struct CompletionQueueBuilder<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool> {
+pub struct CompletionQueueBuilder<
+    'a,
+    const WAIT: bool,
+    const RETRIEVE: bool,
+    const FD: bool,
+    F: EntryFormat = UnspecEntry,
+> {
     cq_attr: CompletionQueueAttr,
     ctx: Option<&'a mut Context>,
     // options: Options<WAIT, WAITFD>,
     default_buff_size: usize,
+    phantom: PhantomData<fn() -> F>,
 }
 
 impl<'a> CompletionQueueBuilder<'a, true, false, false> {
@@ -828,6 +1059,7 @@ impl<'a> CompletionQueueBuilder<'a, true, false, false> {
             cq_attr: CompletionQueueAttr::new(),
             ctx: None,
             default_buff_size: 10,
+            phantom: PhantomData,
         }
     }
 }
@@ -838,8 +1070,8 @@ impl Default for CompletionQueueBuilder<'_, true, false, false> {
     }
 }
 
-impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
-    CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD>
+impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool, F: EntryFormat>
+    CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, F>
 {
     /// Specifies the minimum size of a completion queue.
     ///
@@ -855,14 +1087,6 @@ impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
         self
     }
 
-    /// Specificies the completion `format`
-    ///
-    /// Corresponds to setting the field `fi_cq_attr::format`.
-    pub fn format(mut self, format: crate::enums::CqFormat) -> Self {
-        self.cq_attr.format(format);
-        self
-    }
-
     pub fn default_buff_size(mut self, default_buff_size: usize) -> Self {
         self.default_buff_size = default_buff_size;
         self
@@ -871,26 +1095,28 @@ impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
     /// Sets the underlying low-level waiting object to none.
     ///
     /// Corresponds to setting `fi_cq_attr::wait_obj` to `FI_WAIT_NONE`.
-    pub fn wait_none(mut self) -> CompletionQueueBuilder<'a, false, false, false> {
+    pub fn wait_none(mut self) -> CompletionQueueBuilder<'a, false, false, false, F> {
         self.cq_attr.wait_obj(crate::enums::WaitObj::None);
 
         CompletionQueueBuilder {
             cq_attr: self.cq_attr,
             ctx: self.ctx,
             default_buff_size: self.default_buff_size,
+            phantom: PhantomData,
         }
     }
 
     /// Sets the underlying low-level waiting object to FD.
     ///
     /// Corresponds to setting `fi_cq_attr::wait_obj` to `FI_WAIT_FD`.
-    pub fn wait_fd(mut self) -> CompletionQueueBuilder<'a, true, true, true> {
+    pub fn wait_fd(mut self) -> CompletionQueueBuilder<'a, true, true, true, F> {
         self.cq_attr.wait_obj(crate::enums::WaitObj::Fd);
 
         CompletionQueueBuilder {
             cq_attr: self.cq_attr,
             ctx: self.ctx,
             default_buff_size: self.default_buff_size,
+            phantom: PhantomData,
         }
     }
 
@@ -900,93 +1126,45 @@ impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
     pub fn wait_set(
         mut self,
         set: &crate::sync::WaitSet,
-    ) -> CompletionQueueBuilder<'a, true, false, false> {
+    ) -> CompletionQueueBuilder<'a, true, false, false, F> {
         self.cq_attr.wait_obj(crate::enums::WaitObj::Set(set));
 
         CompletionQueueBuilder {
             cq_attr: self.cq_attr,
             ctx: self.ctx,
             default_buff_size: self.default_buff_size,
+            phantom: PhantomData,
         }
     }
 
     /// Sets the underlying low-level waiting object to Mutex+Conditional.
     ///
     /// Corresponds to setting `fi_cq_attr::wait_obj` to `FI_WAIT_MUTEX_COND`.
-    pub fn wait_mutex(mut self) -> CompletionQueueBuilder<'a, true, true, false> {
+    pub fn wait_mutex(mut self) -> CompletionQueueBuilder<'a, true, true, false, F> {
         self.cq_attr.wait_obj(crate::enums::WaitObj::MutexCond);
 
         CompletionQueueBuilder {
             cq_attr: self.cq_attr,
             ctx: self.ctx,
             default_buff_size: self.default_buff_size,
+            phantom: PhantomData,
         }
     }
 
     /// Indicates that the counter will wait without a wait object but instead yield on every wait.
     ///
     /// Corresponds to setting `fi_cq_attr::wait_obj` to `FI_WAIT_YIELD`.
-    pub fn wait_yield(mut self) -> CompletionQueueBuilder<'a, true, false, false> {
+    pub fn wait_yield(mut self) -> CompletionQueueBuilder<'a, true, false, false, F> {
         self.cq_attr.wait_obj(crate::enums::WaitObj::Yield);
 
         CompletionQueueBuilder {
             cq_attr: self.cq_attr,
             ctx: self.ctx,
             default_buff_size: self.default_buff_size,
+            phantom: PhantomData,
         }
     }
 
-    // pub fn format_ctx(mut self) -> CompletionQueueBuilder<'a, T, WAIT,  WAITFD, libfabric_sys::fi_cq_entry> {
-
-    //     self.cq_attr.format(CqFormat::CONTEXT);
-
-    //     CompletionQueueBuilder {
-    //         options: self.options,
-    //         cq_attr: self.cq_attr,
-    //         ctx: self.ctx,
-    //         default_buff_size: self.default_buff_size,
-    //     }
-    // }
-
-    // pub fn format_msg(mut self) -> CompletionQueueBuilder<'a, T, WAIT,  WAITFD, libfabric_sys::fi_cq_msg_entry> {
-
-    //     self.cq_attr.format(CqFormat::MSG);
-
-    //     CompletionQueueBuilder {
-    //         options: self.options,
-    //         cq_attr: self.cq_attr,
-    //         ctx: self.ctx,
-    //         phantom: PhantomData,
-    //         default_buff_size: self.default_buff_size,
-    //     }
-    // }
-
-    // pub fn format_data(mut self) -> CompletionQueueBuilder<'a, T, WAIT,  WAITFD, libfabric_sys::fi_cq_data_entry> {
-
-    //     self.cq_attr.format(CqFormat::DATA);
-
-    //     CompletionQueueBuilder {
-    //         options: self.options,
-    //         cq_attr: self.cq_attr,
-    //         ctx: self.ctx,
-    //         phantom: PhantomData,
-    //         default_buff_size: self.default_buff_size,
-    //     }
-    // }
-
-    // pub fn format_tagged(mut self) -> CompletionQueueBuilder<'a, T, WAIT,  WAITFD, libfabric_sys::fi_cq_tagged_entry> {
-
-    //     self.cq_attr.format(CqFormat::TAGGED);
-
-    //     CompletionQueueBuilder {
-    //         options: self.options,
-    //         cq_attr: self.cq_attr,
-    //         ctx: self.ctx,
-    //         phantom: PhantomData,
-    //         default_buff_size: self.default_buff_size,
-    //     }
-    // }
-
     /// Enables blocking calls like [CompletionQueue::sread_with_cond] to only 'wake up' after
     /// the number of completions in their field `cond` is satisfied.
     ///
@@ -999,11 +1177,15 @@ impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
     /// Sets the context to be passed to the `CompletionQueue`.
     ///
     /// Corresponds to passing a non-NULL `context` value to `fi_cq_open`.
-    pub fn context(self, ctx: &'a mut Context) -> CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD> {
+    pub fn context(
+        self,
+        ctx: &'a mut Context,
+    ) -> CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, F> {
         CompletionQueueBuilder {
             ctx: Some(ctx),
             cq_attr: self.cq_attr,
             default_buff_size: self.default_buff_size,
+            phantom: PhantomData,
         }
     }
 
@@ -1014,9 +1196,10 @@ impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
     pub fn build<EQ: ?Sized + 'static + SyncSend>(
         self,
         domain: &DomainBase<EQ>,
-    ) -> Result<CompletionQueue<CompletionQueueImpl<WAIT, RETRIEVE, FD>>, crate::error::Error> {
+    ) -> Result<CompletionQueue<CompletionQueueImpl<WAIT, RETRIEVE, FD, F>>, crate::error::Error>
+    {
         // CompletionQueue::new(self.options, self.domain, self.cq_attr, self.ctx, self.default_buff_size)
-        CompletionQueue::<CompletionQueueImpl<WAIT, RETRIEVE, FD>>::new(
+        CompletionQueue::<CompletionQueueImpl<WAIT, RETRIEVE, FD, F>>::new(
             domain.inner.clone(),
             self.cq_attr,
             self.ctx,
@@ -1025,6 +1208,63 @@ impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
     }
 }
 
+impl<'a, const WAIT: bool, const RETRIEVE: bool, const FD: bool>
+    CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, UnspecEntry>
+{
+    /// Specificies the completion `format`
+    ///
+    /// Since the format is only known at runtime, entries will be read as a [Completion].
+    /// See [Self::format_ctx], [Self::format_msg], [Self::format_data] and [Self::format_tagged]
+    /// for queues that return their entries directly.
+    ///
+    /// Corresponds to setting the field `fi_cq_attr::format`.
+    pub fn format(mut self, format: crate::enums::CqFormat) -> Self {
+        self.cq_attr.format(format);
+        self
+    }
+
+    /// Sets the completion format to [CtxEntry].
+    ///
+    /// Corresponds to setting the field `fi_cq_attr::format` to `FI_CQ_FORMAT_CONTEXT`.
+    pub fn format_ctx(self) -> CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, CtxEntry> {
+        self.typed_format()
+    }
+
+    /// Sets the completion format to [MsgEntry].
+    ///
+    /// Corresponds to setting the field `fi_cq_attr::format` to `FI_CQ_FORMAT_MSG`.
+    pub fn format_msg(self) -> CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, MsgEntry> {
+        self.typed_format()
+    }
+
+    /// Sets the completion format to [DataEntry].
+    ///
+    /// Corresponds to setting the field `fi_cq_attr::format` to `FI_CQ_FORMAT_DATA`.
+    pub fn format_data(self) -> CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, DataEntry> {
+        self.typed_format()
+    }
+
+    /// Sets the completion format to [TaggedEntry].
+    ///
+    /// Corresponds to setting the field `fi_cq_attr::format` to `FI_CQ_FORMAT_TAGGED`.
+    pub fn format_tagged(self) -> CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, TaggedEntry> {
+        self.typed_format()
+    }
+
+    fn typed_format<F: TypedEntryFormat>(
+        mut self,
+    ) -> CompletionQueueBuilder<'a, WAIT, RETRIEVE, FD, F> {
+        self.cq_attr.format(F::FORMAT);
+
+        CompletionQueueBuilder {
+            cq_attr: self.cq_attr,
+            ctx: self.ctx,
+            default_buff_size: self.default_buff_size,
+            phantom: PhantomData,
+        }
+    }
+}
+
 //================== CompletionQueue Attribute (fi_cq_attr) ==================//
 
 #[derive(Clone)]
@@ -1096,6 +1336,7 @@ impl Default for CompletionQueueAttr {
 
 /// A single completion entry of type `Format`.
 #[derive(Clone, Debug)]
+#[repr(transparent)]
 pub struct CompletionEntry<Format> {
     pub(crate) c_entry: Format,
 }
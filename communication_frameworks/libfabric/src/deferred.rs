@@ -0,0 +1,590 @@
+use std::marker::PhantomData;
+
+use crate::{
+    cntr::Counter,
+    comm::{
+        atomic::AtomicWriteEp,
+        message::{ConnectedSendEp, SendEp},
+        rma::{ConnectedWriteEp, ReadEp, WriteEp},
+        tagged::TagSendEp,
+    },
+    domain::{DomainBase, DomainImplT},
+    enums::{
+        AtomicMsgOptions, ReadMsgOptions, SendMsgOptions, TaggedSendMsgOptions, WriteMsgOptions,
+    },
+    error::{Error, ErrorKind},
+    fid::{AsRawFid, AsRawTypedFid, AsTypedFid, CntrRawFid, EpRawFid},
+    msg::{Msg, MsgAtomic, MsgConnected, MsgRma, MsgRmaConnected, MsgRmaMut, MsgTagged},
+    utils::check_error,
+    AsFiType, MyRc, SyncSend,
+};
+
+enum DeferredOpType {
+    Msg(libfabric_sys::fi_op_msg),
+    Tagged(libfabric_sys::fi_op_tagged),
+    Rma(libfabric_sys::fi_op_rma),
+    Atomic(libfabric_sys::fi_op_atomic),
+    Cntr(libfabric_sys::fi_op_cntr),
+}
+
+/// An operation whose execution is deferred until a [DeferredWork] is triggered.
+///
+/// The operation borrows the endpoint or counter it targets, as well as the message describing it,
+/// for as long as it can be issued.
+pub struct DeferredOp<'a> {
+    op_type: libfabric_sys::fi_op_type,
+    op: DeferredOpType,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> DeferredOp<'a> {
+    fn new(op_type: libfabric_sys::fi_op_type, op: DeferredOpType) -> Self {
+        Self {
+            op_type,
+            op,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Defers a `fi_sendmsg` of `msg` over `ep`.
+    pub fn send<E: SendEp + AsTypedFid<EpRawFid>>(
+        ep: &'a E,
+        msg: &'a Msg<'_>,
+        options: SendMsgOptions,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_SEND,
+            DeferredOpType::Msg(libfabric_sys::fi_op_msg {
+                ep: ep.as_typed_fid_mut().as_raw_typed_fid(),
+                msg: *msg.inner(),
+                flags: options.as_raw(),
+            }),
+        )
+    }
+
+    /// Same as [DeferredOp::send] but for connected endpoints.
+    pub fn send_connected<E: ConnectedSendEp + AsTypedFid<EpRawFid>>(
+        ep: &'a E,
+        msg: &'a MsgConnected<'_>,
+        options: SendMsgOptions,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_SEND,
+            DeferredOpType::Msg(libfabric_sys::fi_op_msg {
+                ep: ep.as_typed_fid_mut().as_raw_typed_fid(),
+                msg: *msg.inner(),
+                flags: options.as_raw(),
+            }),
+        )
+    }
+
+    /// Defers a `fi_tsendmsg` of `msg` over `ep`.
+    pub fn tsend<E: TagSendEp + AsTypedFid<EpRawFid>>(
+        ep: &'a E,
+        msg: &'a MsgTagged<'_>,
+        options: TaggedSendMsgOptions,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_TSEND,
+            DeferredOpType::Tagged(libfabric_sys::fi_op_tagged {
+                ep: ep.as_typed_fid_mut().as_raw_typed_fid(),
+                msg: *msg.inner(),
+                flags: options.as_raw(),
+            }),
+        )
+    }
+
+    /// Defers a `fi_writemsg` of `msg` over `ep`.
+    ///
+    /// # Safety
+    /// This function is unsafe because the remote memory address that it's writing to cannot be guaranteed to be valid
+    pub unsafe fn write<E: WriteEp + AsTypedFid<EpRawFid>>(
+        ep: &'a E,
+        msg: &'a MsgRma<'_>,
+        options: WriteMsgOptions,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_WRITE,
+            DeferredOpType::Rma(libfabric_sys::fi_op_rma {
+                ep: ep.as_typed_fid_mut().as_raw_typed_fid(),
+                msg: *msg.inner(),
+                flags: options.as_raw(),
+            }),
+        )
+    }
+
+    /// Same as [DeferredOp::write] but for connected endpoints.
+    ///
+    /// # Safety
+    /// This function is unsafe because the remote memory address that it's writing to cannot be guaranteed to be valid
+    pub unsafe fn write_connected<E: ConnectedWriteEp + AsTypedFid<EpRawFid>>(
+        ep: &'a E,
+        msg: &'a MsgRmaConnected<'_>,
+        options: WriteMsgOptions,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_WRITE,
+            DeferredOpType::Rma(libfabric_sys::fi_op_rma {
+                ep: ep.as_typed_fid_mut().as_raw_typed_fid(),
+                msg: *msg.inner(),
+                flags: options.as_raw(),
+            }),
+        )
+    }
+
+    /// Defers a `fi_readmsg` of `msg` over `ep`.
+    ///
+    /// # Safety
+    /// This function is unsafe because the remote memory address that it's reading from cannot be guaranteed to be valid
+    pub unsafe fn read<E: ReadEp + AsTypedFid<EpRawFid>>(
+        ep: &'a E,
+        msg: &'a MsgRmaMut<'_>,
+        options: ReadMsgOptions,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_READ,
+            DeferredOpType::Rma(libfabric_sys::fi_op_rma {
+                ep: ep.as_typed_fid_mut().as_raw_typed_fid(),
+                msg: *msg.inner(),
+                flags: options.as_raw(),
+            }),
+        )
+    }
+
+    /// Defers a `fi_atomicmsg` of `msg` over `ep`.
+    ///
+    /// # Safety
+    /// This function is unsafe because the remote memory address that it's writing to cannot be guaranteed to be valid
+    pub unsafe fn atomic<T: AsFiType, E: AtomicWriteEp + AsTypedFid<EpRawFid>>(
+        ep: &'a E,
+        msg: &'a MsgAtomic<'_, T>,
+        options: AtomicMsgOptions,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_ATOMIC,
+            DeferredOpType::Atomic(libfabric_sys::fi_op_atomic {
+                ep: ep.as_typed_fid_mut().as_raw_typed_fid(),
+                msg: *msg.inner(),
+                flags: options.as_raw(),
+            }),
+        )
+    }
+
+    /// Defers adding `value` to `cntr`.
+    pub fn cntr_add<T: AsRawTypedFid<Output = CntrRawFid>>(
+        cntr: &'a Counter<T>,
+        value: u64,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_CNTR_ADD,
+            DeferredOpType::Cntr(libfabric_sys::fi_op_cntr {
+                cntr: cntr.as_raw_typed_fid(),
+                value,
+            }),
+        )
+    }
+
+    /// Defers setting `cntr` to `value`.
+    pub fn cntr_set<T: AsRawTypedFid<Output = CntrRawFid>>(
+        cntr: &'a Counter<T>,
+        value: u64,
+    ) -> Self {
+        Self::new(
+            libfabric_sys::fi_op_type_FI_OP_CNTR_SET,
+            DeferredOpType::Cntr(libfabric_sys::fi_op_cntr {
+                cntr: cntr.as_raw_typed_fid(),
+                value,
+            }),
+        )
+    }
+
+    fn raw_op(&mut self) -> libfabric_sys::fi_deferred_work__bindgen_ty_1 {
+        match &mut self.op {
+            DeferredOpType::Msg(op) => libfabric_sys::fi_deferred_work__bindgen_ty_1 { msg: op },
+            DeferredOpType::Tagged(op) => {
+                libfabric_sys::fi_deferred_work__bindgen_ty_1 { tagged: op }
+            }
+            DeferredOpType::Rma(op) => libfabric_sys::fi_deferred_work__bindgen_ty_1 { rma: op },
+            DeferredOpType::Atomic(op) => {
+                libfabric_sys::fi_deferred_work__bindgen_ty_1 { atomic: op }
+            }
+            DeferredOpType::Cntr(op) => libfabric_sys::fi_deferred_work__bindgen_ty_1 { cntr: op },
+        }
+    }
+
+    fn is_cntr_op(&self) -> bool {
+        matches!(self.op, DeferredOpType::Cntr(_))
+    }
+
+    // Issues the operation from software, on behalf of a provider that cannot queue it
+    fn issue(&self) -> Result<(), Error> {
+        let err = unsafe {
+            match &self.op {
+                DeferredOpType::Msg(op) => {
+                    libfabric_sys::inlined_fi_sendmsg(op.ep, &op.msg, op.flags)
+                }
+                DeferredOpType::Tagged(op) => {
+                    libfabric_sys::inlined_fi_tsendmsg(op.ep, &op.msg, op.flags)
+                }
+                DeferredOpType::Rma(op) => {
+                    if self.op_type == libfabric_sys::fi_op_type_FI_OP_READ {
+                        libfabric_sys::inlined_fi_readmsg(op.ep, &op.msg, op.flags)
+                    } else {
+                        libfabric_sys::inlined_fi_writemsg(op.ep, &op.msg, op.flags)
+                    }
+                }
+                DeferredOpType::Atomic(op) => {
+                    libfabric_sys::inlined_fi_atomicmsg(op.ep, &op.msg, op.flags)
+                }
+                DeferredOpType::Cntr(op) => {
+                    if self.op_type == libfabric_sys::fi_op_type_FI_OP_CNTR_ADD {
+                        libfabric_sys::inlined_fi_cntr_add(op.cntr, op.value) as isize
+                    } else {
+                        libfabric_sys::inlined_fi_cntr_set(op.cntr, op.value) as isize
+                    }
+                }
+            }
+        };
+
+        check_error(err)
+    }
+}
+
+/// A [DeferredOp] gated on a counter reaching a threshold.
+///
+/// Corresponds to a `struct fi_deferred_work`.
+pub struct DeferredWork<'a> {
+    threshold: u64,
+    triggering_cntr: CntrRawFid,
+    completion_cntr: CntrRawFid,
+    op_cntr: CntrRawFid,
+    op: DeferredOp<'a>,
+}
+
+impl<'a> DeferredWork<'a> {
+    /// Creates a work that issues `op` once `triggering_cntr` reaches `threshold`.
+    pub fn new<T: AsRawTypedFid<Output = CntrRawFid>>(
+        triggering_cntr: &'a Counter<T>,
+        threshold: u64,
+        op: DeferredOp<'a>,
+    ) -> Self {
+        Self {
+            threshold,
+            triggering_cntr: triggering_cntr.as_raw_typed_fid(),
+            completion_cntr: std::ptr::null_mut(),
+            op_cntr: std::ptr::null_mut(),
+            op,
+        }
+    }
+
+    /// Increments `cntr` once the operation has completed, so that it can trigger further works.
+    pub fn completion_cntr<T: AsRawTypedFid<Output = CntrRawFid>>(
+        mut self,
+        cntr: &'a Counter<T>,
+    ) -> Self {
+        self.completion_cntr = cntr.as_raw_typed_fid();
+        self
+    }
+
+    /// Sets the counter that the endpoint increments when the operation completes (i.e., the counter bound
+    /// to the endpoint for this kind of operation).
+    ///
+    /// A [DeferredWorkQueue] that handles works in software relies on it to know when the operation has completed,
+    /// so it is required for works that have a completion counter and do not target a counter. Until the operation
+    /// completes, the counter must only be incremented by the operations of deferred works.
+    pub fn op_cntr<T: AsRawTypedFid<Output = CntrRawFid>>(mut self, cntr: &'a Counter<T>) -> Self {
+        self.op_cntr = cntr.as_raw_typed_fid();
+        self
+    }
+}
+
+struct QueuedWork<'a> {
+    id: usize,
+    c_work: libfabric_sys::fi_deferred_work,
+    work: DeferredWork<'a>,
+    offloaded: bool,
+    // Value the operation counter reaches once an operation issued in software has completed
+    issued: Option<u64>,
+}
+
+impl QueuedWork<'_> {
+    fn triggered(&self) -> bool {
+        unsafe {
+            libfabric_sys::inlined_fi_cntr_read(self.work.triggering_cntr) >= self.work.threshold
+        }
+    }
+
+    // Offloaded works are gone from the provider once triggered
+    fn executed(&self) -> bool {
+        self.offloaded && self.triggered()
+    }
+
+    fn op_cntr_value(&self) -> u64 {
+        // Failed operations also complete
+        unsafe {
+            libfabric_sys::inlined_fi_cntr_read(self.work.op_cntr)
+                + libfabric_sys::inlined_fi_cntr_readerr(self.work.op_cntr)
+        }
+    }
+
+    fn tracks_op(&self) -> bool {
+        !self.work.completion_cntr.is_null() && !self.work.op.is_cntr_op()
+    }
+
+    fn complete(&self) -> Result<(), Error> {
+        if self.work.completion_cntr.is_null() {
+            return Ok(());
+        }
+
+        let err = unsafe { libfabric_sys::inlined_fi_cntr_add(self.work.completion_cntr, 1) };
+        check_error(err as isize)
+    }
+}
+
+/// Identifies a work queued to a [DeferredWorkQueue].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeferredWorkId(usize);
+
+/// A queue of [DeferredWork] for a domain, allowing chains of operations to be offloaded to the NIC
+/// (e.g., "send a notification once 8 writes have landed").
+///
+/// Works are queued to the provider through `fi_control` with `FI_QUEUE_WORK`. If the provider does not support
+/// deferred work, the queue falls back to issuing the works itself from [DeferredWorkQueue::progress], which then has
+/// to be called periodically. In that case the completion counter of a work is incremented once the operation counter
+/// of the work (see [DeferredWork::op_cntr]) shows that its operation has completed.
+///
+/// Works remain queued until they are executed, cancelled or flushed. Dropping the queue flushes them.
+pub struct DeferredWorkQueue<'a> {
+    domain: MyRc<dyn DomainImplT>,
+    works: Vec<Box<QueuedWork<'a>>>,
+    next_id: usize,
+    offload: Option<bool>,
+}
+
+impl<'a> DeferredWorkQueue<'a> {
+    pub(crate) fn new(domain: MyRc<dyn DomainImplT>) -> Self {
+        Self {
+            domain,
+            works: Vec::new(),
+            next_id: 0,
+            offload: None,
+        }
+    }
+
+    fn control(&self, command: i32, arg: *mut std::ffi::c_void) -> Result<(), Error> {
+        let err = unsafe {
+            libfabric_sys::inlined_fi_control(
+                self.domain.as_typed_fid_mut().as_raw_fid(),
+                command,
+                arg,
+            )
+        };
+
+        check_error(err as isize)
+    }
+
+    /// Queues `work`, returning an identifier that can be used to cancel it.
+    ///
+    /// Corresponds to `fi_control` with command `FI_QUEUE_WORK`.
+    pub fn queue(&mut self, work: DeferredWork<'a>) -> Result<DeferredWorkId, Error> {
+        let id = self.next_id;
+        let mut queued = Box::new(QueuedWork {
+            id,
+            c_work: unsafe { std::mem::zeroed() },
+            work,
+            offloaded: false,
+            issued: None,
+        });
+        queued.c_work.threshold = queued.work.threshold;
+        queued.c_work.triggering_cntr = queued.work.triggering_cntr;
+        queued.c_work.completion_cntr = queued.work.completion_cntr;
+        queued.c_work.op_type = queued.work.op.op_type;
+        queued.c_work.op = queued.work.op.raw_op();
+
+        if self.offload != Some(false) {
+            let c_work: *mut libfabric_sys::fi_deferred_work = &mut queued.c_work;
+            match self.control(libfabric_sys::FI_QUEUE_WORK as i32, c_work.cast()) {
+                Ok(()) => {
+                    self.offload = Some(true);
+                    queued.offloaded = true;
+                }
+                Err(err)
+                    if self.offload.is_none()
+                        && matches!(
+                            err.kind,
+                            ErrorKind::NotImplemented | ErrorKind::NotSupported
+                        ) =>
+                {
+                    self.offload = Some(false);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        // The software fallback could not tell when the operation completes
+        if !queued.offloaded && queued.tracks_op() && queued.work.op_cntr.is_null() {
+            return Err(Error::from_err_code(libfabric_sys::FI_EINVAL));
+        }
+
+        self.reap();
+        self.next_id += 1;
+        self.works.push(queued);
+        Ok(DeferredWorkId(id))
+    }
+
+    // Forgets the offloaded works that the provider has executed
+    fn reap(&mut self) {
+        self.works.retain(|w| !w.executed());
+    }
+
+    /// Cancels the work identified by `id`.
+    ///
+    /// Fails with `FI_ENOENT` if the work has already been executed and with `FI_EALREADY` if its operation has been
+    /// issued in software but has not completed yet.
+    ///
+    /// Corresponds to `fi_control` with command `FI_CANCEL_WORK`.
+    pub fn cancel(&mut self, id: DeferredWorkId) -> Result<(), Error> {
+        self.reap();
+        let pos = self
+            .works
+            .iter()
+            .position(|w| w.id == id.0)
+            .ok_or_else(|| Error::from_err_code(libfabric_sys::FI_ENOENT))?;
+
+        if self.works[pos].issued.is_some() {
+            return Err(Error::from_err_code(libfabric_sys::FI_EALREADY));
+        }
+        if self.works[pos].offloaded {
+            let c_work: *mut libfabric_sys::fi_deferred_work = &mut self.works[pos].c_work;
+            self.control(libfabric_sys::FI_CANCEL_WORK as i32, c_work.cast())?;
+        }
+        self.works.remove(pos);
+        Ok(())
+    }
+
+    /// Cancels every work of the queue that has not been executed yet.
+    ///
+    /// Works whose operation has been issued in software are kept until [DeferredWorkQueue::progress] observes
+    /// their completion. If cancelling some works fails, the first error is returned after trying all of them.
+    ///
+    /// Corresponds to `fi_control` with command `FI_CANCEL_WORK` for each of the works of the queue. Unlike
+    /// `FI_FLUSH_WORK`, this leaves the works queued to the domain by others untouched.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.reap();
+        let mut res = Ok(());
+        let mut i = 0;
+        while i < self.works.len() {
+            if self.works[i].issued.is_some() {
+                i += 1;
+                continue;
+            }
+            if self.works[i].offloaded {
+                let c_work: *mut libfabric_sys::fi_deferred_work = &mut self.works[i].c_work;
+                if let Err(err) = self.control(libfabric_sys::FI_CANCEL_WORK as i32, c_work.cast())
+                {
+                    if res.is_ok() {
+                        res = Err(err);
+                    }
+                    // The provider might still reference the work
+                    i += 1;
+                    continue;
+                }
+            }
+            self.works.remove(i);
+        }
+        res
+    }
+
+    /// Issues the works handled in software whose triggering counter has reached its threshold,
+    /// returning the number of works that were issued.
+    ///
+    /// The completion counter of a work is incremented once its operation has completed, as reported by its operation
+    /// counter, which might take several calls. Works are checked in the order they were queued, so a work
+    /// triggered by the completion counter of a preceding work is issued in the same call if that work completed.
+    /// Offloaded works are only forgotten once the provider has executed them.
+    pub fn progress(&mut self) -> Result<usize, Error> {
+        self.reap();
+        let mut issued = 0;
+        let mut i = 0;
+        while i < self.works.len() {
+            let work = &self.works[i];
+            if work.offloaded {
+                i += 1;
+                continue;
+            }
+
+            if let Some(target) = work.issued {
+                if work.op_cntr_value() >= target {
+                    self.works.remove(i).complete()?;
+                } else {
+                    i += 1;
+                }
+                continue;
+            }
+
+            if !work.triggered() {
+                i += 1;
+                continue;
+            }
+
+            // Operations of other works that have not completed yet are counted by the same counter
+            let target = if work.tracks_op() {
+                let value = work.op_cntr_value();
+                let in_flight = self
+                    .works
+                    .iter()
+                    .filter(|w| w.work.op_cntr == work.work.op_cntr)
+                    .filter(|w| matches!(w.issued, Some(t) if t > value))
+                    .count() as u64;
+                Some(value + in_flight + 1)
+            } else {
+                None
+            };
+
+            match work.work.op.issue() {
+                Ok(()) => {}
+                Err(err) if matches!(err.kind, ErrorKind::TryAgain) => break,
+                Err(err) => return Err(err),
+            }
+            issued += 1;
+            match target {
+                Some(target) => {
+                    self.works[i].issued = Some(target);
+                    i += 1;
+                }
+                None => self.works.remove(i).complete()?,
+            }
+        }
+
+        Ok(issued)
+    }
+
+    /// Returns `Some(true)` if works are offloaded to the provider, `Some(false)` if they are handled in software
+    /// and `None` if no work has been queued yet.
+    pub fn is_offloaded(&self) -> Option<bool> {
+        self.offload
+    }
+
+    /// Returns the number of works that have not been executed yet, including those whose operation
+    /// has been issued in software but has not completed.
+    pub fn len(&self) -> usize {
+        self.works.iter().filter(|w| !w.executed()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for DeferredWorkQueue<'_> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<EQ: ?Sized + SyncSend + 'static> DomainBase<EQ> {
+    /// Creates a [DeferredWorkQueue] for the domain.
+    pub fn deferred_work_queue<'a>(&self) -> DeferredWorkQueue<'a> {
+        DeferredWorkQueue::new(self.inner.clone() as MyRc<dyn DomainImplT>)
+    }
+}
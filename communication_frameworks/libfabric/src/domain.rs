@@ -8,7 +8,7 @@ use crate::{
     },
     eq::{EventQueue, EventQueueBase, ReadEq},
     fabric::FabricImpl,
-    fid::{self, AsRawFid, AsRawTypedFid, DomainRawFid, OwnedDomainFid},
+    fid::{self, AsRawFid, AsRawTypedFid, DomainRawFid, EpRawFid, OwnedDomainFid, StxRawFid},
     info::InfoEntry,
     utils::check_error,
     AsFiType, Context, MyOnceCell, MyRc, SyncSend,
@@ -139,13 +139,29 @@ impl DomainImplBase<dyn ReadEq> {
 }
 
 impl<EQ: ?Sized> DomainImplBase<EQ> {
-    // pub(crate) fn srx_context<T0>(&self, rx_attr: crate::RxAttr) -> Result<crate::ep::Endpoint, crate::error::Error> { //[TODO]
-    //     crate::ep::Endpoint::from_attr(self, rx_attr)
-    // }
+    pub(crate) fn srx_context(
+        &self,
+        rx_attr: crate::xcontext::RxAttr,
+        context: *mut std::ffi::c_void,
+    ) -> Result<EpRawFid, crate::error::Error> {
+        let mut c_ep: EpRawFid = std::ptr::null_mut();
+        let err = unsafe {
+            libfabric_sys::inlined_fi_srx_context(
+                self.as_typed_fid_mut().as_raw_typed_fid(),
+                &mut rx_attr.get(),
+                &mut c_ep,
+                context,
+            )
+        };
 
-    // pub(crate) fn srx_context_with_context<T0>(&self, rx_attr: crate::RxAttr, context: &mut T0) -> Result<crate::ep::Endpoint, crate::error::Error> { //[TODO]
-    //     crate::ep::Endpoint::from_attr_with_context(self, rx_attr, context)
-    // }
+        if err != 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            Ok(c_ep)
+        }
+    }
 
     pub(crate) fn query_atomic<T: AsFiType>(
         &self,
@@ -212,13 +228,29 @@ impl<EQ: ?Sized> DomainImplBase<EQ> {
         check_error(err.try_into().unwrap())
     }
 
-    // pub fn stx_context<T0>(&self, attr: crate::TxAttr) -> Result<crate::Stx, crate::error::Error> { //[TODO]
-    //     crate::Stx::new(self, attr, std::ptr::null_mut())
-    // }
+    pub(crate) fn stx_context(
+        &self,
+        tx_attr: crate::xcontext::TxAttr,
+        context: *mut std::ffi::c_void,
+    ) -> Result<StxRawFid, crate::error::Error> {
+        let mut c_stx: StxRawFid = std::ptr::null_mut();
+        let err = unsafe {
+            libfabric_sys::inlined_fi_stx_context(
+                self.as_typed_fid_mut().as_raw_typed_fid(),
+                &mut tx_attr.get(),
+                &mut c_stx,
+                context,
+            )
+        };
 
-    // pub fn stx_context_with_context<T0>(&self, attr: crate::TxAttr , context: &mut T0) -> Result<crate::Stx, crate::error::Error> { //[TODO]
-    //     crate::Stx::new(self, attr, context)
-    // }
+        if err != 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            Ok(c_stx)
+        }
+    }
 
     pub(crate) fn query_collective<T: AsFiType>(
         &self,
@@ -4,6 +4,7 @@ use libfabric_sys::{FI_RECV, FI_TRANSMIT};
 
 macro_rules! gen_enum {
     ($(#[$attr:meta])* $name: ident, $type_: ty, $(($var: ident, $val: expr)),*) => {
+        $(#[$attr])*
         #[derive(Clone, Copy, Debug)]
         pub enum $name {
             $($var,)*
@@ -118,6 +119,10 @@ gen_enum!(
 
 gen_enum!(
     /// An enumeration of collective atomic operations. Can be the operation of a reduce for example
+    ///
+    /// Reductions defined by the application have no libfabric counterpart and are run by the `*_with` operations
+    /// of [crate::comm::software_collective::SoftwareCollective] (see [crate::comm::software_collective::ReduceFn]).
+    #[non_exhaustive]
     ReduceOp,
     u32,
     (Min, libfabric_sys::fi_op_FI_MIN),
@@ -1295,7 +1300,7 @@ impl JoinOptions {
     }
 
     gen_set_get_flag!(send, is_send, libfabric_sys::FI_SEND as u64);
-    gen_set_get_flag!(receive, is_receive, libfabric_sys::FI_SEND as u64);
+    gen_set_get_flag!(receive, is_receive, libfabric_sys::FI_RECV as u64);
 }
 
 impl Default for JoinOptions {
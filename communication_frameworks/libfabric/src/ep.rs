@@ -24,6 +24,10 @@ use crate::{
     info::{InfoEntry, Version},
     trigger::TriggerXpu,
     utils::check_error,
+    xcontext::{
+        SharedRxContext, SharedRxContextImplBase, SharedRxContextImplT, SharedTxContext,
+        SharedTxContextImpl,
+    },
     Context, MyOnceCell, MyRc, MyRefCell, SyncSend,
 };
 
@@ -32,9 +36,12 @@ use crate::fid::EpCompletionOwnedTypedFid;
 
 #[repr(C)]
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A unmapped network address.
 ///
 /// This struct encapsulates a raw byte representation of a network address.
+/// With the `serde` feature, it can be sent to peers, which insert it in an
+/// [crate::av::AddressVector] to get the corresponding [crate::MappedAddress].
 pub struct Address {
     pub(crate) address: Vec<u8>,
 }
@@ -73,11 +80,29 @@ pub(crate) enum EpCq<CQ: ?Sized> {
     Shared(MyRc<CQ>),
 }
 
+impl<CQ: ?Sized> Clone for EpCq<CQ> {
+    fn clone(&self) -> Self {
+        match self {
+            EpCq::Separate(tx_cq, rx_cq) => EpCq::Separate(tx_cq.clone(), rx_cq.clone()),
+            EpCq::Shared(cq) => EpCq::Shared(cq.clone()),
+        }
+    }
+}
+
 pub(crate) enum EpType {
     Connected(bool),
     Connectionless
 }
 
+impl EpType {
+    fn duplicate(&self) -> Self {
+        match self {
+            EpType::Connected(has_conn_req) => EpType::Connected(*has_conn_req),
+            EpType::Connectionless => EpType::Connectionless,
+        }
+    }
+}
+
 pub struct EndpointImplBase<T, EQ: ?Sized, CQ: ?Sized> {
     #[cfg(not(feature = "threading-completion"))]
     pub(crate) c_ep: OwnedEpFid,
@@ -87,7 +112,10 @@ pub struct EndpointImplBase<T, EQ: ?Sized, CQ: ?Sized> {
     pub(crate) eq: MyOnceCell<MyRc<EQ>>,
     _bound_cntrs: MyRefCell<Vec<MyRc<dyn ReadCntr>>>,
     _bound_av: MyOnceCell<MyRc<dyn AddressVectorImplT>>,
+    _bound_srx: MyOnceCell<MyRc<dyn SharedRxContextImplT>>,
+    _bound_stx: MyOnceCell<MyRc<SharedTxContextImpl>>,
     _domain_rc: MyRc<dyn DomainImplT>,
+    _alias_of: Option<MyRc<EndpointImplBase<T, EQ, CQ>>>,
     phantom: PhantomData<fn() -> T>, // fn() -> T because we only need to track the Endpoint capabilities requested but avoid requiring caps to implement Sync+Send
     pub(crate) eptype: EpType,
 }
@@ -96,9 +124,12 @@ impl<T, EQ: ?Sized, CQ: ?Sized> Drop for EndpointImplBase<T, EQ, CQ> {
     fn drop(&mut self) {
         match self.eptype {
             EpType::Connected(_) => {
-                let ep = self.c_ep.as_typed_fid_mut().as_raw_typed_fid();
-                let err = unsafe{libfabric_sys::inlined_fi_shutdown(ep, 0)};
-                check_error(err as isize).unwrap();
+                // An alias shares the connection of the endpoint it was created from
+                if self._alias_of.is_none() {
+                    let ep = self.c_ep.as_typed_fid_mut().as_raw_typed_fid();
+                    let err = unsafe{libfabric_sys::inlined_fi_shutdown(ep, 0)};
+                    check_error(err as isize).unwrap();
+                }
             },
             EpType::Connectionless => {},
         }
@@ -459,6 +490,7 @@ pub(crate) struct ScalableEndpointImpl {
     #[cfg(feature = "threading-completion")]
     pub(crate) c_sep: EpCompletionOwnedTypedFid<EpRawFid>,
     _domain_rc: MyRc<dyn DomainImplT>,
+    _alias_of: Option<MyRc<ScalableEndpointImpl>>,
 }
 
 /// A scalable endpoint that can manage multiple connections.
@@ -466,7 +498,7 @@ pub(crate) struct ScalableEndpointImpl {
 /// This endpoint type is suitable for applications that require high scalability and flexibility in managing connections.
 /// Corresponds to `fi_scalable_ep` in libfabric.
 pub struct ScalableEndpoint<E> {
-    inner: MyRc<ScalableEndpointImpl>,
+    pub(crate) inner: MyRc<ScalableEndpointImpl>,
     phantom: PhantomData<fn() -> E>,
 }
 
@@ -524,6 +556,7 @@ impl ScalableEndpointImpl {
                 // #[cfg(feature="threading-domain")]
                 // c_sep: OwnedEpFid::from(c_sep, domain.c_domain.domain.clone()),
                 _domain_rc: domain.clone(),
+                _alias_of: None,
             })
         }
     }
@@ -556,24 +589,33 @@ impl ScalableEndpointImpl {
     //     self.bind(&av, 0)
     // }
 
-    // pub(crate) fn alias(&self, flags: u64) -> Result<ScalableEndpointImpl, crate::error::Error> {
-    //     let mut c_sep: EpRawFid = std::ptr::null_mut();
-    //     let c_sep_ptr: *mut EpRawFid = &mut c_sep;
-    //     let err = unsafe {
-    //         libfabric_sys::inlined_fi_ep_alias(self.as_typed_fid().as_raw_typed_fid(), c_sep_ptr, flags)
-    //     };
-
-    //     if err != 0 {
-    //         Err(crate::error::Error::from_err_code(
-    //             (-err).try_into().unwrap(),
-    //         ))
-    //     } else {
-    //         Ok(ScalableEndpointImpl {
-    //             c_sep: OwnedEpFid::from(c_sep),
-    //             _domain_rc: self._domain_rc.clone(),
-    //         })
-    //     }
-    // }
+    fn alias(parent: &MyRc<Self>, flags: u64) -> Result<ScalableEndpointImpl, crate::error::Error> {
+        let mut c_sep: EpRawFid = std::ptr::null_mut();
+        let err = unsafe {
+            libfabric_sys::inlined_fi_ep_alias(
+                parent.as_typed_fid_mut().as_raw_typed_fid(),
+                &mut c_sep,
+                flags,
+            )
+        };
+
+        if err != 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            Ok(ScalableEndpointImpl {
+                #[cfg(not(any(feature = "threading-domain", feature = "threading-completion")))]
+                c_sep: OwnedEpFid::from(c_sep),
+                #[cfg(feature = "threading-domain")]
+                c_sep: OwnedEpFid::from(c_sep, parent.c_sep.domain.clone()),
+                #[cfg(feature = "threading-completion")]
+                c_sep: EpCompletionOwnedTypedFid::from(c_sep),
+                _domain_rc: parent._domain_rc.clone(),
+                _alias_of: Some(parent.clone()),
+            })
+        }
+    }
 }
 
 impl<E> ScalableEndpoint<E> {
@@ -584,12 +626,28 @@ impl<E> ScalableEndpoint<E> {
     pub fn enable(&self) -> Result<(), crate::error::Error> {
         self.inner.enable()
     }
-    // pub fn alias(&self, flags: u64) -> Result<ScalableEndpoint<E>, crate::error::Error> {
-    //     Ok(Self {
-    //         inner: MyRc::new(self.inner.alias(flags)?),
-    //         phantom: PhantomData,
-    //     })
-    // }
+
+    /// Creates an alias of this endpoint that shares the underlying endpoint but uses
+    /// `options` as its default transmit flags.
+    ///
+    /// Corresponds to `fi_ep_alias`
+    pub fn alias(&self, options: TransferOptions) -> Result<ScalableEndpoint<E>, crate::error::Error> {
+        Ok(Self {
+            inner: MyRc::new(ScalableEndpointImpl::alias(&self.inner, options.transmit().as_raw() as u64)?),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Creates an alias of this endpoint that shares the underlying endpoint but uses
+    /// `options` as its default receive flags.
+    ///
+    /// Corresponds to `fi_ep_alias` with `FI_RECV`
+    pub fn receive_alias(&self, options: TransferOptions) -> Result<ScalableEndpoint<E>, crate::error::Error> {
+        Ok(Self {
+            inner: MyRc::new(ScalableEndpointImpl::alias(&self.inner, options.recv().as_raw() as u64)?),
+            phantom: PhantomData,
+        })
+    }
 }
 
 // impl AsFid for ScalableEndpointImpl {
@@ -1058,9 +1116,12 @@ impl<T, EQ: ?Sized + ReadEq, CQ: ?Sized + ReadCq> EndpointImplBase<T, EQ, CQ> {
                 c_ep: EpCompletionOwnedTypedFid::from(c_ep),
                 _bound_av: MyOnceCell::new(),
                 _bound_cntrs: MyRefCell::new(Vec::new()),
+                _bound_srx: MyOnceCell::new(),
+                _bound_stx: MyOnceCell::new(),
                 cq: MyOnceCell::new(),
                 eq: MyOnceCell::new(),
                 _domain_rc: domain.clone(),
+                _alias_of: None,
                 phantom: PhantomData,
                 eptype,
             })
@@ -1171,6 +1232,85 @@ impl<EP, EQ: ?Sized + ReadEq + 'static, CQ: ?Sized + ReadCq> EndpointImplBase<EP
             Ok(())
         }
     }
+
+    pub(crate) fn bind_srx_<I: 'static>(
+        &self,
+        res: &MyRc<SharedRxContextImplBase<I, CQ>>,
+    ) -> Result<(), crate::error::Error>
+    where
+        CQ: 'static,
+    {
+        // Completions for receives posted to the shared context are reported on the
+        // receive queue of the endpoint, so it must be known, and common to all the endpoints
+        // sharing the context, before binding.
+        let rx_cq = match self.cq.get() {
+            Some(EpCq::Shared(rx_cq)) | Some(EpCq::Separate(_, rx_cq)) => rx_cq,
+            None => {
+                return Err(crate::error::Error::from_err_code(
+                    libfabric_sys::FI_ENOCQ,
+                ))
+            }
+        };
+        if let Some(srx_cq) = res.cq.get() {
+            if !MyRc::ptr_eq(srx_cq, rx_cq) {
+                return Err(crate::error::Error::from_err_code(
+                    libfabric_sys::FI_EINVAL,
+                ));
+            }
+        }
+        if self._bound_srx.get().is_some() {
+            return Err(crate::error::Error::from_err_code(
+                libfabric_sys::FI_EALREADY,
+            ));
+        }
+
+        let err = unsafe {
+            libfabric_sys::inlined_fi_ep_bind(
+                self.as_typed_fid_mut().as_raw_typed_fid(),
+                res.as_typed_fid().as_raw_fid(),
+                0,
+            )
+        };
+
+        if err != 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            let _ = self._bound_srx.set(res.clone());
+            let _ = res.cq.set(rx_cq.clone());
+            Ok(())
+        }
+    }
+
+    pub(crate) fn using_context2(&self) -> bool {
+        self._domain_rc.fabric_impl().using_context2
+    }
+
+    pub(crate) fn bind_stx_(&self, res: &MyRc<SharedTxContextImpl>) -> Result<(), crate::error::Error> {
+        if self._bound_stx.get().is_some() {
+            return Err(crate::error::Error::from_err_code(
+                libfabric_sys::FI_EALREADY,
+            ));
+        }
+
+        let err = unsafe {
+            libfabric_sys::inlined_fi_ep_bind(
+                self.as_typed_fid_mut().as_raw_typed_fid(),
+                res.as_typed_fid().as_raw_fid(),
+                0,
+            )
+        };
+
+        if err != 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            let _ = self._bound_stx.set(res.clone());
+            Ok(())
+        }
+    }
 }
 
 impl<EP, EQ: ?Sized + ReadEq + 'static> EndpointImplBase<EP, EQ, dyn ReadCq> {
@@ -1293,32 +1433,66 @@ impl<EP, EQ: ?Sized + 'static + ReadEq, CQ: ?Sized + ReadCq> EndpointImplBase<EP
         self.bind_av_(&av.inner, 0)
     }
 
-    // #[allow(dead_code)]
-    // pub(crate) fn alias(&self, flags: u64) -> Result<Self, crate::error::Error> {
-    //     let mut c_ep: EpRawFid = std::ptr::null_mut();
-    //     let err = unsafe {
-    //         libfabric_sys::inlined_fi_ep_alias(self.as_typed_fid().as_raw_typed_fid(), &mut c_ep, flags)
-    //     };
-
-    //     if err != 0 {
-    //         Err(crate::error::Error::from_err_code(
-    //             (-err).try_into().unwrap(),
-    //         ))
-    //     } else {
-    //         Ok(Self {
-    //             #[cfg(not(feature="threading-domain"))]
-    //             c_ep: OwnedEpFid::from(c_ep),
-    //             #[cfg(feature="threading-domain")]
-    //             c_ep: OwnedEpFid::from(c_ep,self._domain_rc.c_domain.typed_fid.typed_fid.clone()),
-    //             _bound_av: MyOnceCell::new(),
-    //             _bound_cntrs: MyRefCell::new(Vec::new()),
-    //             cq: MyOnceCell::new(),
-    //             eq: MyOnceCell::new(),
-    //             _domain_rc: self._domain_rc.clone(),
-    //             phantom: PhantomData,
-    //         })
-    //     }
-    // }
+    pub(crate) fn alias(parent: &MyRc<Self>, flags: u64) -> Result<Self, crate::error::Error> {
+        let mut c_ep: EpRawFid = std::ptr::null_mut();
+        let err = unsafe {
+            libfabric_sys::inlined_fi_ep_alias(
+                parent.as_typed_fid_mut().as_raw_typed_fid(),
+                &mut c_ep,
+                flags,
+            )
+        };
+
+        if err != 0 {
+            Err(crate::error::Error::from_err_code(
+                (-err).try_into().unwrap(),
+            ))
+        } else {
+            #[cfg(feature = "threading-completion")]
+            let alias_c_ep = {
+                let alias_c_ep = EpCompletionOwnedTypedFid::from(c_ep);
+                if let Some(cq) = parent.c_ep.bound_cq0.get() {
+                    let _ = alias_c_ep.bound_cq0.set(cq.clone());
+                }
+                if let Some(cq) = parent.c_ep.bound_cq1.get() {
+                    let _ = alias_c_ep.bound_cq1.set(cq.clone());
+                }
+                if let Some(cntr) = parent.c_ep.bound_cntr.get() {
+                    let _ = alias_c_ep.bound_cntr.set(cntr.clone());
+                }
+                alias_c_ep
+            };
+
+            let cq = MyOnceCell::new();
+            if let Some(ep_cq) = parent.cq.get() {
+                let _ = cq.set(ep_cq.clone());
+            }
+
+            let eq = MyOnceCell::new();
+            if let Some(ep_eq) = parent.eq.get() {
+                let _ = eq.set(ep_eq.clone());
+            }
+
+            Ok(Self {
+                #[cfg(not(any(feature = "threading-domain", feature = "threading-completion")))]
+                c_ep: OwnedEpFid::from(c_ep),
+                #[cfg(feature = "threading-domain")]
+                c_ep: OwnedEpFid::from(c_ep, parent.c_ep.domain.clone()),
+                #[cfg(feature = "threading-completion")]
+                c_ep: alias_c_ep,
+                _bound_av: MyOnceCell::new(),
+                _bound_cntrs: MyRefCell::new(Vec::new()),
+                _bound_srx: MyOnceCell::new(),
+                _bound_stx: MyOnceCell::new(),
+                cq,
+                eq,
+                _domain_rc: parent._domain_rc.clone(),
+                _alias_of: Some(parent.clone()),
+                phantom: PhantomData,
+                eptype: parent.eptype.duplicate(),
+            })
+        }
+    }
 }
 
 impl<EP> EndpointBase<EndpointImplBase<EP, dyn ReadEq, dyn ReadCq>, UninitConnectionless> {
@@ -1381,13 +1555,30 @@ impl<EP> EndpointBase<EndpointImplBase<EP, dyn ReadEq, dyn ReadCq>, UninitConnec
         self.inner.bind_av(av)
     }
 
-    // pub fn alias(&self, flags: u64) -> Result<Self, crate::error::Error> {
-    //     Ok(
-    //         Self {
-    //             inner: MyRc::new (self.inner.alias(flags)?),
-    //         }
-    //     )
-    // }
+    /// Binds a [SharedRxContext] to the endpoint.
+    ///
+    /// Receive operations must then be posted to the shared context instead of the endpoint.
+    ///
+    /// The endpoint must already be bound to a completion queue, and its receive queue must be the
+    /// same as the one of the other endpoints bound to `srx`. Otherwise an error of kind
+    /// [NoCompletionQueue](crate::error::ErrorKind::NoCompletionQueue) or
+    /// [InvalidArgument](crate::error::ErrorKind::InvalidArgument) is returned. Binding a second shared
+    /// receive context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_srx<I: 'static>(&self, srx: &SharedRxContext<I>) -> Result<(), crate::error::Error> {
+        self.inner.bind_srx_(&srx.inner)
+    }
+
+    /// Binds a [SharedTxContext] to the endpoint.
+    ///
+    /// Transmit operations of the endpoint will then be queued to the shared context. Binding a second
+    /// shared transmit context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_stx(&self, stx: &SharedTxContext) -> Result<(), crate::error::Error> {
+        self.inner.bind_stx_(&stx.inner)
+    }
 }
 
 impl<EP> EndpointBase<EndpointImplBase<EP, dyn ReadEq, dyn ReadCq>, UninitUnconnected> {
@@ -1450,13 +1641,30 @@ impl<EP> EndpointBase<EndpointImplBase<EP, dyn ReadEq, dyn ReadCq>, UninitUnconn
         self.inner.bind_av(av)
     }
 
-    // pub fn alias(&self, flags: u64) -> Result<Self, crate::error::Error> {
-    //     Ok(
-    //         Self {
-    //             inner: MyRc::new (self.inner.alias(flags)?),
-    //         }
-    //     )
-    // }
+    /// Binds a [SharedRxContext] to the endpoint.
+    ///
+    /// Receive operations must then be posted to the shared context instead of the endpoint.
+    ///
+    /// The endpoint must already be bound to a completion queue, and its receive queue must be the
+    /// same as the one of the other endpoints bound to `srx`. Otherwise an error of kind
+    /// [NoCompletionQueue](crate::error::ErrorKind::NoCompletionQueue) or
+    /// [InvalidArgument](crate::error::ErrorKind::InvalidArgument) is returned. Binding a second shared
+    /// receive context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_srx<I: 'static>(&self, srx: &SharedRxContext<I>) -> Result<(), crate::error::Error> {
+        self.inner.bind_srx_(&srx.inner)
+    }
+
+    /// Binds a [SharedTxContext] to the endpoint.
+    ///
+    /// Transmit operations of the endpoint will then be queued to the shared context. Binding a second
+    /// shared transmit context fails with [AlreadyInProgress](crate::error::ErrorKind::AlreadyInProgress).
+    ///
+    /// Corresponds to `fi_ep_bind` in libfabric.
+    pub fn bind_stx(&self, stx: &SharedTxContext) -> Result<(), crate::error::Error> {
+        self.inner.bind_stx_(&stx.inner)
+    }
 }
 
 // impl<E: AsFid, STATE: EpState> AsFid for EndpointBase<E, STATE> {
@@ -1471,6 +1679,37 @@ impl<EP> EndpointBase<EndpointImplBase<EP, dyn ReadEq, dyn ReadCq>, UninitUnconn
 //     }
 // }
 
+impl<EP, EQ: ?Sized + ReadEq + 'static, CQ: ?Sized + ReadCq, STATE: EpState> EndpointBase<EndpointImplBase<EP, EQ, CQ>, STATE> {
+    /// Creates an alias of this endpoint that shares the underlying endpoint, its bound resources
+    /// and its connection, but uses `options` as its default transmit flags.
+    ///
+    /// This allows, for example, one thread to issue `FI_INJECT` operations through an alias while
+    /// another thread uses the original endpoint with completions.
+    /// The original endpoint is kept alive for as long as any of its aliases.
+    ///
+    /// Corresponds to `fi_ep_alias`
+    pub fn alias(&self, options: TransferOptions) -> Result<Self, crate::error::Error> {
+        Ok(Self {
+            inner: MyRc::new(EndpointImplBase::alias(&self.inner, options.transmit().as_raw() as u64)?),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Creates an alias of this endpoint that shares the underlying endpoint, its bound resources
+    /// and its connection, but uses `options` as its default receive flags.
+    ///
+    /// This allows, for example, posting `FI_MULTI_RECV` buffers through an alias while the original
+    /// endpoint keeps its own receive flags. See [EndpointBase::alias] for aliases used for transmitting.
+    ///
+    /// Corresponds to `fi_ep_alias` with `FI_RECV`
+    pub fn receive_alias(&self, options: TransferOptions) -> Result<Self, crate::error::Error> {
+        Ok(Self {
+            inner: MyRc::new(EndpointImplBase::alias(&self.inner, options.recv().as_raw() as u64)?),
+            phantom: PhantomData,
+        })
+    }
+}
+
 impl<E: AsTypedFid<EpRawFid>, STATE: EpState> AsTypedFid<EpRawFid> for EndpointBase<E, STATE> {
     fn as_typed_fid(&self) -> BorrowedTypedFid<'_, EpRawFid> {
         self.inner.as_typed_fid()
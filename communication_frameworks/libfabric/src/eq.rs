@@ -9,7 +9,8 @@ use std::{
 use crate::{
     cq::WaitObjectRetrieve,
     fid::{AsRawFid, AsRawTypedFid, EqRawFid, OwnedEqFid},
-    Context, MyRc, MyRefCell, SyncSend,
+    progress::{progress_result, Progressable},
+    Context, MyRc, MyRefCell, SyncSend, Waitable,
 };
 use crate::{
     enums::WaitObjType,
@@ -755,6 +756,37 @@ impl<T: AsRawTypedFid<Output = EqRawFid>> AsRawTypedFid for EventQueueBase<T> {
     }
 }
 
+/// Drives the progress of `eq` by peeking at its next entry, which is left in the queue.
+pub(crate) fn peek_progress(eq: &impl ReadEq) -> Result<usize, crate::error::Error> {
+    let mut buf = [0u8; std::mem::size_of::<libfabric_sys::fi_eq_err_entry>()];
+    let mut event = 0;
+    progress_result(eq.peek_in(&mut buf, &mut event))
+}
+
+impl<T: ReadEq + SyncEq + 'static> Progressable for EventQueue<T> {
+    fn progress_once(&self) -> Result<usize, crate::error::Error> {
+        peek_progress(self.inner.as_ref())
+    }
+
+    fn progressable_clone(&self) -> Box<dyn Progressable> {
+        Box::new(Self {
+            inner: self.inner.clone(),
+        })
+    }
+}
+
+impl<T: ReadEq + 'static> Waitable for EventQueue<T> {
+    fn waitable_fid(&self) -> RawFid {
+        self.inner.as_typed_fid().as_raw_fid()
+    }
+
+    fn waitable_clone(&self) -> Box<dyn Waitable> {
+        Box::new(Self {
+            inner: self.inner.clone(),
+        })
+    }
+}
+
 impl<const WRITE: bool, const WAIT: bool, const RETRIEVE: bool, const FD: bool> AsTypedFid<EqRawFid>
     for EventQueueImpl<WRITE, WAIT, RETRIEVE, FD>
 {
@@ -129,6 +129,10 @@ impl Error {
         Self { c_err, kind }
     }
 
+    pub(crate) fn from_io_err(err: std::io::Error) -> Self {
+        Self::from_err_code(err.raw_os_error().map_or(libfabric_sys::FI_EIO, |code| code as u32))
+    }
+
     pub(crate) fn caps_error() -> Self {
         Self {
             c_err: 0,
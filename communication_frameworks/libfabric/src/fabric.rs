@@ -8,7 +8,7 @@ use crate::{
     fid::{AsRawFid, AsRawTypedFid, FabricRawFid, OwnedFabricFid, RawFid},
     info::{InfoEntry, Version},
     utils::check_error,
-    Context, MyRc,
+    Context, MyRc, Waitable,
 };
 
 pub(crate) struct FabricImpl {
@@ -49,13 +49,7 @@ impl FabricImpl {
         }
     }
 
-    pub(crate) fn trywait_slice<FID: AsRawFid>(
-        &self,
-        fids: &[&impl AsTypedFid<FID>],
-    ) -> Result<(), crate::error::Error> {
-        // [TODO] Move this into the WaitSet struct
-        let mut raw_fids: Vec<*mut libfabric_sys::fid> =
-            fids.iter().map(|x| x.as_typed_fid().as_raw_fid()).collect();
+    pub(crate) fn trywait_raw(&self, raw_fids: &mut [RawFid]) -> Result<(), crate::error::Error> {
         let err = unsafe {
             libfabric_sys::inlined_fi_trywait(
                 self.as_typed_fid_mut().as_raw_typed_fid(),
@@ -67,21 +61,19 @@ impl FabricImpl {
         check_error(err.try_into().unwrap())
     }
 
+    pub(crate) fn trywait_waitables(
+        &self,
+        objs: &[&dyn Waitable],
+    ) -> Result<(), crate::error::Error> {
+        let mut raw_fids: Vec<RawFid> = objs.iter().map(|x| x.waitable_fid()).collect();
+        self.trywait_raw(&mut raw_fids)
+    }
+
     pub(crate) fn trywait<FID: AsRawFid>(
         &self,
         fid: &impl AsTypedFid<FID>,
     ) -> Result<(), crate::error::Error> {
-        // [TODO] Move this into the WaitSet struct
-        let mut raw_fid = fid.as_typed_fid().as_raw_fid();
-        let err = unsafe {
-            libfabric_sys::inlined_fi_trywait(
-                self.as_typed_fid_mut().as_raw_typed_fid(),
-                &mut raw_fid,
-                1,
-            )
-        };
-
-        check_error(err.try_into().unwrap())
+        self.trywait_raw(&mut [fid.as_typed_fid().as_raw_fid()])
     }
 }
 
@@ -101,26 +93,23 @@ impl Fabric {
         })
     }
 
-    /// Tries to wait for a slice of event queue entries.
+    /// Checks whether it is safe to block on the wait objects of a heterogeneous collection of
+    /// completion queues, event queues and counters.
     ///
-    /// Corresponds to `libfabric_sys::fi_trywait`
-    pub fn trywait_slice<FID: AsRawFid>(
-        &self,
-        fids: &[&impl AsTypedFid<FID>],
-    ) -> Result<(), crate::error::Error> {
-        // [TODO] Move this into the WaitSet struct
-        self.inner.trywait_slice(fids)
+    /// Returns `Ok(())` if none of the objects has pending events, in which case the caller may block on
+    /// their native wait objects (e.g., through `poll`/`epoll` on their file descriptors). Otherwise
+    /// an error of kind [crate::error::ErrorKind::TryAgain] is returned and the objects should be read first.
+    ///
+    /// Corresponds to `fi_trywait`
+    pub fn trywait_slice(&self, objs: &[&dyn Waitable]) -> Result<(), crate::error::Error> {
+        self.inner.trywait_waitables(objs)
     }
 
-    /// Tries to wait for a single event queue entry.
+    /// Checks whether it is safe to block on the wait object of a single completion queue, event queue or counter.
     ///
-    /// Corresponds to `libfabric_sys::fi_trywait`
-    pub fn trywait<FID: AsRawFid>(
-        &self,
-        fid: &impl AsTypedFid<FID>,
-    ) -> Result<(), crate::error::Error> {
-        // [TODO] Move this into the WaitSet struct
-        self.inner.trywait(fid)
+    /// Corresponds to `fi_trywait`
+    pub fn trywait(&self, obj: &impl Waitable) -> Result<(), crate::error::Error> {
+        self.inner.trywait_waitables(&[obj as &dyn Waitable])
     }
 }
 
@@ -749,6 +749,29 @@ pub type OwnedEpFid = OwnedTypedFid<EpRawFid>;
 #[cfg(feature = "threading-domain")]
 pub type OwnedEpFid = DomainOwnedTypedFid<EpRawFid>;
 
+pub(crate) type StxRawFid = *mut libfabric_sys::fid_stx;
+
+impl AsRawTypedFid for StxRawFid {
+    type Output = StxRawFid;
+
+    #[inline]
+    fn as_raw_typed_fid(&self) -> Self::Output {
+        *self
+    }
+}
+
+impl AsRawFid for StxRawFid {
+    #[inline]
+    fn as_raw_fid(&self) -> RawFid {
+        unsafe { &mut (**self).fid }
+    }
+}
+
+#[cfg(not(feature = "threading-domain"))]
+pub(crate) type OwnedStxFid = OwnedTypedFid<StxRawFid>;
+#[cfg(feature = "threading-domain")]
+pub(crate) type OwnedStxFid = DomainOwnedTypedFid<StxRawFid>;
+
 pub(crate) type PepRawFid = *mut libfabric_sys::fid_pep;
 
 impl AsRawTypedFid for PepRawFid {
@@ -1130,12 +1130,36 @@ impl<T> EndpointAttrIn<T> {
         self
     }
 
+    /// Requests that the transmit context of the endpoint is shared, i.e., that the endpoint
+    /// will be bound to a [SharedTxContext](crate::xcontext::SharedTxContext).
+    ///
+    /// Corresponds to setting `fi_ep_attr::tx_ctx_cnt` to `FI_SHARED_CONTEXT`.
+    pub fn shared_tx_ctx(mut self) -> Self {
+        self.hints
+            .info_builder
+            .hints_info
+            .set_ep_tx_ctx_cnt(usize::MAX); // FI_SHARED_CONTEXT is defined as SIZE_MAX
+        self
+    }
+
     /// Sets the receive context count for the endpoint.
     pub fn rx_ctx_cnt(mut self, size: usize) -> Self {
         self.hints.info_builder.hints_info.set_ep_rx_ctx_cnt(size);
         self
     }
 
+    /// Requests that the receive context of the endpoint is shared, i.e., that the endpoint
+    /// will be bound to a [SharedRxContext](crate::xcontext::SharedRxContext).
+    ///
+    /// Corresponds to setting `fi_ep_attr::rx_ctx_cnt` to `FI_SHARED_CONTEXT`.
+    pub fn shared_rx_ctx(mut self) -> Self {
+        self.hints
+            .info_builder
+            .hints_info
+            .set_ep_rx_ctx_cnt(usize::MAX); // FI_SHARED_CONTEXT is defined as SIZE_MAX
+        self
+    }
+
     /// Sets the authentication key for the endpoint.
     pub fn auth_key(mut self, key: &[u8]) -> Self {
         self.hints.info_builder.hints_info.set_ep_auth_key(key);
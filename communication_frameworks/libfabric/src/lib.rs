@@ -56,6 +56,7 @@ use std::sync::OnceLock;
 
 use cq::SingleCompletion;
 use domain::DomainBase;
+use domain::DomainImplT;
 use enums::AddressVectorType;
 use eq::Event;
 use info::InfoEntry;
@@ -95,6 +96,7 @@ pub mod comm;
 pub mod conn_ep;
 pub mod cq;
 pub mod cqoptions;
+pub mod deferred;
 pub mod domain;
 pub mod enums;
 pub mod ep;
@@ -110,8 +112,14 @@ pub mod mcast;
 pub mod mr;
 pub mod msg;
 pub mod nic;
+pub mod peer_table;
+#[cfg(feature = "pmi")]
+pub mod pmi_exchange;
 pub mod profile;
+pub mod progress;
 pub mod sync;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod trigger;
 mod utils;
 pub mod xcontext;
@@ -235,10 +243,139 @@ impl<T: Copy> From<RemoteMemoryAddress<T>> for u32 {
     }
 }
 
+const MEM_ADDRESS_INFO_MAGIC: [u8; 4] = *b"OFMA";
+const MEM_ADDRESS_INFO_VERSION: u8 = 1;
+const MEM_ADDRESS_INFO_RAW_KEY: u8 = 0x1;
+// magic, version, flags, reserved, mr_mode, key size
+const MEM_ADDRESS_INFO_HEADER_LEN: usize = 4 + 1 + 1 + 2 + 4 + 4;
+// key base address, address, length
+const MEM_ADDRESS_INFO_TRAILER_LEN: usize = 3 * std::mem::size_of::<u64>();
+
+/// A description of a remote memory buffer (address, length and key), to be sent to the peers that will access it.
+///
+/// The encoding returned by [MemAddressInfo::to_bytes] is self-describing and independent of the endianness and
+/// pointer width of the host: a header (magic, version, memory registration mode of the domain the key belongs to,
+/// key size) is followed by the key and the address and length of the buffer, all integers being little-endian.
+/// Peers decode it with [MemAddressInfo::try_from_bytes], which validates the encoding.
 pub struct MemAddressInfo {
     bytes: Vec<u8>,
 }
 
+/// The error returned when decoding a malformed [MemAddressInfo].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemAddressInfoError {
+    /// The buffer does not start with the magic number of the encoding.
+    BadMagic,
+    /// The buffer was encoded with a version of the format this crate does not understand.
+    UnsupportedVersion(u8),
+    /// The length of the buffer does not match the one described by its header.
+    InvalidLength { expected: usize, found: usize },
+    /// The size of the key does not match its kind (e.g., a non-raw key that is not 8 bytes long).
+    InvalidKeySize(usize),
+}
+
+impl std::fmt::Display for MemAddressInfoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MemAddressInfoError::BadMagic => write!(f, "Not an encoded MemAddressInfo"),
+            MemAddressInfoError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported MemAddressInfo version {}", version)
+            }
+            MemAddressInfoError::InvalidLength { expected, found } => write!(
+                f,
+                "Invalid MemAddressInfo length (expected {} bytes, found {})",
+                expected, found
+            ),
+            MemAddressInfoError::InvalidKeySize(size) => {
+                write!(f, "Invalid MemAddressInfo key size {}", size)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MemAddressInfoError {}
+
+/// Serializes the encoding returned by [MemAddressInfo::to_bytes].
+#[cfg(feature = "serde")]
+impl serde::Serialize for MemAddressInfo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+/// Deserializes and validates an encoding (see [MemAddressInfo::try_from_bytes]).
+///
+/// The result must still be mapped in a domain with [MemAddressInfo::into_remote_info].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MemAddressInfo {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer, "an encoded MemAddressInfo")?;
+        MemAddressInfo::try_from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deserializes a byte string, also accepted as a sequence of bytes by the formats without native byte strings.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_bytes<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+    expecting: &'static str,
+) -> Result<Vec<u8>, D::Error> {
+    struct BytesVisitor(&'static str);
+
+    impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element::<u8>()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
+    deserializer.deserialize_byte_buf(BytesVisitor(expecting))
+}
+
+impl From<MemAddressInfoError> for crate::error::Error {
+    fn from(_: MemAddressInfoError) -> Self {
+        crate::error::Error::from_err_code(libfabric_sys::FI_EINVAL)
+    }
+}
+
+// A validated view over an encoded MemAddressInfo
+struct MemAddressInfoFields<'a> {
+    raw_key: bool,
+    mr_mode: enums::MrMode,
+    key: &'a [u8],
+    key_base_addr: u64,
+    addr: u64,
+    len: u64,
+}
+
+fn read_u32_le(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn read_u64_le(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
 impl MemAddressInfo {
     pub fn from_slice<T: Copy, I>(
         slice_base: &[T],
@@ -246,41 +383,40 @@ impl MemAddressInfo {
         key: &MemoryRegionKey,
         info: &InfoEntry<I>,
     ) -> MemAddressInfo {
-        let addr = if info.domain_attr().mr_mode().is_basic()
-            || info.domain_attr().mr_mode().is_virt_addr()
-        {
+        let mr_mode = info.domain_attr().mr_mode();
+        let addr = if mr_mode.is_basic() || mr_mode.is_virt_addr() {
             let base_addr = slice_base.as_ptr() as u64;
             base_addr + (std::mem::size_of::<T>() * offset) as u64
         } else {
             (std::mem::size_of::<T>() * offset) as u64
         };
 
-        let key_raw = match key.key {
-            mr::OwnedMemoryRegionKey::Key(_) => false,
-            mr::OwnedMemoryRegionKey::RawKey(_) => true,
+        // Raw keys carry the address the peer must target
+        let (flags, key_bytes, key_base_addr, addr) = match key.key {
+            mr::OwnedMemoryRegionKey::Key(key) => (0, key.to_le_bytes().to_vec(), 0, addr),
+            mr::OwnedMemoryRegionKey::RawKey((raw, base_addr)) => (
+                MEM_ADDRESS_INFO_RAW_KEY,
+                raw.clone(),
+                *base_addr,
+                *base_addr,
+            ),
         };
 
-        let mut key_bytes = key.to_bytes();
-        let mut bytes = if key_raw {
-            key_bytes
-        } else {
-            key_bytes.extend(unsafe {
-                std::slice::from_raw_parts(
-                    &addr as *const u64 as *const u8,
-                    std::mem::size_of::<u64>(),
-                )
-            });
-            key_bytes
-        };
+        let addr_size = std::mem::size_of_val(slice_base) - offset * std::mem::size_of::<T>();
 
-        let addr_size =
-            std::mem::size_of_val(slice_base) - offset * std::mem::size_of::<T>();
-        bytes.extend(unsafe {
-            std::slice::from_raw_parts(
-                &addr_size as *const usize as *const u8,
-                std::mem::size_of::<usize>(),
-            )
-        });
+        let mut bytes = Vec::with_capacity(
+            MEM_ADDRESS_INFO_HEADER_LEN + key_bytes.len() + MEM_ADDRESS_INFO_TRAILER_LEN,
+        );
+        bytes.extend_from_slice(&MEM_ADDRESS_INFO_MAGIC);
+        bytes.push(MEM_ADDRESS_INFO_VERSION);
+        bytes.push(flags);
+        bytes.extend_from_slice(&[0; 2]);
+        bytes.extend_from_slice(&mr_mode.as_raw().to_le_bytes());
+        bytes.extend_from_slice(&(key_bytes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&key_bytes);
+        bytes.extend_from_slice(&key_base_addr.to_le_bytes());
+        bytes.extend_from_slice(&addr.to_le_bytes());
+        bytes.extend_from_slice(&(addr_size as u64).to_le_bytes());
         Self { bytes }
     }
 
@@ -292,55 +428,101 @@ impl MemAddressInfo {
         &mut self.bytes
     }
 
+    /// Decodes a [MemAddressInfo] received from a peer, validating its encoding.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, MemAddressInfoError> {
+        Self::decode(bytes)?;
+        Ok(Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Same as [MemAddressInfo::try_from_bytes], deferring validation to [MemAddressInfo::into_remote_info].
+    ///
+    /// # Safety
+    /// Kept for compatibility, this function performs no validation of `bytes`.
     pub unsafe fn from_bytes(bytes: &[u8]) -> Self {
         Self {
             bytes: bytes.to_vec(),
         }
     }
 
+    /// Returns the memory registration mode of the domain the key was issued by.
+    pub fn mr_mode(&self) -> Result<enums::MrMode, MemAddressInfoError> {
+        Ok(Self::decode(&self.bytes)?.mr_mode)
+    }
+
+    /// Maps the key of the remote buffer in `domain`.
+    ///
+    /// Fails with [crate::error::ErrorKind::InvalidArgument] if the encoding is malformed, or if the key was issued
+    /// by a domain whose memory registration mode (raw keys, virtual addressing) or key size differs from `domain`'s.
     pub fn into_remote_info<EQ: ?Sized + SyncSend + 'static>(
         self,
         domain: &DomainBase<EQ>,
     ) -> Result<RemoteMemAddressInfo, crate::error::Error> {
-        let mapped_key = self.mapped_mr_key(domain)?;
-        let (addr, addr_size) = self.addr();
+        let fields = Self::decode(&self.bytes)?;
+
+        let mr_mode = domain.mr_mode();
+        let virt_addr = |mode: &enums::MrMode| mode.is_basic() || mode.is_virt_addr();
+        if fields.raw_key != mr_mode.is_raw()
+            || virt_addr(&fields.mr_mode) != virt_addr(&mr_mode)
+            || (fields.raw_key && fields.key.len() != domain.mr_key_size())
+        {
+            return Err(crate::error::Error::from_err_code(libfabric_sys::FI_EINVAL));
+        }
+
+        let key = if fields.raw_key {
+            mr::OwnedMemoryRegionKey::RawKey((fields.key.to_vec(), fields.key_base_addr))
+        } else {
+            mr::OwnedMemoryRegionKey::Key(read_u64_le(fields.key, 0))
+        };
+        let len = usize::try_from(fields.len)
+            .map_err(|_| crate::error::Error::from_err_code(libfabric_sys::FI_EINVAL))?;
+
         Ok(RemoteMemAddressInfo::new(
-            RemoteMemoryAddress::new(addr as *const u8),
-            addr_size,
-            mapped_key,
+            RemoteMemoryAddress::new(fields.addr as *const u8),
+            len,
+            key.into_mapped(domain)?,
         ))
     }
 
-    fn mapped_mr_key<EQ: ?Sized + SyncSend + 'static>(
-        &self,
-        domain: &DomainBase<EQ>,
-    ) -> Result<MappedMemoryRegionKey, crate::error::Error> {
-        unsafe {
-            MappedMemoryRegionKey::from_raw(
-                &self.bytes[..self.bytes.len()
-                    - (std::mem::size_of::<u64>() + std::mem::size_of::<usize>())],
-                domain,
-            )
+    fn decode(bytes: &[u8]) -> Result<MemAddressInfoFields<'_>, MemAddressInfoError> {
+        if bytes.len() < MEM_ADDRESS_INFO_MAGIC.len() || bytes[..4] != MEM_ADDRESS_INFO_MAGIC {
+            return Err(MemAddressInfoError::BadMagic);
+        }
+        if bytes.len() < MEM_ADDRESS_INFO_HEADER_LEN {
+            return Err(MemAddressInfoError::InvalidLength {
+                expected: MEM_ADDRESS_INFO_HEADER_LEN,
+                found: bytes.len(),
+            });
+        }
+        if bytes[4] != MEM_ADDRESS_INFO_VERSION {
+            return Err(MemAddressInfoError::UnsupportedVersion(bytes[4]));
         }
-    }
 
-    fn addr(&self) -> (u64, usize) {
-        let mut addr = 0u64;
-        unsafe {
-            std::slice::from_raw_parts_mut(&mut addr as *mut u64 as *mut u8, 8).copy_from_slice(
-                &self.bytes[self.bytes.len()
-                    - (std::mem::size_of::<u64>() + std::mem::size_of::<usize>())
-                    ..self.bytes.len() - std::mem::size_of::<usize>()],
-            )
-        };
+        let raw_key = bytes[5] & MEM_ADDRESS_INFO_RAW_KEY != 0;
+        let mr_mode = enums::MrMode::from_raw(read_u32_le(bytes, 8));
+        let key_size = read_u32_le(bytes, 12) as usize;
+        if key_size == 0 || (!raw_key && key_size != std::mem::size_of::<u64>()) {
+            return Err(MemAddressInfoError::InvalidKeySize(key_size));
+        }
 
-        let mut addr_size = 0usize;
-        unsafe {
-            std::slice::from_raw_parts_mut(&mut addr_size as *mut usize as *mut u8, 8)
-                .copy_from_slice(&self.bytes[self.bytes.len() - std::mem::size_of::<usize>()..])
-        };
+        let expected = MEM_ADDRESS_INFO_HEADER_LEN + key_size + MEM_ADDRESS_INFO_TRAILER_LEN;
+        if bytes.len() != expected {
+            return Err(MemAddressInfoError::InvalidLength {
+                expected,
+                found: bytes.len(),
+            });
+        }
 
-        (addr, addr_size)
+        let key_end = MEM_ADDRESS_INFO_HEADER_LEN + key_size;
+        Ok(MemAddressInfoFields {
+            raw_key,
+            mr_mode,
+            key: &bytes[MEM_ADDRESS_INFO_HEADER_LEN..key_end],
+            key_base_addr: read_u64_le(bytes, key_end),
+            addr: read_u64_le(bytes, key_end + 8),
+            len: read_u64_le(bytes, key_end + 16),
+        })
     }
 }
 
@@ -664,6 +846,14 @@ impl RawMappedAddress {
         }
     }
 
+    pub(crate) fn as_ptr(&self) -> *const libfabric_sys::fi_addr_t {
+        match self {
+            RawMappedAddress::Map(addr) => addr,
+            RawMappedAddress::Table(addr) => addr,
+            RawMappedAddress::Unspec(addr) => addr,
+        }
+    }
+
     pub(crate) fn from_raw(
         av_type: AddressVectorType,
         raw_addr: libfabric_sys::fi_addr_t,
@@ -691,7 +881,7 @@ pub(crate) enum AddressSource {
 ///
 /// Note that other objects that it will extend the respective [`crate::av::AddressVector`]'s (if any) lifetime until they
 /// it is dropped.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum MappedAddress {
     Unspec(UnspecMappedAddress),
     Map(MapMappedAddress),
@@ -780,30 +970,6 @@ const FI_ADDR_NOTAVAIL: u64 = u64::MAX;
 const FI_KEY_NOTAVAIL: u64 = u64::MAX;
 const FI_ADDR_UNSPEC: u64 = u64::MAX;
 
-// pub struct Stx {
-
-//     #[allow(dead_code)]
-//     c_stx: *mut libfabric_sys::fid_stx,
-// }
-
-// impl Stx {
-//     pub(crate) fn new<T0>(domain: &crate::domain::Domain, mut attr: crate::TxAttr, context: &mut T0) -> Result<Stx, error::Error> {
-//         let mut c_stx: *mut libfabric_sys::fid_stx = std::ptr::null_mut();
-//         let c_stx_ptr: *mut *mut libfabric_sys::fid_stx = &mut c_stx;
-//         let err = unsafe { libfabric_sys::inlined_fi_stx_context(domain.c_domain, attr.get_mut(), c_stx_ptr, context as *mut T0 as *mut std::ffi::c_void) };
-
-//         if err != 0 {
-//             Err(crate::error::Error::from_err_code((-err).try_into().unwrap()))
-//         }
-//         else {
-//             Ok(
-//                 Self { c_stx }
-//             )
-//         }
-
-//     }
-// }
-
 // pub struct SrxAttr {
 //     c_attr: libfabric_sys::fi_srx_attr,
 // }
@@ -875,6 +1041,8 @@ struct Context1 {
     pub(crate) ready: AtomicBool,
     pub(crate) state: MyOnceCell<ContextState>,
     pub(crate) waker: MyOnceCell<std::task::Waker>,
+    // Whether the context replaces one given up to an operation that could not be canceled
+    abandoned: bool,
 }
 
 impl Context1 {
@@ -889,6 +1057,7 @@ impl Context1 {
             ready: AtomicBool::new(false),
             state: MyOnceCell::new(),
             waker: MyOnceCell::new(),
+            abandoned: false,
         }
     }
 
@@ -927,6 +1096,7 @@ struct Context2 {
     state: MyOnceCell<ContextState>,
     pub(crate) ready: AtomicBool,
     pub(crate) waker: MyOnceCell<std::task::Waker>,
+    abandoned: bool,
 }
 
 impl Context2 {
@@ -941,6 +1111,7 @@ impl Context2 {
             ready: AtomicBool::new(false),
             state: MyOnceCell::new(),
             waker: MyOnceCell::new(),
+            abandoned: false,
         }
     }
 
@@ -1034,22 +1205,70 @@ impl ContextType {
             ContextType::Context1(ctx) => {
                 ctx.ready.store(false, atomic::Ordering::Relaxed);
                 ctx.state.take();
+                ctx.abandoned = false;
             }
             ContextType::Context2(ctx) => {
                 ctx.ready.store(false, atomic::Ordering::Relaxed);
                 ctx.state.take();
+                ctx.abandoned = false;
             }
         }
     }
 
+    fn abandoned(&self) -> bool {
+        match self {
+            ContextType::Context1(ctx) => ctx.abandoned,
+            ContextType::Context2(ctx) => ctx.abandoned,
+        }
+    }
+
     pub(crate) fn state(&mut self) -> &mut MyOnceCell<ContextState> {
         match self {
             ContextType::Context1(ctx) => &mut ctx.state,
             ContextType::Context2(ctx) => &mut ctx.state,
         }
     }
+
+    fn set_trigger(&mut self, event: &mut enums::TriggerEvent) {
+        match self {
+            ContextType::Context1(ctx) => {
+                let (event_type, trigger) = event.as_raw();
+                unsafe {
+                    std::ptr::write(
+                        (&mut ctx.c_val as *mut libfabric_sys::fi_context).cast(),
+                        libfabric_sys::fi_triggered_context {
+                            event_type,
+                            trigger,
+                        },
+                    )
+                }
+            }
+            ContextType::Context2(ctx) => {
+                let (event_type, trigger) = event.as_raw2();
+                unsafe {
+                    std::ptr::write(
+                        (&mut ctx.c_val as *mut libfabric_sys::fi_context2).cast(),
+                        libfabric_sys::fi_triggered_context2 {
+                            event_type,
+                            trigger,
+                        },
+                    )
+                }
+            }
+        }
+    }
 }
 
+// A triggered context overlays the libfabric context at the beginning of [Context1]/[Context2]
+const _: () = assert!(
+    std::mem::size_of::<libfabric_sys::fi_triggered_context>()
+        <= std::mem::size_of::<libfabric_sys::fi_context>()
+);
+const _: () = assert!(
+    std::mem::size_of::<libfabric_sys::fi_triggered_context2>()
+        <= std::mem::size_of::<libfabric_sys::fi_context2>()
+);
+
 impl Context {
     fn inner_mut(&mut self) -> *mut std::ffi::c_void {
         self.0.inner_mut()
@@ -1083,6 +1302,45 @@ impl Context {
     pub(crate) fn ready(&self) -> bool {
         self.0.ready()
     }
+
+    /// Gives up the memory of the context to an operation that can neither be canceled nor waited for,
+    /// replacing it with a fresh one. The completion of the operation, if any, is stored in leaked memory.
+    ///
+    /// Until the context is reset, [Context::abandoned] reports that the operation was given up, so that the
+    /// owners of the buffers of the operation know that they must not release them either.
+    pub(crate) fn leak(&mut self) {
+        let fresh = match &self.0 {
+            ContextType::Context1(ctx) => ContextType::Context1(Box::new(Context1 {
+                abandoned: true,
+                ..Context1::new(ctx.id)
+            })),
+            ContextType::Context2(ctx) => ContextType::Context2(Box::new(Context2 {
+                abandoned: true,
+                ..Context2::new(ctx.id)
+            })),
+        };
+        match std::mem::replace(&mut self.0, fresh) {
+            ContextType::Context1(ctx) => {
+                Box::leak(ctx);
+            }
+            ContextType::Context2(ctx) => {
+                Box::leak(ctx);
+            }
+        }
+    }
+
+    /// Whether the last operation the context was passed to was given up with [Context::leak].
+    pub(crate) fn abandoned(&self) -> bool {
+        self.0.abandoned()
+    }
+
+    /// Turns the context into a triggered context, so that the operation it is passed to is deferred
+    /// until `event` occurs (e.g., a counter reaches its threshold).
+    ///
+    /// Once the operation has completed, the context can be reused for regular operations.
+    pub(crate) fn set_trigger(&mut self, event: &mut enums::TriggerEvent) {
+        self.0.set_trigger(event)
+    }
 }
 
 // pub trait BindImpl: AsRawFid {}
@@ -1091,7 +1349,15 @@ impl Context {
 // }
 
 pub trait FdRetrievable {}
-pub trait Waitable: AsRawFid {}
+/// Objects that expose a wait object to the fabric (i.e., [cq::CompletionQueue], [eq::EventQueue] and [cntr::Counter])
+/// and can thus be passed to [sync::WaitSet::trywait] or, with the exception of event queues, added to a [sync::PollSet].
+pub trait Waitable: SyncSend {
+    #[doc(hidden)]
+    fn waitable_fid(&self) -> fid::RawFid;
+
+    #[doc(hidden)]
+    fn waitable_clone(&self) -> Box<dyn Waitable>;
+}
 pub trait Writable {}
 pub trait WaitRetrievable {}
 
@@ -1149,8 +1415,6 @@ pub enum SyncCaps {
 pub use SyncCaps as CntrCaps;
 pub use SyncCaps as CqCaps;
 
-use crate::fid::AsRawFid;
-
 pub enum EqCaps {
     WAIT = 0,
     RETRIEVE = 1,
@@ -1,22 +1,24 @@
 use crate::{
     av_set::{AddressVectorSet, AddressVectorSetImpl},
     comm::{collective::CollectiveEp, message::extract_raw_ctx},
+    cq::ReadCq,
     enums::{AddressVectorType, JoinOptions},
-    ep::{EndpointBase, EpState},
-    eq::JoinCompleteEvent,
+    ep::{Address, EndpointBase, EndpointImplBase, EpState},
+    eq::{JoinCompleteEvent, ReadEq},
     error::Error,
     fid::{
-        AsRawTypedFid, AsTypedFid, BorrowedTypedFid, EpRawFid, McRawFid, MutBorrowedTypedFid,
-        OwnedMcFid,
+        AsRawFid, AsRawTypedFid, AsTypedFid, BorrowedTypedFid, EpRawFid, McRawFid,
+        MutBorrowedTypedFid, OwnedMcFid,
     },
+    infocapsoptions::McastCap,
     Context, MappedAddress, MyOnceCell, MyRc, MyRefCell, RawMappedAddress, SyncSend,
 };
 
 pub(crate) enum MulticastAddressSource {
     MulticastGroup(MyRc<MulticastGroupImpl>),
     AVSet(MyRc<AddressVectorSetImpl>),
-    #[allow(dead_code)]
     RawAddress(RawMappedAddress),
+    Address(Address),
 }
 
 /// Represents a multicast group that is ready for use
@@ -39,7 +41,13 @@ pub struct MulticastGroupBuilder {
 
 /// A mutlicast group that has not been joined yet
 pub struct PendingMulticastGroup {
-    _inner: MyRc<MulticastGroupImpl>,
+    pub(crate) inner: MyRc<MulticastGroupImpl>,
+}
+
+/// A mutlicast group that has been joined but not yet fully established
+/// Requires a `JoinCompleteEvent` to be retrieved before it can be used
+pub struct WaitingMulticastGroup {
+    inner: MyRc<MulticastGroupImpl>,
 }
 
 /// A mutlicast group that has not been joined yet collectively
@@ -78,6 +86,15 @@ impl MulticastGroupBuilder {
         }
     }
 
+    /// Creates a new [MulticastGroupBuilder] from a provider specific multicast [Address]
+    pub fn from_addr(addr: &Address) -> Self {
+        Self {
+            addr_source: MulticastAddressSource::Address(Address {
+                address: addr.as_bytes().to_vec(),
+            }),
+        }
+    }
+
     /// Creates a new [MulticastGroupCollectiveBuilder] from an [AddressVectorSet]
     pub fn collective(self, avset: &AddressVectorSet) -> MulticastGroupCollectiveBuilder {
         MulticastGroupCollectiveBuilder {
@@ -86,11 +103,11 @@ impl MulticastGroupBuilder {
         }
     }
 
+    /// Builds a new incomplete [MultiCastGroup]
     pub fn build(self) -> PendingMulticastGroup {
-        todo!();
-        // PendingMulticastGroup {
-        //     inner: MyRc::new(MulticastGroupImpl::new(self.addr_source))
-        // }
+        PendingMulticastGroup {
+            inner: MyRc::new(MulticastGroupImpl::new(self.addr_source)),
+        }
     }
 }
 
@@ -108,43 +125,65 @@ impl MulticastGroupCollectiveBuilder {
 
 impl WaitingMulticastGroupCollective {
     /// Completes the join process for the multicast group and return the fully established group
-    pub fn join_complete(self, _event: JoinCompleteEvent) -> MultiCastGroup {
+    /// This method asserts that the event's fid matches the multicast group's fid.
+    ///
+    /// # Panics
+    /// Panics if the event's fid does not match the multicast group's fid.
+    pub fn join_complete(self, event: JoinCompleteEvent) -> MultiCastGroup {
+        assert_eq!(*event.fid(), self.inner.as_typed_fid().as_raw_fid());
+        MultiCastGroup { inner: self.inner }
+    }
+
+    /// Same as [join_complete](Self::join_complete) but does not check that the event's fid matches the multicast group's fid.
+    pub unsafe fn join_complete_unchecked(self, _event: JoinCompleteEvent) -> MultiCastGroup {
         MultiCastGroup { inner: self.inner }
     }
 }
 
-// impl PendingMulticastGroup {
-//         pub fn join_with_context<
-//         E: CollectiveEp + AsTypedFid<EpRawFid> + 'static + SyncSend,
-//         STATE: EpState,
-//     >(
-//         &self,
-//         ep: &EndpointBase<E, STATE>,
-//         options: JoinOptions,
-//         context: &mut Context,
-//     ) -> Result<WaitingMulticastGroup, Error> {
-//         self.inner
-//             .join_impl(&ep.inner, options, Some(context.inner_mut()))?;
-//         Ok(WaitingMulticastGroup {
-//             inner: self.inner.clone()
-//         })
-//     }
-
-//     pub fn join_collective<
-//         E: CollectiveEp + AsTypedFid<EpRawFid> + 'static + SyncSend,
-//         STATE: EpState,
-//         const INIT: bool,
-//     >(
-//         &self,
-//         ep: &EndpointBase<E, STATE>,
-//         options: JoinOptions,
-//     ) -> Result<WaitingMulticastGroup, Error> {
-//         self.inner.join_impl(&ep.inner, options, None)?;
-//         Ok(WaitingMulticastGroup {
-//             inner: self.inner.clone()
-//         })
-//     }
-// }
+impl WaitingMulticastGroup {
+    /// Completes the join process for the multicast group and return the fully established group
+    /// This method asserts that the event's fid matches the multicast group's fid.
+    ///
+    /// # Panics
+    /// Panics if the event's fid does not match the multicast group's fid.
+    pub fn join_complete(self, event: JoinCompleteEvent) -> MultiCastGroup {
+        assert_eq!(*event.fid(), self.inner.as_typed_fid().as_raw_fid());
+        MultiCastGroup { inner: self.inner }
+    }
+
+    /// Same as [join_complete](Self::join_complete) but does not check that the event's fid matches the multicast group's fid.
+    pub unsafe fn join_complete_unchecked(self, _event: JoinCompleteEvent) -> MultiCastGroup {
+        MultiCastGroup { inner: self.inner }
+    }
+}
+
+impl PendingMulticastGroup {
+    /// Joins the multicast group with the given endpoint and options
+    ///
+    /// Corresponds to `fi_join`
+    pub fn join_with_context<E: MulticastEp + 'static, STATE: EpState>(
+        self,
+        ep: &EndpointBase<E, STATE>,
+        options: JoinOptions,
+        context: &mut Context,
+    ) -> Result<WaitingMulticastGroup, Error> {
+        self.inner
+            .join_impl(&ep.inner, options, Some(context.inner_mut()))?;
+        Ok(WaitingMulticastGroup { inner: self.inner })
+    }
+
+    /// Joins the multicast group with the given endpoint and options
+    ///
+    /// Corresponds to `fi_join`
+    pub fn join<E: MulticastEp + 'static, STATE: EpState>(
+        self,
+        ep: &EndpointBase<E, STATE>,
+        options: JoinOptions,
+    ) -> Result<WaitingMulticastGroup, Error> {
+        self.inner.join_impl(&ep.inner, options, None)?;
+        Ok(WaitingMulticastGroup { inner: self.inner })
+    }
+}
 
 impl PendingMulticastGroupCollective {
     /// Joins the multicast group collectively with the given endpoint and options
@@ -186,25 +225,32 @@ impl PendingMulticastGroupCollective {
 
 pub(crate) struct MulticastGroupImpl {
     c_mc: MyOnceCell<OwnedMcFid>,
-    eps: MyRefCell<Vec<MyRc<dyn CollectiveValidEp>>>,
+    eps: MyRefCell<Vec<MyRc<dyn McastValidEp>>>,
     addr: MyOnceCell<RawMappedAddress>,
     addr_source: MulticastAddressSource,
     avset: MyOnceCell<MyRc<AddressVectorSetImpl>>,
 }
 
-pub(crate) trait CollectiveValidEp: SyncSend + AsTypedFid<EpRawFid> {}
-impl<EP: CollectiveEp + SyncSend + AsTypedFid<EpRawFid>> CollectiveValidEp for EP {}
+pub(crate) trait McastValidEp: SyncSend + AsTypedFid<EpRawFid> {}
+impl<EP: SyncSend + AsTypedFid<EpRawFid>> McastValidEp for EP {}
+
+/// Endpoints that can join a multicast group through [PendingMulticastGroup::join]
+pub trait MulticastEp: AsTypedFid<EpRawFid> + SyncSend {}
+impl<EP: McastCap, EQ: ?Sized + ReadEq, CQ: ?Sized + ReadCq> MulticastEp
+    for EndpointImplBase<EP, EQ, CQ>
+{
+}
 
 impl MulticastGroupImpl {
-    // pub(crate) fn new(addr: MulticastAddressSource) -> Self {
-    //     Self {
-    //         c_mc: MyOnceCell::new(),
-    //         addr: MyOnceCell::new(),
-    //         eps: MyRefCell::new(Vec::new()),
-    //         addr_source: addr,
-    //         avset: MyOnceCell::new(),
-    //     }
-    // }
+    pub(crate) fn new(addr: MulticastAddressSource) -> Self {
+        Self {
+            c_mc: MyOnceCell::new(),
+            addr: MyOnceCell::new(),
+            eps: MyRefCell::new(Vec::new()),
+            addr_source: addr,
+            avset: MyOnceCell::new(),
+        }
+    }
 
     pub(crate) fn new_collective(
         addr: MulticastAddressSource,
@@ -223,54 +269,59 @@ impl MulticastGroupImpl {
         }
     }
 
-    // pub(crate) fn join_impl<
-    //     EP: CollectiveEp + AsTypedFid<EpRawFid> + 'static + SyncSend,
-    // >(
-    //     &self,
-    //     ep: &MyRc<EP>,
-    //     options: JoinOptions,
-    //     context: Option<*mut std::ffi::c_void>,
-    // ) -> Result<(), Error> {
-    //     let mut c_mc: McRawFid = std::ptr::null_mut();
-    //     let ctx = extract_raw_ctx(context);
-    //     let addr = self.addr.get();
-    //     let raw_addr = if let Some(addr) = addr {
-    //         addr.clone()
-    //     } else {
-    //         match &self.addr_source {
-    //             MulticastAddressSource::MulticastGroup(mc) => mc.addr.get().unwrap().clone(),
-    //             MulticastAddressSource::AVSet(avset) => {panic!("Cannot call join with a AV set address");},
-    //             MulticastAddressSource::RawAddress(addr) => {*addr}
-    //         }
-    //     };
-    //     let err =
-    //         if let Some(ctx) = context {
-    //             unsafe { libfabric_sys::inlined_fi_join(
-    //                 ep.as_typed_fid_mut().as_raw_typed_fid(),
-    //                 raw_addr.get(), options.as_raw(), &mut c_mc, (ctx as *mut T).cast()
-    //             ) }
-    //         }
-    //         else {
-    //             unsafe { libfabric_sys::inlined_fi_join(
-    //                 ep.as_typed_fid_mut().as_raw_typed_fid(),
-    //                 raw_addr.get(), options.as_raw(), &mut c_mc, std::ptr::null_mut()
-    //             ) }
-    //         };
-
-    //     if err != 0 {
-    //         Err(Error::from_err_code((-err).try_into().unwrap()))
-    //     }
-    //     else {
-    //         if let Err(old_mc)  = self.c_mc.set(OwnedMcFid::from(c_mc)) {
-    //             assert!(old_mc.as_raw_typed_fid() == c_mc);
-    //         }
-    //         else {
-    //             self.addr.set(unsafe { libfabric_sys::inlined_fi_mc_addr(c_mc)}).unwrap()
-    //         }
-    //         self.eps.write().push(ep.clone());
-    //         Ok(())
-    //     }
-    // }
+    pub(crate) fn join_impl<EP: MulticastEp + 'static>(
+        &self,
+        ep: &MyRc<EP>,
+        options: JoinOptions,
+        context: Option<*mut std::ffi::c_void>,
+    ) -> Result<(), Error> {
+        let mut c_mc: McRawFid = std::ptr::null_mut();
+        let ctx = extract_raw_ctx(context);
+        let raw_addr = self.addr.get().or(match &self.addr_source {
+            MulticastAddressSource::MulticastGroup(mc) => mc.addr.get(),
+            _ => None,
+        });
+        let c_addr: *const std::ffi::c_void = match (&raw_addr, &self.addr_source) {
+            (Some(addr), _) => addr.as_ptr().cast(),
+            (None, MulticastAddressSource::RawAddress(addr)) => addr.as_ptr().cast(),
+            (None, MulticastAddressSource::Address(addr)) => addr.as_bytes().as_ptr().cast(),
+            // AV sets are joined through join_collective, and groups must be established before they are joined
+            (None, MulticastAddressSource::AVSet(_))
+            | (None, MulticastAddressSource::MulticastGroup(_)) => {
+                return Err(Error::from_err_code(libfabric_sys::FI_EINVAL));
+            }
+        };
+        let err = unsafe {
+            libfabric_sys::inlined_fi_join(
+                ep.as_typed_fid_mut().as_raw_typed_fid(),
+                c_addr,
+                options.as_raw(),
+                &mut c_mc,
+                ctx,
+            )
+        };
+
+        if err != 0 {
+            Err(Error::from_err_code((-err).try_into().unwrap()))
+        } else {
+            if let Err(old_mc) = self.c_mc.set(OwnedMcFid::from(c_mc)) {
+                assert!(old_mc.as_typed_fid().as_raw_typed_fid() == c_mc);
+            } else {
+                let mc_addr = unsafe { libfabric_sys::inlined_fi_mc_addr(c_mc) };
+                let mc_addr = match raw_addr {
+                    Some(RawMappedAddress::Map(_)) => RawMappedAddress::Map(mc_addr),
+                    Some(RawMappedAddress::Table(_)) => RawMappedAddress::Table(mc_addr),
+                    _ => RawMappedAddress::Unspec(mc_addr),
+                };
+                let _ = self.addr.set(mc_addr);
+            }
+            #[cfg(feature = "thread-safe")]
+            self.eps.write().push(ep.clone());
+            #[cfg(not(feature = "thread-safe"))]
+            self.eps.borrow_mut().push(ep.clone());
+            Ok(())
+        }
+    }
 
     pub(crate) fn join_collective_impl<
         EP: CollectiveEp + AsTypedFid<EpRawFid> + 'static + SyncSend,
@@ -287,10 +338,15 @@ impl MulticastGroupImpl {
             addr.clone()
         } else {
             match &self.addr_source {
-                MulticastAddressSource::MulticastGroup(mc) => mc.addr.get().unwrap().clone(),
+                MulticastAddressSource::MulticastGroup(mc) => mc
+                    .addr
+                    .get()
+                    .ok_or_else(|| Error::from_err_code(libfabric_sys::FI_EINVAL))?
+                    .clone(),
                 MulticastAddressSource::AVSet(avset) => avset.address()?,
-                MulticastAddressSource::RawAddress(_) => {
-                    panic!("Cannot call join_collective without a AV set or MC group");
+                // Collective joins need an AV set or an established group
+                MulticastAddressSource::RawAddress(_) | MulticastAddressSource::Address(_) => {
+                    return Err(Error::from_err_code(libfabric_sys::FI_EINVAL));
                 }
             }
         };
@@ -344,14 +400,6 @@ impl MultiCastGroup {
     pub(crate) fn raw_addr(&self) -> &RawMappedAddress {
         self.inner.addr.get().unwrap()
     }
-
-    // pub fn join<E: CollectiveEp + AsRawTypedFid<Output = EpRawFid> + 'static>(&self, ep: &EndpointBase<E>, addr: &Address, options: JoinOptions) -> Result<(), Error> {
-    //     self.inner.join_impl::<(), E>(&ep.inner, addr, options, None)
-    // }
-
-    // pub fn join_with_context<E: CollectiveEp + AsRawTypedFid<Output = EpRawFid> + 'static,T>(&self, ep: &EndpointBase<E>, addr: &Address, options: JoinOptions, context: &mut Context) -> Result<(), Error> {
-    //     self.inner.join_impl(&ep.inner, addr, options, Some(context.inner_mut()))
-    // }
 }
 
 impl AsTypedFid<McRawFid> for MultiCastGroup {
@@ -1,16 +1,16 @@
-use std::{marker::PhantomData, ptr::null};
+use std::{collections::BTreeMap, marker::PhantomData, ops::RangeBounds, ptr::null};
 
 #[allow(unused_imports)]
 // use crate::fid::AsFid;
 use crate::{
     cntr::ReadCntr,
-    domain::DomainImplT,
+    domain::{DomainBase, DomainImplT},
     enums::{MrAccess, MrRegOpt},
     ep::{BaseEndpoint, EpState},
     fid::{self, AsRawFid, AsRawTypedFid, MrRawFid, OwnedMrFid, RawFid},
     iovec::IoVec,
     utils::check_error,
-    Context, MyOnceCell, MyRc, SyncSend,
+    Context, MyOnceCell, MyRc, MyRefCell, SyncSend,
 };
 use crate::{
     ep::ActiveEndpoint,
@@ -41,6 +41,73 @@ impl MemoryRegionKey<'_> {
     }
 }
 
+/// Serializes the byte representation of the key (see [MemoryRegionKey::to_bytes]).
+///
+/// Peers deserialize it as an [UnmappedMemoryRegionKey].
+#[cfg(feature = "serde")]
+impl serde::Serialize for MemoryRegionKey<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+/// The byte representation of a [MemoryRegionKey] received from a peer, which has to be mapped in a
+/// domain before it can be used to access the remote [MemoryRegion].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnmappedMemoryRegionKey {
+    bytes: Vec<u8>,
+}
+
+impl UnmappedMemoryRegionKey {
+    /// Wraps the byte representation of a key (see [MemoryRegionKey::to_bytes]).
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    /// Returns the byte representation of the key.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Maps the key in `domain` (see [MappedMemoryRegionKey::from_raw]).
+    ///
+    /// Fails with [crate::error::ErrorKind::InvalidArgument] if the length of the bytes does not match the size of
+    /// the keys of `domain`. Bytes that do not represent a key of a remote region are not detected, but only make the
+    /// remote accesses using the mapped key fail.
+    pub fn into_mapped<EQ: ?Sized + SyncSend + 'static>(
+        self,
+        domain: &crate::domain::DomainBase<EQ>,
+    ) -> Result<MappedMemoryRegionKey, Error> {
+        // The length of the bytes is validated against the domain before they are read or mapped
+        unsafe { MappedMemoryRegionKey::from_raw(&self.bytes, domain) }
+    }
+}
+
+impl From<MemoryRegionKey<'_>> for UnmappedMemoryRegionKey {
+    fn from(key: MemoryRegionKey<'_>) -> Self {
+        Self {
+            bytes: key.to_bytes(),
+        }
+    }
+}
+
+/// Serializes the byte representation of the key, as [MemoryRegionKey] does.
+#[cfg(feature = "serde")]
+impl serde::Serialize for UnmappedMemoryRegionKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.bytes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UnmappedMemoryRegionKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::deserialize_bytes(deserializer, "a memory region key").map(|bytes| Self { bytes })
+    }
+}
+
 impl OwnedMemoryRegionKey {
     /// Construct a new [OwnedMemoryRegionKey] from a slice of bytes, usually received
     /// from remote node using raw keys.
@@ -52,7 +119,7 @@ impl OwnedMemoryRegionKey {
     unsafe fn from_bytes<EQ: ?Sized + SyncSend>(
         raw: &[u8],
         domain: &crate::domain::DomainBase<EQ>,
-    ) -> Self {
+    ) -> Result<Self, crate::error::Error> {
         OwnedMemoryRegionKey::from_bytes_impl(raw, &*domain.inner)
     }
 
@@ -79,21 +146,31 @@ impl OwnedMemoryRegionKey {
         }
     }
 
-    unsafe fn from_bytes_impl(raw: &[u8], domain: &(impl DomainImplT + ?Sized)) -> Self {
+    unsafe fn from_bytes_impl(
+        raw: &[u8],
+        domain: &(impl DomainImplT + ?Sized),
+    ) -> Result<Self, crate::error::Error> {
         if domain.mr_mode().is_raw() {
-            assert!(raw.len() == domain.mr_key_size() + std::mem::size_of::<u64>());
-            let base_addr = *(raw[raw.len() - std::mem::size_of::<u64>()..].as_ptr() as *const u64);
-            Self::RawKey((
+            if raw.len() != domain.mr_key_size() + std::mem::size_of::<u64>() {
+                return Err(crate::error::Error::from_err_code(libfabric_sys::FI_EINVAL));
+            }
+            let base_addr = std::ptr::read_unaligned(
+                raw[raw.len() - std::mem::size_of::<u64>()..].as_ptr() as *const u64,
+            );
+            Ok(Self::RawKey((
                 raw[0..raw.len() - std::mem::size_of::<u64>()].to_vec(),
                 base_addr,
-            ))
+            )))
         } else {
+            if raw.len() != std::mem::size_of::<u64>() {
+                return Err(crate::error::Error::from_err_code(libfabric_sys::FI_EINVAL));
+            }
             let mut key = 0u64;
             unsafe {
                 std::slice::from_raw_parts_mut(&mut key as *mut u64 as *mut u8, 8)
                     .copy_from_slice(raw)
             };
-            Self::Key(key)
+            Ok(Self::Key(key))
         }
     }
 
@@ -101,7 +178,7 @@ impl OwnedMemoryRegionKey {
         MemoryRegionKey { key: self }
     }
 
-    fn into_mapped<EQ: ?Sized + 'static + SyncSend>(
+    pub(crate) fn into_mapped<EQ: ?Sized + 'static + SyncSend>(
         mut self,
         domain: &crate::domain::DomainBase<EQ>,
     ) -> Result<MappedMemoryRegionKey, crate::error::Error> {
@@ -142,7 +219,7 @@ impl MappedMemoryRegionKey {
         raw: &[u8],
         domain: &crate::domain::DomainBase<EQ>,
     ) -> Result<Self, Error> {
-        OwnedMemoryRegionKey::from_bytes(raw, domain)
+        OwnedMemoryRegionKey::from_bytes(raw, domain)?
             .into_mapped(domain)
             .map_err(|err| crate::error::Error::from_err_code(err.c_err))
     }
@@ -234,7 +311,7 @@ fn raw_key(
         raw_key[raw_key_len - std::mem::size_of::<u64>()..].copy_from_slice(unsafe {
             std::slice::from_raw_parts(&base_addr as *const u64 as *const u8, 8)
         });
-        Ok(unsafe { OwnedMemoryRegionKey::from_bytes_impl(&raw_key, domain) })
+        unsafe { OwnedMemoryRegionKey::from_bytes_impl(&raw_key, domain) }
     }
 }
 
@@ -391,15 +468,14 @@ impl MemoryRegionImpl {
                 },
             )
         };
-        if err != 0 && self.bound_cntr.set(cntr.clone()).is_err() {
-            panic!("Memory Region already bound to an Endpoint");
+        if err == 0 && self.bound_cntr.set(cntr.clone()).is_err() {
+            panic!("Memory Region already bound to a Counter");
         }
         check_error(err.try_into().unwrap())
     }
 }
 
 impl MemoryRegionImpl {
-    #[allow(dead_code)]
     pub(crate) fn bind_ep<EP: ActiveEndpoint + 'static>(
         &self,
         ep: &MyRc<EP>,
@@ -411,7 +487,7 @@ impl MemoryRegionImpl {
                 0,
             )
         };
-        if err != 0 && self.bound_ep.set(ep.clone()).is_err() {
+        if err == 0 && self.bound_ep.set(ep.clone()).is_err() {
             panic!("Memory Region already bound to an Endpoint");
         }
         check_error(err.try_into().unwrap())
@@ -961,7 +1037,7 @@ impl Default for MemoryRegionAttr {
 
 /// A memory region bound to an [crate::ep::Endpoint]
 pub struct EpBindingMemoryRegion {
-    mr: MemoryRegion,
+    pub(crate) mr: MemoryRegion,
 }
 
 /// A memory region bound to a [MemoryRegion]
@@ -997,7 +1073,10 @@ impl EpBindingMemoryRegion {
 
     /// Enables a memory region for use.
     ///
-    /// Corresponds to `fi_mr_enable`
+    /// The memory region is bound to `ep` before being enabled. `ep` may be
+    /// connectionless or connection-oriented, and does not need to be enabled yet.
+    ///
+    /// Corresponds to `fi_mr_bind` with a `fid_ep`, followed by `fi_mr_enable`
     pub fn enable<EP: ActiveEndpoint + 'static, STATE: EpState>(
         self,
         ep: &crate::ep::EndpointBase<EP, STATE>,
@@ -1006,6 +1085,18 @@ impl EpBindingMemoryRegion {
         self.mr.inner.enable()?;
         Ok(self.mr)
     }
+
+    /// Enables a memory region for use with all the contexts of a scalable endpoint.
+    ///
+    /// Corresponds to `fi_mr_bind` with the `fid_ep` of a scalable endpoint, followed by `fi_mr_enable`
+    pub fn enable_scalable<E>(
+        self,
+        sep: &crate::ep::ScalableEndpoint<E>,
+    ) -> Result<MemoryRegion, crate::error::Error> {
+        self.mr.inner.bind_ep(&sep.inner)?;
+        self.mr.inner.enable()?;
+        Ok(self.mr)
+    }
 }
 
 impl RmaEventMemoryRegion {
@@ -1236,7 +1327,7 @@ impl<'a> MemoryRegionBuilder<'a> {
         if domain.inner._eq_rc.get().is_some() {
             let (_eq, async_reg) = domain.inner._eq_rc.get().unwrap();
             if *async_reg {
-                panic!("Manual async memory registration is not supported. Use the ::async_::mr::MemoryRegionBuilder for that.")
+                panic!("Manual async memory registration is not supported. Use MemoryRegionBuilder::build_async for that.")
             }
         }
         match &mut self.backing_buf {
@@ -1255,15 +1346,391 @@ impl<'a> MemoryRegionBuilder<'a> {
         }
     }
 
-    // /// Constructs a new [MemoryRegion] with the configurations requested so far.
-    // ///
-    // /// Corresponds to creating a `fi_mr_attr`, setting its fields to the requested ones,
-    // /// and passign it to `fi_mr_regattr`.
-    // pub async fn build_async(self) -> Result<(Event<usize>,MemoryRegion), crate::error::Error> {
-    //     panic!("Async memory registration is currently not supported due to a potential bug in libfabric");
-    //     self.mr_attr.iov(&self.iovs);
-    //     MemoryRegion::from_attr_async(self.domain, self.mr_attr, self.flags).await
-    // }
+}
+
+//================== Memory Region Cache ==================//
+
+struct CachedRegion {
+    mr: MemoryRegion,
+    last_use: u64,
+    // Only cloned into the handles returned by the cache, so that other clones of the region do not count as uses
+    handles: MyRc<()>,
+}
+
+impl CachedRegion {
+    fn in_use(&self) -> bool {
+        MyRc::strong_count(&self.handles) > 1
+    }
+}
+
+// Identifies a cached region by the buffer it belongs to and the address range it covers
+type CachedRegionKey = (u64, usize, usize);
+
+struct MemoryRegionCacheState {
+    regions: BTreeMap<CachedRegionKey, CachedRegion>,
+    // Keys of the cached regions ordered by last use
+    lru: BTreeMap<u64, CachedRegionKey>,
+    clock: u64,
+    next_buffer: u64,
+}
+
+impl MemoryRegionCacheState {
+    fn remove(&mut self, key: &CachedRegionKey) -> Option<CachedRegion> {
+        let region = self.regions.remove(key)?;
+        self.lru.remove(&region.last_use);
+        Some(region)
+    }
+
+    fn buffer_keys(&self, buffer: u64) -> impl Iterator<Item = CachedRegionKey> + '_ {
+        self.regions
+            .range((buffer, 0, 0)..=(buffer, usize::MAX, usize::MAX))
+            .map(|(key, _)| *key)
+    }
+}
+
+/// A cache of [MemoryRegion]s registered over ranges of the buffers it manages.
+///
+/// Buffers are handed over to the cache with [MemoryRegionCache::insert], which returns a [CachedBuffer] owning them.
+/// Looking up a range of a [CachedBuffer] returns a registration covering it if one exists, or registers the range
+/// otherwise. Regions are reference-counted by the handles returned from the cache, and only regions that are not in
+/// use are evicted, in least recently used order, once the number of cached regions reaches the configured capacity.
+/// If every cached region is in use, lookups that need a new registration fail with [crate::error::ErrorKind::NoSpace].
+///
+/// The regions of a buffer are deregistered when its [CachedBuffer] is dropped, so cached regions always describe
+/// live memory.
+pub struct MemoryRegionCache<EQ: ?Sized> {
+    domain: DomainBase<EQ>,
+    access: MrAccess,
+    iface: crate::enums::HmemIface,
+    capacity: usize,
+    state: MyRc<MyRefCell<MemoryRegionCacheState>>,
+}
+
+/// Builder for the [MemoryRegionCache] type.
+pub struct MemoryRegionCacheBuilder {
+    access: MrAccess,
+    iface: crate::enums::HmemIface,
+    capacity: usize,
+}
+
+impl MemoryRegionCacheBuilder {
+    /// Initiates the creation of a new [MemoryRegionCache].
+    ///
+    /// The capacity of the cache defaults to `fi_domain_attr::mr_cnt` of `info`.
+    pub fn new<E>(info: &crate::info::InfoEntry<E>) -> Self {
+        let mr_cnt = info.domain_attr().mr_cnt();
+        Self {
+            access: MrAccess::new(),
+            iface: crate::enums::HmemIface::System,
+            capacity: if mr_cnt == 0 { usize::MAX } else { mr_cnt },
+        }
+    }
+
+    /// Sets the access permissions requested for the memory regions registered by the cache.
+    pub fn access(mut self, access: &MrAccess) -> Self {
+        self.access = *access;
+        self
+    }
+
+    /// Sets the interface of the memory registered by the cache.
+    pub fn iface(mut self, iface: crate::enums::HmemIface) -> Self {
+        self.iface = iface;
+        self
+    }
+
+    /// Sets the maximum number of memory regions the cache keeps registered.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Constructs a new [MemoryRegionCache] registering memory on `domain`.
+    pub fn build<EQ: ?Sized + 'static + SyncSend>(
+        self,
+        domain: &crate::domain::DomainBase<EQ>,
+    ) -> MemoryRegionCache<EQ> {
+        MemoryRegionCache {
+            domain: DomainBase {
+                inner: domain.inner.clone(),
+            },
+            access: self.access,
+            iface: self.iface,
+            capacity: self.capacity,
+            state: MyRc::new(MyRefCell::new(MemoryRegionCacheState {
+                regions: BTreeMap::new(),
+                lru: BTreeMap::new(),
+                clock: 0,
+                next_buffer: 0,
+            })),
+        }
+    }
+}
+
+impl<EQ: ?Sized + 'static + SyncSend> MemoryRegionCache<EQ> {
+    /// Hands `buf` over to the cache, returning a [CachedBuffer] whose ranges can be looked up with
+    /// [MemoryRegionCache::get] and [MemoryRegionCache::get_mut].
+    ///
+    /// No memory is registered until a range of the buffer is looked up.
+    pub fn insert<T>(&self, buf: impl Into<Box<[T]>>) -> CachedBuffer<T> {
+        #[cfg(feature = "thread-safe")]
+        let mut state = self.state.write();
+        #[cfg(not(feature = "thread-safe"))]
+        let mut state = self.state.borrow_mut();
+
+        let id = state.next_buffer;
+        state.next_buffer += 1;
+        CachedBuffer {
+            buf: buf.into(),
+            id,
+            state: self.state.clone(),
+        }
+    }
+
+    /// Returns a handle to a memory region covering `range` of `buf`, registering the range if no cached region
+    /// covers it.
+    ///
+    /// Fails with [crate::error::ErrorKind::InvalidArgument] if `buf` was not inserted in this cache, and with
+    /// [crate::error::ErrorKind::NoSpace] if the cache is full and all of its regions are in use.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of the bounds of `buf`.
+    pub fn get<'a, T>(
+        &self,
+        buf: &'a CachedBuffer<T>,
+        range: impl RangeBounds<usize>,
+    ) -> Result<CachedMemoryRegion<'a>, crate::error::Error> {
+        let slice = &buf.buf[(range.start_bound().cloned(), range.end_bound().cloned())];
+        let (mr, handle) = self.lookup_or_register(buf, slice)?;
+        Ok(CachedMemoryRegion {
+            mr,
+            _handle: handle,
+            mem_base: slice.as_ptr() as usize,
+            mem_len: std::mem::size_of_val(slice),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns a handle to a memory region covering `range` of `buf`, registering the range if no cached region
+    /// covers it.
+    ///
+    /// Fails and panics like [MemoryRegionCache::get].
+    pub fn get_mut<'a, T>(
+        &self,
+        buf: &'a mut CachedBuffer<T>,
+        range: impl RangeBounds<usize>,
+    ) -> Result<CachedMemoryRegionMut<'a>, crate::error::Error> {
+        let slice = &buf.buf[(range.start_bound().cloned(), range.end_bound().cloned())];
+        let (mr, handle) = self.lookup_or_register(buf, slice)?;
+        Ok(CachedMemoryRegionMut {
+            mr,
+            _handle: handle,
+            mem_base: slice.as_ptr() as usize,
+            mem_len: std::mem::size_of_val(slice),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the number of memory regions currently held by the cache.
+    pub fn len(&self) -> usize {
+        #[cfg(feature = "thread-safe")]
+        let state = self.state.read();
+        #[cfg(not(feature = "thread-safe"))]
+        let state = self.state.borrow();
+
+        state.regions.len()
+    }
+
+    /// Returns true if the cache does not hold any memory regions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lookup_or_register<T>(
+        &self,
+        buffer: &CachedBuffer<T>,
+        slice: &[T],
+    ) -> Result<(MemoryRegion, MyRc<()>), crate::error::Error> {
+        if !MyRc::ptr_eq(&buffer.state, &self.state) {
+            return Err(crate::error::Error::from_err_code(libfabric_sys::FI_EINVAL));
+        }
+
+        let id = buffer.id;
+        let start = slice.as_ptr() as usize;
+        let end = start + std::mem::size_of_val(slice);
+        #[cfg(feature = "thread-safe")]
+        let mut state = self.state.write();
+        #[cfg(not(feature = "thread-safe"))]
+        let mut state = self.state.borrow_mut();
+
+        state.clock += 1;
+        let clock = state.clock;
+        let state = &mut *state;
+        let covering = state
+            .regions
+            .range((id, 0, 0)..=(id, start, usize::MAX))
+            .rev()
+            .find(|(key, _)| key.2 >= end)
+            .map(|(key, _)| *key);
+        if let Some(key) = covering {
+            let region = state.regions.get_mut(&key).unwrap();
+            state.lru.remove(&region.last_use);
+            state.lru.insert(clock, key);
+            region.last_use = clock;
+            return Ok((region.mr.clone(), region.handles.clone()));
+        }
+
+        // Unused regions overlapping the range are superseded by its registration.
+        // Regions in use stay cached, and count against the capacity, until they can be evicted.
+        let superseded: Vec<CachedRegionKey> = state
+            .regions
+            .range((id, 0, 0)..(id, end, 0))
+            .filter(|(key, region)| key.2 > start && !region.in_use())
+            .map(|(key, _)| *key)
+            .collect();
+        for key in superseded {
+            state.remove(&key);
+        }
+
+        if state.regions.len() >= self.capacity {
+            let regions = &state.regions;
+            let lru = state
+                .lru
+                .values()
+                .find(|key| !regions[*key].in_use())
+                .copied();
+
+            match lru {
+                Some(key) => {
+                    state.remove(&key);
+                }
+                None => {
+                    return Err(crate::error::Error::from_err_code(libfabric_sys::FI_ENOSPC));
+                }
+            }
+        }
+
+        let mr = match MemoryRegionBuilder::new(slice, self.iface)
+            .access(&self.access)
+            .build(&self.domain)?
+        {
+            MaybeDisabledMemoryRegion::Enabled(mr) => mr,
+            MaybeDisabledMemoryRegion::Disabled(_) => {
+                return Err(crate::error::Error::from_err_code(
+                    libfabric_sys::FI_EOPNOTSUPP,
+                ));
+            }
+        };
+
+        let key = (id, start, end);
+        let handles = MyRc::new(());
+        state.lru.insert(clock, key);
+        state.regions.insert(
+            key,
+            CachedRegion {
+                mr: mr.clone(),
+                last_use: clock,
+                handles: handles.clone(),
+            },
+        );
+
+        Ok((mr, handles))
+    }
+}
+
+/// A buffer owned by a [MemoryRegionCache].
+///
+/// Dropping the buffer removes all of its regions from the cache.
+pub struct CachedBuffer<T> {
+    buf: Box<[T]>,
+    id: u64,
+    state: MyRc<MyRefCell<MemoryRegionCacheState>>,
+}
+
+impl<T> CachedBuffer<T> {
+    /// Returns the contents of the buffer.
+    pub fn as_slice(&self) -> &[T] {
+        &self.buf
+    }
+
+    /// Returns the contents of the buffer as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.buf
+    }
+
+    /// Removes the regions of the buffer from the cache and returns the buffer.
+    pub fn into_inner(mut self) -> Box<[T]> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+impl<T> Drop for CachedBuffer<T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "thread-safe")]
+        let mut state = self.state.write();
+        #[cfg(not(feature = "thread-safe"))]
+        let mut state = self.state.borrow_mut();
+
+        let keys: Vec<CachedRegionKey> = state.buffer_keys(self.id).collect();
+        for key in keys {
+            state.remove(&key);
+        }
+    }
+}
+
+/// A handle to a cached [MemoryRegion] covering a range of a [CachedBuffer].
+///
+/// The region will not be evicted from the [MemoryRegionCache] while the handle is alive.
+pub struct CachedMemoryRegion<'a> {
+    mr: MemoryRegion,
+    _handle: MyRc<()>,
+    mem_base: usize,
+    mem_len: usize,
+    phantom: PhantomData<&'a ()>,
+}
+
+/// A handle to a cached [MemoryRegion] covering a range of a mutably borrowed [CachedBuffer].
+///
+/// The region will not be evicted from the [MemoryRegionCache] while the handle is alive.
+pub struct CachedMemoryRegionMut<'a> {
+    mr: MemoryRegion,
+    _handle: MyRc<()>,
+    mem_base: usize,
+    mem_len: usize,
+    phantom: PhantomData<&'a mut ()>,
+}
+
+impl CachedMemoryRegion<'_> {
+    /// Returns the memory region covering the buffer
+    pub fn memory_region(&self) -> &MemoryRegion {
+        &self.mr
+    }
+
+    /// Return a local descriptor of the memory region covering the buffer.
+    pub fn descriptor(&self) -> MemoryRegionDesc<'_> {
+        self.mr.descriptor()
+    }
+
+    /// Returns the buffer as a slice of the memory region covering it.
+    pub fn slice(&self) -> MemoryRegionSlice<'_> {
+        MemoryRegionSlice::new(self.mem_base, self.mem_len, self.mr.descriptor())
+    }
+}
+
+impl CachedMemoryRegionMut<'_> {
+    /// Returns the memory region covering the buffer
+    pub fn memory_region(&self) -> &MemoryRegion {
+        &self.mr
+    }
+
+    /// Return a local descriptor of the memory region covering the buffer.
+    pub fn descriptor(&self) -> MemoryRegionDesc<'_> {
+        self.mr.descriptor()
+    }
+
+    /// Returns the buffer as a mutable slice of the memory region covering it.
+    pub fn slice_mut(&mut self) -> MemoryRegionSliceMut<'_> {
+        MemoryRegionSliceMut::new(self.mem_base, self.mem_len, self.mr.descriptor())
+    }
 }
 
 //=================== Async Stuff =========================//
@@ -1401,7 +1868,22 @@ mod tests {
                     let mr = match mr {
                         MaybeDisabledMemoryRegion::Enabled(mr) => mr,
                         MaybeDisabledMemoryRegion::Disabled(disabled_mr) => match disabled_mr {
-                            super::DisabledMemoryRegion::EpBind(_disabled_mr) => todo!(), //disabled_mr.enable(ep),
+                            super::DisabledMemoryRegion::EpBind(disabled_mr) => {
+                                let cq = crate::cq::CompletionQueueBuilder::new()
+                                    .build(&domain)
+                                    .unwrap();
+                                match crate::ep::EndpointBuilder::new(&entry)
+                                    .build_with_shared_cq(&domain, &cq, false)
+                                    .unwrap()
+                                {
+                                    crate::ep::Endpoint::Connectionless(ep) => {
+                                        disabled_mr.enable(&ep).unwrap()
+                                    }
+                                    crate::ep::Endpoint::ConnectionOriented(ep) => {
+                                        disabled_mr.enable(&ep).unwrap()
+                                    }
+                                }
+                            }
                             super::DisabledMemoryRegion::RmaEvent(disabled_mr) => {
                                 disabled_mr.enable().unwrap()
                             }
@@ -1419,6 +1901,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mr_cache() {
+        let info = Info::new(&crate::info::libfabric_version())
+            .enter_hints()
+            .caps(crate::infocapsoptions::InfoCaps::new().msg().rma())
+            .leave_hints()
+            .get()
+            .unwrap();
+        let entry = info.into_iter().next();
+
+        if let Some(entry) = entry {
+            let fab = crate::fabric::FabricBuilder::new().build(&entry).unwrap();
+            let domain = crate::domain::DomainBuilder::new(&fab, &entry)
+                .build()
+                .unwrap();
+            if domain.mr_mode().is_endpoint() {
+                return;
+            }
+
+            let cache = super::MemoryRegionCacheBuilder::new(&entry)
+                .access(&MrAccess::new().send().recv())
+                .capacity(2)
+                .build(&domain);
+
+            let bufs = [
+                cache.insert(vec![0u8; 64]),
+                cache.insert(vec![0u8; 64]),
+                cache.insert(vec![0u8; 64]),
+            ];
+            let unrelated;
+            {
+                let whole = cache.get(&bufs[0], ..).unwrap();
+                let part = cache.get(&bufs[0], 8..32).unwrap();
+                assert_eq!(cache.len(), 1);
+                assert_eq!(part.slice().as_slice().len(), 24);
+                assert_eq!(
+                    whole.descriptor().as_raw(),
+                    part.descriptor().as_raw()
+                );
+                unrelated = whole.memory_region().clone();
+
+                let _second = cache.get(&bufs[1], ..).unwrap();
+                assert_eq!(cache.len(), 2);
+
+                // Both cached regions are in use, so none can be evicted
+                let err = cache.get(&bufs[2], ..).err().unwrap();
+                assert!(matches!(err.kind, crate::error::ErrorKind::NoSpace));
+            }
+
+            // Evicts the least recently used region, clones of it that were not handed out by the cache do not matter
+            let _third = cache.get(&bufs[2], ..).unwrap();
+            assert_eq!(cache.len(), 2);
+            drop(unrelated);
+
+            // Dropping a buffer removes its regions from the cache
+            drop(_third);
+            let [_, second, third] = bufs;
+            drop(second);
+            assert_eq!(cache.len(), 1);
+            assert_eq!(third.into_inner().len(), 64);
+            assert!(cache.is_empty());
+
+            // Buffers can only be looked up in the cache that owns them
+            let other = super::MemoryRegionCacheBuilder::new(&entry).build(&domain);
+            let foreign = other.insert(vec![0u8; 64]);
+            let err = cache.get(&foreign, ..).err().unwrap();
+            assert!(matches!(err.kind, crate::error::ErrorKind::InvalidArgument));
+        } else {
+            panic!("No capable fabric found!");
+        }
+    }
+
     // #[test]
     // fn try_wrapper() {
     //     let mut vec = vec![0u8; 10];
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use crate::{
+    av::{AddressVectorBase, AvInAddress, Block},
+    enums::AVOptions,
+    ep::Address,
+    eq::ReadEq,
+    error::Error,
+    MappedAddress,
+};
+
+/// A table of peers built on an [AddressVectorBase], identified by dense ids `0..n`.
+///
+/// Whether the underlying address vector is of type [crate::enums::AddressVectorType::Map] or
+/// [crate::enums::AddressVectorType::Table], peers are looked up by id in constant time, and the
+/// [MappedAddress] of a peer can be translated back to its id (e.g., for the source of a received message).
+///
+/// Ids are stable: removing a peer leaves its id vacant until a new peer is inserted under it, so that ids can
+/// follow the ranks of a job whose members join and leave dynamically.
+pub struct PeerTable<EQ: ?Sized + ReadEq + 'static> {
+    av: AddressVectorBase<Block, EQ>,
+    peers: Vec<Option<MappedAddress>>,
+    ids: HashMap<libfabric_sys::fi_addr_t, usize>,
+}
+
+impl<EQ: ?Sized + ReadEq + 'static> PeerTable<EQ> {
+    /// Creates an empty table whose peers are inserted in `av`.
+    pub fn new(av: &AddressVectorBase<Block, EQ>) -> Self {
+        Self {
+            av: AddressVectorBase::from_impl(&av.inner),
+            peers: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Inserts `addrs` in the address vector as new peers, returning their ids.
+    ///
+    /// Either all the addresses are inserted or none is: if any address cannot be mapped, the others are removed
+    /// from the address vector and the error of the first address that failed is returned.
+    pub fn extend(&mut self, addrs: &[Address]) -> Result<std::ops::Range<usize>, Error> {
+        let mapped = self.insert_all(addrs)?;
+        let start = self.peers.len();
+        for addr in mapped {
+            self.ids.insert(addr.raw_addr(), self.peers.len());
+            self.peers.push(Some(addr));
+        }
+        Ok(start..self.peers.len())
+    }
+
+    /// Inserts `addr` in the address vector as a new peer, returning its id.
+    pub fn push(&mut self, addr: &Address) -> Result<usize, Error> {
+        Ok(self.extend(std::slice::from_ref(addr))?.start)
+    }
+
+    /// Inserts `addr` in the address vector as the peer `id`, growing the table if needed.
+    ///
+    /// Fails with [crate::error::ErrorKind::InvalidArgument] if `id` is already taken.
+    pub fn insert(&mut self, id: usize, addr: &Address) -> Result<(), Error> {
+        if self.contains(id) {
+            return Err(Error::from_err_code(libfabric_sys::FI_EINVAL));
+        }
+
+        let mapped = self.insert_all(std::slice::from_ref(addr))?.pop().unwrap();
+        if id >= self.peers.len() {
+            self.peers.resize(id + 1, None);
+        }
+        self.ids.insert(mapped.raw_addr(), id);
+        self.peers[id] = Some(mapped);
+        Ok(())
+    }
+
+    /// Removes the peer `id` from the table and the address vector (see [AddressVectorBase::remove]).
+    ///
+    /// Fails with [crate::error::ErrorKind::InvalidArgument] if there is no such peer.
+    pub fn remove(&mut self, id: usize) -> Result<(), Error> {
+        let addr = self
+            .peers
+            .get(id)
+            .cloned()
+            .flatten()
+            .ok_or_else(|| Error::from_err_code(libfabric_sys::FI_EINVAL))?;
+
+        let raw_addr = addr.raw_addr();
+        self.av.remove(vec![addr])?;
+        self.peers[id] = None;
+        self.ids.remove(&raw_addr);
+        Ok(())
+    }
+
+    /// Returns the address of the peer `id`, if any.
+    pub fn get(&self, id: usize) -> Option<&MappedAddress> {
+        self.peers.get(id).and_then(|addr| addr.as_ref())
+    }
+
+    /// Returns the id of the peer whose address is `addr`, if any.
+    pub fn id_of(&self, addr: &MappedAddress) -> Option<usize> {
+        self.ids.get(&addr.raw_addr()).copied()
+    }
+
+    /// Returns `true` if there is a peer with id `id`.
+    pub fn contains(&self, id: usize) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Returns the number of ids allocated by the table, i.e., one more than the largest id ever inserted.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Returns `true` if no id has been allocated.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Returns the number of peers currently in the table.
+    pub fn num_peers(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Iterates over the peers of the table and their ids, in increasing order of id.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &MappedAddress)> {
+        self.peers
+            .iter()
+            .enumerate()
+            .filter_map(|(id, addr)| addr.as_ref().map(|addr| (id, addr)))
+    }
+
+    fn insert_all(&self, addrs: &[Address]) -> Result<Vec<MappedAddress>, Error> {
+        let mapped = self
+            .av
+            .insert(AvInAddress::Encoded(addrs), AVOptions::new())?;
+        if mapped.iter().all(|addr| addr.is_ok()) {
+            return Ok(mapped.into_iter().flatten().collect());
+        }
+
+        // Do not leave the addresses that could be mapped behind
+        let (inserted, failed): (Vec<_>, Vec<_>) =
+            mapped.into_iter().partition(|addr| addr.is_ok());
+        let inserted: Vec<_> = inserted.into_iter().flatten().collect();
+        if !inserted.is_empty() {
+            self.av.remove(inserted)?;
+        }
+        Err(failed.into_iter().find_map(|addr| addr.err()).unwrap())
+    }
+}
@@ -0,0 +1,138 @@
+//! Out-of-band exchange of endpoint addresses over PMI.
+//!
+//! Requires the `pmi` feature, along with one of the backend features of the `pmi` crate
+//! (e.g., `pmi/with-pmi2`).
+
+use pmi::{Pmi, PmiError};
+
+/// The `pmi` crate, to initialize the [Pmi] instance passed to [exchange_addresses].
+pub use pmi;
+
+use crate::{
+    av::{AddressVectorBase, AvInAddress, Block},
+    enums::AVOptions,
+    ep::{Address, BaseEndpoint},
+    eq::ReadEq,
+    fid::AsRawFid,
+    MappedAddress,
+};
+
+const DEFAULT_KEY: &str = "libfabric-ep-addr";
+
+/// The error returned by [exchange_addresses].
+pub enum ExchangeError {
+    /// A PMI operation (put, fence or get) failed.
+    Pmi(PmiError),
+    /// Retrieving the local address or inserting the addresses in the [AddressVectorBase] failed.
+    Fabric(crate::error::Error),
+    /// The address published by `rank` is malformed or could not be inserted in the [AddressVectorBase], because of
+    /// `error`.
+    Unmapped {
+        rank: usize,
+        error: crate::error::Error,
+    },
+}
+
+impl std::fmt::Debug for ExchangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ExchangeError::Pmi(err) => write!(f, "PMI error: {:?}", err),
+            ExchangeError::Fabric(err) => write!(f, "{:?}", err),
+            ExchangeError::Unmapped { rank, error } => {
+                write!(
+                    f,
+                    "Address of rank {} could not be mapped: {:?}",
+                    rank, error
+                )
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ExchangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            // PmiError only implements Debug
+            ExchangeError::Pmi(err) => write!(f, "PMI error: {:?}", err),
+            ExchangeError::Fabric(err) => write!(f, "{}", err),
+            ExchangeError::Unmapped { rank, error } => {
+                write!(f, "Address of rank {} could not be mapped: {}", rank, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExchangeError {}
+
+impl From<PmiError> for ExchangeError {
+    fn from(err: PmiError) -> Self {
+        ExchangeError::Pmi(err)
+    }
+}
+
+impl From<crate::error::Error> for ExchangeError {
+    fn from(err: crate::error::Error) -> Self {
+        ExchangeError::Fabric(err)
+    }
+}
+
+/// Publishes the address of `ep` through `pmi`, retrieves the addresses of all the ranks of the job and inserts them
+/// in `av`, in rank order.
+///
+/// Returns the [MappedAddress] of every rank, indexed by rank (including the local one). This is a collective
+/// operation: every rank of the job must call it, with the same `key` if [exchange_addresses_with_key] is used.
+///
+/// Addresses published with a different length than the local one are rejected as [ExchangeError::Unmapped]. If any
+/// address cannot be inserted, the addresses inserted along with it are removed from `av` before returning.
+pub fn exchange_addresses<FID: AsRawFid, EQ: ReadEq + ?Sized + 'static>(
+    ep: &impl BaseEndpoint<FID>,
+    pmi: &impl Pmi,
+    av: &AddressVectorBase<Block, EQ>,
+) -> Result<Vec<MappedAddress>, ExchangeError> {
+    exchange_addresses_with_key(ep, pmi, av, DEFAULT_KEY)
+}
+
+/// Same as [exchange_addresses] but publishes the address under `key`, so that several endpoints can be exchanged
+/// within the same job.
+pub fn exchange_addresses_with_key<FID: AsRawFid, EQ: ReadEq + ?Sized + 'static>(
+    ep: &impl BaseEndpoint<FID>,
+    pmi: &impl Pmi,
+    av: &AddressVectorBase<Block, EQ>,
+    key: &str,
+) -> Result<Vec<MappedAddress>, ExchangeError> {
+    let local = ep.getname()?;
+    pmi.put(key, local.as_bytes())?;
+    pmi.exchange()?;
+
+    let mut addresses = Vec::with_capacity(pmi.ranks().len());
+    for rank in pmi.ranks() {
+        if *rank == pmi.rank() {
+            addresses.push(local.clone());
+        } else {
+            let bytes = pmi.get(key, rank)?;
+            // Every endpoint of the job is opened from the same provider, so their addresses share the same format
+            if bytes.len() != local.as_bytes().len() {
+                return Err(ExchangeError::Unmapped {
+                    rank: addresses.len(),
+                    error: crate::error::Error::from_err_code(libfabric_sys::FI_EINVAL),
+                });
+            }
+            addresses.push(unsafe { Address::from_bytes(&bytes) });
+        }
+    }
+
+    let mapped = av.insert(AvInAddress::Encoded(&addresses), AVOptions::new())?;
+    if let Some(rank) = mapped.iter().position(|addr| addr.is_err()) {
+        // Do not leave the addresses that could be mapped behind
+        let (inserted, mut failed): (Vec<_>, Vec<_>) =
+            mapped.into_iter().partition(|addr| addr.is_ok());
+        let inserted: Vec<_> = inserted.into_iter().flatten().collect();
+        if !inserted.is_empty() {
+            av.remove(inserted)?;
+        }
+        let error = failed.swap_remove(0).err().unwrap();
+        return Err(ExchangeError::Unmapped { rank, error });
+    }
+
+    Ok(mapped.into_iter().flatten().collect())
+}
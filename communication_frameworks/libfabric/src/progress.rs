@@ -0,0 +1,217 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::{error::ErrorKind, MyRc, SyncSend};
+
+/// Objects whose progress can be driven by a [ProgressEngine] (i.e., [crate::cq::CompletionQueue],
+/// [crate::eq::EventQueue] and [crate::cntr::Counter], including their asynchronous counterparts).
+///
+/// For synchronous objects, driving progress does not consume any entry, which are left for the user to read.
+/// Asynchronous completion queues are drained instead, and each completion is handed over to the [crate::Context]
+/// of its operation, waking up the future awaiting it.
+pub trait Progressable: SyncSend {
+    /// Drives the progress of the object once, returning the number of entries that were dispatched
+    /// (for synchronous objects, whether entries are waiting to be read or the number of new counter events).
+    #[doc(hidden)]
+    fn progress_once(&self) -> Result<usize, crate::error::Error>;
+
+    #[doc(hidden)]
+    fn progressable_clone(&self) -> Box<dyn Progressable>;
+}
+
+/// Maps the outcome of a non-consuming, progress-driving read to the number of entries waiting to be read
+/// (i.e., 1 if the queue is not empty), ignoring the expected errors.
+pub(crate) fn progress_result<T>(
+    res: Result<T, crate::error::Error>,
+) -> Result<usize, crate::error::Error> {
+    match res {
+        Ok(_) => Ok(1),
+        Err(err) if matches!(err.kind, ErrorKind::ErrorAvailable) => Ok(1),
+        Err(err) if matches!(err.kind, ErrorKind::TryAgain) => Ok(0),
+        Err(err) => Err(err),
+    }
+}
+
+/// A builder for a [ProgressEngine].
+pub struct ProgressEngineBuilder {
+    objs: Vec<Box<dyn Progressable>>,
+    idle_interval: Option<Duration>,
+}
+
+impl ProgressEngineBuilder {
+    pub fn new() -> Self {
+        Self {
+            objs: Vec::new(),
+            idle_interval: None,
+        }
+    }
+
+    /// Adds `obj` to the objects driven by the engine.
+    ///
+    /// The engine keeps the object alive until it is dropped.
+    pub fn add(mut self, obj: &impl Progressable) -> Self {
+        self.objs.push(obj.progressable_clone());
+        self
+    }
+
+    /// Makes the progress thread sleep for `interval` after every round that did not dispatch any entry,
+    /// i.e., when no asynchronous operation completed, no synchronous queue has entries waiting to be read
+    /// and no counter was incremented.
+    ///
+    /// By default the thread only yields, trading CPU time for latency.
+    pub fn idle_interval(mut self, interval: Duration) -> Self {
+        self.idle_interval = Some(interval);
+        self
+    }
+
+    pub fn build(self) -> ProgressEngine {
+        ProgressEngine {
+            objs: self.objs,
+            idle_interval: self.idle_interval,
+            stop: MyRc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Default for ProgressEngineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drives the progress of a set of completion queues, event queues and counters on behalf of the user.
+///
+/// Providers with [crate::enums::Progress::Manual] data or control progress only make progress while the
+/// application calls into them (e.g., `fi_cq_read`). A `ProgressEngine` takes care of it, either from a
+/// dedicated thread ([ProgressEngine::start]), as a task of an asynchronous executor ([ProgressEngine::run_async])
+/// or manually ([ProgressEngine::progress]).
+pub struct ProgressEngine {
+    objs: Vec<Box<dyn Progressable>>,
+    idle_interval: Option<Duration>,
+    stop: MyRc<AtomicBool>,
+}
+
+impl ProgressEngine {
+    /// Drives the progress of every object of the engine once, returning the number of entries that were dispatched
+    /// (see [Progressable]).
+    pub fn progress(&self) -> Result<usize, crate::error::Error> {
+        let mut dispatched = 0;
+        for obj in self.objs.iter() {
+            dispatched += obj.progress_once()?;
+        }
+        Ok(dispatched)
+    }
+
+    /// Returns a handle to stop [ProgressEngine::run_async] from another task.
+    pub fn stopper(&self) -> ProgressStopper {
+        ProgressStopper {
+            stop: self.stop.clone(),
+        }
+    }
+
+    /// Drives the progress of the objects of the engine until it is stopped through a [ProgressStopper]
+    /// or an error occurs, yielding to the executor after every round.
+    #[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+    pub async fn run_async(&self) -> Result<(), crate::error::Error> {
+        while !self.stop.load(Ordering::Acquire) {
+            self.progress()?;
+            #[cfg(feature = "use-async-std")]
+            async_std::task::yield_now().await;
+            #[cfg(feature = "use-tokio")]
+            tokio::task::yield_now().await;
+        }
+        self.stop.store(false, Ordering::Release);
+        Ok(())
+    }
+
+    #[cfg(feature = "thread-safe")]
+    fn run(&self) -> Result<(), crate::error::Error> {
+        while !self.stop.load(Ordering::Acquire) {
+            if self.progress()? == 0 {
+                match self.idle_interval {
+                    Some(interval) => std::thread::sleep(interval),
+                    None => std::thread::yield_now(),
+                }
+            }
+        }
+        self.stop.store(false, Ordering::Release);
+        Ok(())
+    }
+
+    /// Moves the engine to a new thread driving the progress of its objects until [ProgressThread::stop] is called.
+    #[cfg(feature = "thread-safe")]
+    pub fn start(self) -> Result<ProgressThread, std::io::Error> {
+        let stop = self.stop.clone();
+        let handle = std::thread::Builder::new()
+            .name("libfabric-progress".to_owned())
+            .spawn(move || {
+                let res = self.run();
+                (self, res)
+            })?;
+
+        Ok(ProgressThread {
+            handle: Some(handle),
+            stop,
+        })
+    }
+}
+
+/// Stops a [ProgressEngine] running as an asynchronous task.
+#[derive(Clone)]
+pub struct ProgressStopper {
+    stop: MyRc<AtomicBool>,
+}
+
+impl ProgressStopper {
+    /// Makes the engine return after its current round.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Release);
+    }
+}
+
+#[cfg(feature = "thread-safe")]
+type ProgressThreadOutput = (ProgressEngine, Result<(), crate::error::Error>);
+
+/// A [ProgressEngine] running on its own thread.
+///
+/// Dropping a `ProgressThread` stops the thread and waits for it to exit.
+#[cfg(feature = "thread-safe")]
+pub struct ProgressThread {
+    handle: Option<std::thread::JoinHandle<ProgressThreadOutput>>,
+    stop: MyRc<AtomicBool>,
+}
+
+#[cfg(feature = "thread-safe")]
+impl ProgressThread {
+    /// Returns `true` if the thread has exited on its own, i.e., because driving progress failed.
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().map_or(true, |h| h.is_finished())
+    }
+
+    /// Stops the thread and hands the engine back so that it can be restarted.
+    ///
+    /// Returns the error that made the thread exit early, if any.
+    ///
+    /// # Panics
+    /// If the progress thread panicked.
+    pub fn stop(mut self) -> Result<ProgressEngine, crate::error::Error> {
+        self.stop.store(true, Ordering::Release);
+        let (engine, res) = self
+            .handle
+            .take()
+            .unwrap()
+            .join()
+            .expect("Progress thread panicked");
+        res.map(|_| engine)
+    }
+}
+
+#[cfg(feature = "thread-safe")]
+impl Drop for ProgressThread {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            self.stop.store(true, Ordering::Release);
+            let _ = handle.join();
+        }
+    }
+}
@@ -6,10 +6,10 @@ use crate::{
     fabric::FabricImpl,
     fid::{
         AsRawFid, AsRawTypedFid, AsTypedFid, BorrowedTypedFid, OwnedPollFid, OwnedWaitFid,
-        PollRawFid, WaitRawFid,
+        PollRawFid, RawFid, WaitRawFid,
     },
     utils::check_error,
-    MyRc, Waitable,
+    MyRc, MyRefCell, Waitable,
 };
 
 //================== Wait (fi_wait) ==================//
@@ -187,6 +187,20 @@ impl WaitSet {
     pub fn wait_object(&self) -> Result<WaitObjType2<'_>, crate::error::Error> {
         self.inner.wait_object()
     }
+
+    /// Checks whether it is safe to block on this WaitSet, given the completion queues, event queues and
+    /// counters associated with it.
+    ///
+    /// Returns `Ok(())` if none of the objects has pending events, in which case [WaitSet::wait] (or the native wait object)
+    /// can be used to block. Otherwise an error of kind [crate::error::ErrorKind::TryAgain] is returned and the objects should be read first.
+    ///
+    /// Corresponds to `fi_trywait`
+    pub fn trywait(&self, objs: &[&dyn Waitable]) -> Result<(), crate::error::Error> {
+        let mut raw_fids: Vec<RawFid> = std::iter::once(self.inner.as_typed_fid().as_raw_fid())
+            .chain(objs.iter().map(|x| x.waitable_fid()))
+            .collect();
+        self.inner._fabric_rc.trywait_raw(&mut raw_fids)
+    }
 }
 
 // impl AsFid for WaitSetImpl {
@@ -237,7 +251,7 @@ impl AsRawTypedFid for WaitSetImpl {
     type Output = WaitRawFid;
 
     fn as_raw_typed_fid(&self) -> Self::Output {
-        todo!()
+        self.c_wait.as_typed_fid().as_raw_typed_fid()
     }
 }
 
@@ -282,7 +296,7 @@ impl WaitSetAttr {
 
 //================== Poll (fi_poll) ==================//
 
-// A builder for a [PollSet].
+/// A builder for a [PollSet].
 pub struct PollSetBuilder {
     poll_attr: PollSetAttr,
 }
@@ -294,10 +308,11 @@ impl PollSetBuilder {
         }
     }
 
-    pub fn build<EQ>(
+    /// Builds the PollSet. Objects added to it will be identified by a key of type `K` when polled.
+    pub fn build<K, EQ>(
         self,
         domain: &crate::domain::DomainBase<EQ>,
-    ) -> Result<PollSet, crate::error::Error> {
+    ) -> Result<PollSet<K>, crate::error::Error> {
         PollSet::new(domain, self.poll_attr)
     }
 }
@@ -312,11 +327,23 @@ pub(crate) struct PollSetImpl {
     pub(crate) c_poll: OwnedPollFid,
 }
 
-/// Represents a set for polling events.
+struct PollSetMember<K> {
+    context: usize,
+    fid: usize,
+    key: K,
+    _obj: Box<dyn Waitable>,
+}
+
+/// Represents a set of completion queues and counters that can be polled together.
+///
+/// Each object added to the set is associated with a user-provided key of type `K`, which is what
+/// [PollSet::poll] reports for every object with pending events. Objects are kept alive for as long as they
+/// remain in the set.
 ///
 /// Corresponds to a `fi_poll_set` struct.
-pub struct PollSet {
+pub struct PollSet<K = usize> {
     inner: MyRc<PollSetImpl>,
+    members: MyRefCell<Vec<PollSetMember<K>>>,
 }
 
 impl PollSetImpl {
@@ -347,11 +374,14 @@ impl PollSetImpl {
         }
     }
 
-    pub(crate) fn poll<T0>(&self, contexts: &mut [T0]) -> Result<usize, crate::error::Error> {
+    pub(crate) fn poll(
+        &self,
+        contexts: &mut [*mut std::ffi::c_void],
+    ) -> Result<usize, crate::error::Error> {
         let ret = unsafe {
             libfabric_sys::inlined_fi_poll(
                 self.as_typed_fid_mut().as_raw_typed_fid(),
-                contexts.as_mut_ptr().cast(),
+                contexts.as_mut_ptr(),
                 contexts.len() as i32,
             )
         };
@@ -365,11 +395,11 @@ impl PollSetImpl {
         }
     }
 
-    pub(crate) fn add(&self, fid: &impl Waitable, flags: u64) -> Result<(), crate::error::Error> {
+    pub(crate) fn add(&self, fid: RawFid, flags: u64) -> Result<(), crate::error::Error> {
         let err = unsafe {
             libfabric_sys::inlined_fi_poll_add(
                 self.as_typed_fid_mut().as_raw_typed_fid(),
-                fid.as_raw_fid(),
+                fid,
                 flags,
             )
         };
@@ -377,11 +407,11 @@ impl PollSetImpl {
         check_error(err.try_into().unwrap())
     }
 
-    pub(crate) fn del(&self, fid: &impl Waitable, flags: u64) -> Result<(), crate::error::Error> {
+    pub(crate) fn del(&self, fid: RawFid, flags: u64) -> Result<(), crate::error::Error> {
         let err = unsafe {
             libfabric_sys::inlined_fi_poll_del(
                 self.as_typed_fid_mut().as_raw_typed_fid(),
-                fid.as_raw_fid(),
+                fid,
                 flags,
             )
         };
@@ -468,35 +498,119 @@ impl PollSetImpl {
     }
 }
 
-impl PollSet {
+impl<K> PollSet<K> {
     pub(crate) fn new<EQ>(
         domain: &crate::domain::DomainBase<EQ>,
         attr: crate::sync::PollSetAttr,
     ) -> Result<Self, crate::error::Error> {
         Ok(Self {
             inner: MyRc::new(PollSetImpl::new(domain, attr)?),
+            members: MyRefCell::new(Vec::new()),
         })
     }
 
-    /// Polls the set for events.
+    /// Polls the set for objects with pending events and returns the keys they were added with.
+    ///
+    /// At most one key is returned per object, regardless of how many events it has pending.
+    /// An empty vector means that none of the objects in the set is ready.
     ///
     /// Corresponds to a `fi_poll` function.
-    pub fn poll<T0>(&self, contexts: &mut [T0]) -> Result<usize, crate::error::Error> {
-        self.inner.poll(contexts)
+    pub fn poll(&self) -> Result<Vec<K>, crate::error::Error>
+    where
+        K: Clone,
+    {
+        #[cfg(feature = "thread-safe")]
+        let members = self.members.read();
+        #[cfg(not(feature = "thread-safe"))]
+        let members = self.members.borrow();
+
+        if members.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut contexts = vec![std::ptr::null_mut(); members.len()];
+        let count = self.inner.poll(&mut contexts)?;
+
+        Ok(contexts[..count]
+            .iter()
+            .filter_map(|ctx| {
+                members
+                    .iter()
+                    .find(|m| m.context == *ctx as usize)
+                    .map(|m| m.key.clone())
+            })
+            .collect())
     }
 
-    /// Adds a waitable object to the PollSet.
+    /// Adds a completion queue or counter to the PollSet, associating it with `key`.
+    ///
+    /// `fi_poll` reports objects by the context they were opened with, so two objects sharing the same
+    /// context cannot be part of the same PollSet. Completion queues and counters opened without a context are given
+    /// a unique one when opened.
     ///
     /// Corresponds to a `fi_poll_add` function.
-    pub fn add(&self, fid: &impl Waitable) -> Result<(), crate::error::Error> {
-        self.inner.add(fid, 0)
+    pub fn add(&self, obj: &impl Waitable, key: K) -> Result<(), crate::error::Error> {
+        #[cfg(feature = "thread-safe")]
+        let mut members = self.members.write();
+        #[cfg(not(feature = "thread-safe"))]
+        let mut members = self.members.borrow_mut();
+
+        let fid = obj.waitable_fid();
+        if members.iter().any(|m| m.fid == fid as usize) {
+            return Err(crate::error::Error::from_err_code(
+                libfabric_sys::FI_EALREADY,
+            ));
+        }
+
+        let context = unsafe { (*fid).context } as usize;
+        if members.iter().any(|m| m.context == context) {
+            return Err(crate::error::Error::from_err_code(
+                libfabric_sys::FI_EINVAL,
+            ));
+        }
+
+        self.inner.add(fid, 0)?;
+        members.push(PollSetMember {
+            context,
+            fid: fid as usize,
+            key,
+            _obj: obj.waitable_clone(),
+        });
+        Ok(())
     }
 
-    /// Removes a waitable object from the PollSet.
+    /// Removes a completion queue or counter from the PollSet, returning the key it was added with.
     ///
     /// Corresponds to a `fi_poll_del` function.
-    pub fn del(&self, fid: &impl Waitable) -> Result<(), crate::error::Error> {
-        self.inner.del(fid, 0)
+    pub fn del(&self, obj: &impl Waitable) -> Result<K, crate::error::Error> {
+        #[cfg(feature = "thread-safe")]
+        let mut members = self.members.write();
+        #[cfg(not(feature = "thread-safe"))]
+        let mut members = self.members.borrow_mut();
+
+        let fid = obj.waitable_fid();
+        let pos = members
+            .iter()
+            .position(|m| m.fid == fid as usize)
+            .ok_or_else(|| crate::error::Error::from_err_code(libfabric_sys::FI_ENOENT))?;
+
+        self.inner.del(fid, 0)?;
+        Ok(members.remove(pos).key)
+    }
+
+    /// Returns the number of objects currently in the PollSet.
+    pub fn len(&self) -> usize {
+        #[cfg(feature = "thread-safe")]
+        let members = self.members.read();
+        #[cfg(not(feature = "thread-safe"))]
+        let members = self.members.borrow();
+
+        members.len()
+    }
+
+    /// Returns `true` if no objects have been added to the PollSet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     /// Returns the wait object associated with the PollSet.
@@ -507,6 +621,16 @@ impl PollSet {
     }
 }
 
+impl<K> Drop for PollSet<K> {
+    fn drop(&mut self) {
+        let members = self.members.get_mut();
+
+        for member in members.drain(..) {
+            let _ = self.inner.del(member.fid as RawFid, 0);
+        }
+    }
+}
+
 // impl AsFid for PollSetImpl {
 //     fn as_fid(&self) -> fid::BorrowedFid<'_> {
 //         self.c_poll.as_fid()
@@ -540,7 +664,7 @@ impl AsTypedFid<PollRawFid> for PollSetImpl {
     }
 }
 
-impl AsTypedFid<PollRawFid> for PollSet {
+impl<K> AsTypedFid<PollRawFid> for PollSet<K> {
     fn as_typed_fid(&self) -> BorrowedTypedFid<'_, PollRawFid> {
         self.inner.as_typed_fid()
     }
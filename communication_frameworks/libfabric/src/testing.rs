@@ -0,0 +1,495 @@
+//! In-process loopback harness for testing code built on top of this crate.
+//!
+//! A [LoopbackBuilder] spins up a server and a client in the same process over a loopback-capable provider
+//! (`tcp`, `shm` or `sockets`), exchanges their addresses (and memory region keys) through an in-memory channel
+//! and returns a [LoopbackPair] whose endpoints, completion queues and memory regions are ready to be used.
+//!
+//! Since the exchange never goes through the network or the environment, several pairs can be created
+//! concurrently from different tests without any synchronization between them.
+//!
+//! ```ignore
+//! let pair = LoopbackBuilder::new(InfoCaps::new().msg(), EndpointType::Rdm).build()?;
+//! ```
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::{
+    av::{AddressVector, AddressVectorBuilder},
+    conn_ep::{ConnectedEndpoint, EnabledConnectionOrientedEndpoint},
+    connless_ep::ConnectionlessEndpoint,
+    cq::{CompletionQueue, CompletionQueueBuilder, CompletionQueueImpl},
+    domain::{Domain, DomainBuilder},
+    enums::{AVOptions, AddressVectorType, CqFormat, EndpointType, HmemIface},
+    ep::{Address, BaseEndpoint, Endpoint, EndpointBuilder},
+    eq::{Event, EventQueue, EventQueueBuilder, EventQueueImpl, ReadEq, WaitEq},
+    error::{Error, ErrorKind},
+    fabric::{Fabric, FabricBuilder},
+    info::{Info, InfoEntry, ServiceAddress},
+    infocapsoptions::Caps,
+    mr::{
+        DisabledMemoryRegion, MaybeDisabledMemoryRegion, MemoryRegion, MemoryRegionBuilder,
+        MemoryRegionDesc,
+    },
+    MappedAddress, MemAddressInfo, RemoteMemAddressInfo,
+};
+
+/// Providers that are tried, in order, when no provider is explicitly requested.
+pub const LOOPBACK_PROVIDERS: [&str; 3] = ["tcp", "shm", "sockets"];
+
+/// The completion queue type used by the peers of a [LoopbackPair].
+pub type LoopbackCq = CompletionQueue<CompletionQueueImpl<true, false, false>>;
+
+/// The event queue type used by the peers of a [LoopbackPair].
+pub type LoopbackEq = EventQueue<EventQueueImpl<false, true, false, false>>;
+
+/// The endpoint of a [LoopbackPeer], depending on the requested [EndpointType].
+pub enum LoopbackEndpoint<I> {
+    /// An endpoint of type [EndpointType::Msg], already connected to the other peer.
+    Connected(ConnectedEndpoint<I>),
+    /// An endpoint of type [EndpointType::Rdm] or [EndpointType::Dgram], with the other peer already inserted in its address vector.
+    Connectionless(ConnectionlessEndpoint<I>),
+}
+
+/// One side of a [LoopbackPair].
+///
+/// The memory region, if any, covers the whole of [LoopbackPeer::buf] and its key has already
+/// been exchanged with the other peer (see `remote_mem_info`).
+pub struct LoopbackPeer<I> {
+    pub info: InfoEntry<I>,
+    pub ep: LoopbackEndpoint<I>,
+    pub mr: Option<MemoryRegion>,
+    pub remote_mem_info: Option<RemoteMemAddressInfo>,
+    pub av: Option<AddressVector>,
+    /// The address of the other peer, only set for connectionless endpoints.
+    pub peer_addr: Option<MappedAddress>,
+    pub cq: LoopbackCq,
+    pub eq: LoopbackEq,
+    pub domain: Domain,
+    pub fabric: Fabric,
+    // Not public, so that the buffer covered by the memory region is never reallocated
+    buf: Vec<u8>,
+}
+
+impl<I> LoopbackPeer<I> {
+    /// Returns the name of the provider this peer is running on.
+    pub fn provider(&self) -> &str {
+        self.info.fabric_attr().prov_name()
+    }
+
+    /// Returns the buffer of this peer, covered by its memory region if any.
+    pub fn buf(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the buffer of this peer mutably.
+    pub fn buf_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+
+    /// Borrows the endpoint, the buffer and the descriptor of the memory region (if any) at once,
+    /// e.g., to post a receive in the buffer.
+    pub fn split_mut(
+        &mut self,
+    ) -> (
+        &LoopbackEndpoint<I>,
+        &mut [u8],
+        Option<MemoryRegionDesc<'_>>,
+    ) {
+        (
+            &self.ep,
+            &mut self.buf,
+            self.mr.as_ref().map(|mr| mr.descriptor()),
+        )
+    }
+}
+
+/// A server and a client connected to each other through a loopback provider.
+pub struct LoopbackPair<I> {
+    pub server: LoopbackPeer<I>,
+    pub client: LoopbackPeer<I>,
+}
+
+/// Builder for a [LoopbackPair].
+pub struct LoopbackBuilder<I> {
+    caps: I,
+    ep_type: EndpointType,
+    providers: Vec<String>,
+    buf_size: usize,
+}
+
+struct Channel {
+    tx: Sender<Vec<u8>>,
+    rx: Receiver<Vec<u8>>,
+}
+
+impl Channel {
+    fn pair() -> (Self, Self) {
+        let (server_tx, client_rx) = channel();
+        let (client_tx, server_rx) = channel();
+        (
+            Self {
+                tx: server_tx,
+                rx: server_rx,
+            },
+            Self {
+                tx: client_tx,
+                rx: client_rx,
+            },
+        )
+    }
+
+    fn send(&self, bytes: &[u8]) {
+        self.tx.send(bytes.to_vec()).unwrap();
+    }
+
+    fn recv(&self) -> Vec<u8> {
+        self.rx.recv().unwrap()
+    }
+}
+
+struct PeerBase<I> {
+    info: InfoEntry<I>,
+    fabric: Fabric,
+    domain: Domain,
+    cq: LoopbackCq,
+    eq: LoopbackEq,
+    buf: Vec<u8>,
+}
+
+impl<I: Caps + Clone + 'static> LoopbackBuilder<I> {
+    /// Creates a new builder for endpoints of type `ep_type` with the capabilities `caps`.
+    pub fn new(caps: I, ep_type: EndpointType) -> Self {
+        Self {
+            caps,
+            ep_type,
+            providers: LOOPBACK_PROVIDERS.iter().map(|p| p.to_string()).collect(),
+            buf_size: 4096,
+        }
+    }
+
+    /// Restricts the harness to the given provider instead of trying each of [LOOPBACK_PROVIDERS].
+    pub fn provider(mut self, prov_name: &str) -> Self {
+        self.providers = vec![prov_name.to_string()];
+        self
+    }
+
+    /// Sets the size of the buffer allocated (and registered, if needed) for each peer.
+    pub fn buf_size(mut self, buf_size: usize) -> Self {
+        self.buf_size = buf_size;
+        self
+    }
+
+    /// Builds the pair using the first provider that supports the requested configuration.
+    ///
+    /// Returns an error of kind [ErrorKind::NoData] if none of the providers does, and of kind
+    /// [ErrorKind::InvalidArgument] or [ErrorKind::BadState] if a provider does not behave as requested
+    /// (e.g., it returns an endpoint or an event of another type).
+    pub fn build(self) -> Result<LoopbackPair<I>, Error> {
+        for prov_name in self.providers.iter() {
+            let (server_info, client_info) =
+                match (self.query(prov_name), self.query(prov_name)) {
+                    (Ok(server_info), Ok(client_info)) => (server_info, client_info),
+                    (Err(err), _) | (_, Err(err)) => {
+                        if matches!(err.kind, ErrorKind::NoData | ErrorKind::NotFound) {
+                            continue;
+                        }
+                        return Err(err);
+                    }
+                };
+
+            return match self.ep_type {
+                EndpointType::Msg => self.build_connected(server_info, client_info),
+                _ => self.build_connectionless(server_info, client_info),
+            };
+        }
+
+        Err(Error::from_err_code(libfabric_sys::FI_ENODATA))
+    }
+
+    fn query(&self, prov_name: &str) -> Result<InfoEntry<I>, Error> {
+        let info = Info::new(&crate::info::libfabric_version())
+            .enter_hints()
+            .enter_ep_attr()
+            .type_(self.ep_type)
+            .leave_ep_attr()
+            .enter_fabric_attr()
+            .prov_name(prov_name)
+            .leave_fab_attr()
+            .caps(self.caps.clone())
+            .leave_hints();
+
+        let info = match prov_name {
+            "shm" => info,
+            _ => info.source(ServiceAddress::Node("127.0.0.1".to_string())),
+        };
+
+        info.get()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::from_err_code(libfabric_sys::FI_ENODATA))
+    }
+
+    fn open(&self, info: InfoEntry<I>, fabric: Fabric, eq: LoopbackEq) -> Result<PeerBase<I>, Error> {
+        let format = if info.caps().is_tagged() {
+            CqFormat::Tagged
+        } else {
+            CqFormat::Data
+        };
+        let domain = DomainBuilder::new(&fabric, &info).build()?;
+        let cq = CompletionQueueBuilder::new()
+            .size(info.rx_attr().size() + info.tx_attr().size())
+            .format(format)
+            .build(&domain)?;
+
+        Ok(PeerBase {
+            info,
+            fabric,
+            domain,
+            cq,
+            eq,
+            buf: vec![0; self.buf_size],
+        })
+    }
+
+    fn build_connected(
+        &self,
+        server_info: InfoEntry<I>,
+        client_info: InfoEntry<I>,
+    ) -> Result<LoopbackPair<I>, Error> {
+        let (server_chan, client_chan) = Channel::pair();
+
+        // Server: listen and publish the address of the passive endpoint
+        let server_fabric = FabricBuilder::new().build(&server_info)?;
+        let server_eq = EventQueueBuilder::new(&server_fabric).build()?;
+        let pep = EndpointBuilder::new(&server_info).build_passive(&server_fabric)?;
+        pep.bind(&server_eq, 0)?;
+        pep.listen()?;
+        server_chan.send(pep.getname()?.as_bytes());
+
+        // Client: connect to the published address
+        let client_fabric = FabricBuilder::new().build(&client_info)?;
+        let client_eq = EventQueueBuilder::new(&client_fabric).build()?;
+        let client = self.open(client_info, client_fabric, client_eq)?;
+        let ep = match EndpointBuilder::new(&client.info).build_with_shared_cq(
+            &client.domain,
+            &client.cq,
+            false,
+        )? {
+            Endpoint::ConnectionOriented(ep) => ep,
+            Endpoint::Connectionless(_) => {
+                return Err(Error::from_err_code(libfabric_sys::FI_EINVAL))
+            }
+        };
+        let server_addr = unsafe { Address::from_bytes(&client_chan.recv()) };
+        let client_pending = match ep.enable(&client.eq)? {
+            EnabledConnectionOrientedEndpoint::Unconnected(ep) => ep.connect(&server_addr)?,
+            EnabledConnectionOrientedEndpoint::AcceptPending(_) => {
+                return Err(Error::from_err_code(libfabric_sys::FI_EOPBADSTATE))
+            }
+        };
+
+        // Server: accept the connection request on a new domain
+        let conn_info = match server_eq.sread(-1)? {
+            Event::ConnReq(entry) => entry.info()?,
+            _ => return Err(Error::from_err_code(libfabric_sys::FI_EOPBADSTATE)),
+        };
+        let server = self.open(conn_info, server_fabric, server_eq)?;
+        let ep = match EndpointBuilder::new(&server.info).build_with_shared_cq(
+            &server.domain,
+            &server.cq,
+            false,
+        )? {
+            Endpoint::ConnectionOriented(ep) => ep,
+            Endpoint::Connectionless(_) => {
+                return Err(Error::from_err_code(libfabric_sys::FI_EINVAL))
+            }
+        };
+        let server_pending = match ep.enable(&server.eq)? {
+            EnabledConnectionOrientedEndpoint::AcceptPending(ep) => ep.accept()?,
+            EnabledConnectionOrientedEndpoint::Unconnected(_) => {
+                return Err(Error::from_err_code(libfabric_sys::FI_EOPBADSTATE))
+            }
+        };
+
+        // Both sides have to make progress for the connection to complete
+        let mut server_pending = Some(server_pending);
+        let mut client_pending = Some(client_pending);
+        let mut server_ep = None;
+        let mut client_ep = None;
+        while server_ep.is_none() || client_ep.is_none() {
+            if server_ep.is_none() {
+                if let Some(event) = Self::poll_connected(&server.eq)? {
+                    server_ep = Some(server_pending.take().unwrap().connect_complete(event));
+                }
+            }
+            if client_ep.is_none() {
+                if let Some(event) = Self::poll_connected(&client.eq)? {
+                    client_ep = Some(client_pending.take().unwrap().connect_complete(event));
+                }
+            }
+        }
+        let server_ep = LoopbackEndpoint::Connected(server_ep.unwrap());
+        let client_ep = LoopbackEndpoint::Connected(client_ep.unwrap());
+
+        Self::finish(server, server_ep, None, None, &server_chan, client, client_ep, None, None, &client_chan)
+    }
+
+    fn poll_connected(eq: &LoopbackEq) -> Result<Option<crate::eq::ConnectedEvent>, Error> {
+        match eq.read() {
+            Ok(Event::Connected(event)) => Ok(Some(event)),
+            Ok(_) => Err(Error::from_err_code(libfabric_sys::FI_EOPBADSTATE)),
+            Err(err) if matches!(err.kind, ErrorKind::TryAgain) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn build_connectionless(
+        &self,
+        server_info: InfoEntry<I>,
+        client_info: InfoEntry<I>,
+    ) -> Result<LoopbackPair<I>, Error> {
+        let (server_chan, client_chan) = Channel::pair();
+
+        let mut peers = Vec::with_capacity(2);
+        for info in [server_info, client_info] {
+            let fabric = FabricBuilder::new().build(&info)?;
+            let eq = EventQueueBuilder::new(&fabric).build()?;
+            let peer = self.open(info, fabric, eq)?;
+            let av = match peer.info.domain_attr().av_type() {
+                AddressVectorType::Unspec => AddressVectorBuilder::new().build(&peer.domain)?,
+                av_type => AddressVectorBuilder::new()
+                    .type_(*av_type)
+                    .build(&peer.domain)?,
+            };
+            let ep = match EndpointBuilder::new(&peer.info).build_with_shared_cq(
+                &peer.domain,
+                &peer.cq,
+                false,
+            )? {
+                Endpoint::Connectionless(ep) => ep,
+                Endpoint::ConnectionOriented(_) => {
+                    return Err(Error::from_err_code(libfabric_sys::FI_EINVAL))
+                }
+            };
+            ep.bind_eq(&peer.eq)?;
+            let ep = ep.enable(&av)?;
+            peers.push((peer, ep, av));
+        }
+        let (client, client_ep, client_av) = peers.pop().unwrap();
+        let (server, server_ep, server_av) = peers.pop().unwrap();
+
+        server_chan.send(server_ep.getname()?.as_bytes());
+        client_chan.send(client_ep.getname()?.as_bytes());
+        let server_peer_addr = Self::insert_peer(&server_av, &server_chan)?;
+        let client_peer_addr = Self::insert_peer(&client_av, &client_chan)?;
+
+        Self::finish(
+            server,
+            LoopbackEndpoint::Connectionless(server_ep),
+            Some(server_av),
+            Some(server_peer_addr),
+            &server_chan,
+            client,
+            LoopbackEndpoint::Connectionless(client_ep),
+            Some(client_av),
+            Some(client_peer_addr),
+            &client_chan,
+        )
+    }
+
+    fn insert_peer(av: &AddressVector, chan: &Channel) -> Result<MappedAddress, Error> {
+        let addr = unsafe { Address::from_bytes(&chan.recv()) };
+        av.insert(std::slice::from_ref(&addr).into(), AVOptions::new())?
+            .pop()
+            .unwrap_or_else(|| Err(Error::from_err_code(libfabric_sys::FI_EADDRNOTAVAIL)))
+    }
+
+    fn register(peer: &PeerBase<I>, ep: &LoopbackEndpoint<I>) -> Result<Option<MemoryRegion>, Error> {
+        if !peer.info.domain_attr().mr_mode().is_local() && !peer.info.caps().is_rma() {
+            return Ok(None);
+        }
+
+        let mr = MemoryRegionBuilder::new(&peer.buf, HmemIface::System)
+            .access_read()
+            .access_write()
+            .access_send()
+            .access_recv()
+            .access_remote_read()
+            .access_remote_write()
+            .build(&peer.domain)?;
+
+        let mr = match mr {
+            MaybeDisabledMemoryRegion::Enabled(mr) => mr,
+            MaybeDisabledMemoryRegion::Disabled(DisabledMemoryRegion::EpBind(mr)) => match ep {
+                LoopbackEndpoint::Connected(ep) => mr.enable(ep)?,
+                LoopbackEndpoint::Connectionless(ep) => mr.enable(ep)?,
+            },
+            MaybeDisabledMemoryRegion::Disabled(DisabledMemoryRegion::RmaEvent(mr)) => mr.enable()?,
+        };
+        Ok(Some(mr))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn finish(
+        server: PeerBase<I>,
+        server_ep: LoopbackEndpoint<I>,
+        server_av: Option<AddressVector>,
+        server_peer_addr: Option<MappedAddress>,
+        server_chan: &Channel,
+        client: PeerBase<I>,
+        client_ep: LoopbackEndpoint<I>,
+        client_av: Option<AddressVector>,
+        client_peer_addr: Option<MappedAddress>,
+        client_chan: &Channel,
+    ) -> Result<LoopbackPair<I>, Error> {
+        let server_mr = Self::register(&server, &server_ep)?;
+        let client_mr = Self::register(&client, &client_ep)?;
+
+        let (server_remote, client_remote) = match (&server_mr, &client_mr) {
+            (Some(server_mr), Some(client_mr)) if server.info.caps().is_rma() => {
+                let server_key = server_mr.key()?;
+                let client_key = client_mr.key()?;
+                server_chan.send(
+                    MemAddressInfo::from_slice(&server.buf, 0, &server_key, &server.info).to_bytes(),
+                );
+                client_chan.send(
+                    MemAddressInfo::from_slice(&client.buf, 0, &client_key, &client.info).to_bytes(),
+                );
+                let server_remote = MemAddressInfo::try_from_bytes(&server_chan.recv())?
+                    .into_remote_info(&server.domain)?;
+                let client_remote = MemAddressInfo::try_from_bytes(&client_chan.recv())?
+                    .into_remote_info(&client.domain)?;
+                (Some(server_remote), Some(client_remote))
+            }
+            _ => (None, None),
+        };
+
+        Ok(LoopbackPair {
+            server: LoopbackPeer {
+                info: server.info,
+                ep: server_ep,
+                mr: server_mr,
+                remote_mem_info: server_remote,
+                av: server_av,
+                peer_addr: server_peer_addr,
+                cq: server.cq,
+                eq: server.eq,
+                domain: server.domain,
+                fabric: server.fabric,
+                buf: server.buf,
+            },
+            client: LoopbackPeer {
+                info: client.info,
+                ep: client_ep,
+                mr: client_mr,
+                remote_mem_info: client_remote,
+                av: client_av,
+                peer_addr: client_peer_addr,
+                cq: client.cq,
+                eq: client.eq,
+                domain: client.domain,
+                fabric: client.fabric,
+                buf: client.buf,
+            },
+        })
+    }
+}
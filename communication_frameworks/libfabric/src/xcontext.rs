@@ -5,13 +5,18 @@ use crate::{
     conn_ep::ConnectedEp,
     connless_ep::ConnlessEp,
     cq::ReadCq,
+    domain::{DomainBase, DomainImplBase, DomainImplT},
     enums::{Mode, TrafficClass, TransferOptions},
     ep::{
         ActiveEndpoint, BaseEndpoint, Connected, Connectionless, EndpointBase, EndpointImplBase,
         EpState,
     },
     eq::ReadEq,
-    fid::{AsRawFid, AsRawTypedFid, AsTypedFid, BorrowedTypedFid, EpRawFid, OwnedEpFid},
+    fid::{
+        AsRawFid, AsRawTypedFid, AsTypedFid, BorrowedTypedFid, EpRawFid, OwnedEpFid, OwnedStxFid,
+        StxRawFid,
+    },
+    info::InfoEntry,
     Context, MyOnceCell, MyRc, SyncSend,
 };
 
@@ -574,6 +579,197 @@ impl TxAttr {
 //     }
 // }
 
+//================== SharedTxContext ==================//
+pub(crate) struct SharedTxContextImpl {
+    pub(crate) c_stx: OwnedStxFid,
+    _domain_rc: MyRc<dyn DomainImplT>,
+}
+
+/// A transmit context that can be shared among multiple endpoints of the same domain.
+///
+/// Endpoints bound to a [SharedTxContext] queue their transmit operations to the same
+/// underlying hardware queue, which allows limiting the transmit resources used when a large
+/// number of endpoints is opened. The endpoints keep the shared context alive.
+///
+/// Corresponds to a `fid_stx` created with `fi_stx_context`.
+pub struct SharedTxContext {
+    pub(crate) inner: MyRc<SharedTxContextImpl>,
+}
+
+impl SyncSend for SharedTxContextImpl {}
+impl SyncSend for SharedTxContext {}
+
+impl SharedTxContextImpl {
+    pub(crate) fn new<EQ: ?Sized + 'static + SyncSend>(
+        domain: &MyRc<DomainImplBase<EQ>>,
+        attr: TxAttr,
+        context: *mut std::ffi::c_void,
+    ) -> Result<Self, crate::error::Error> {
+        let c_stx = domain.stx_context(attr, context)?;
+
+        Ok(Self {
+            #[cfg(not(feature = "threading-domain"))]
+            c_stx: OwnedStxFid::from(c_stx),
+            #[cfg(feature = "threading-domain")]
+            c_stx: OwnedStxFid::from(c_stx, domain.c_domain.domain.clone()),
+            _domain_rc: domain.clone(),
+        })
+    }
+}
+
+impl SharedTxContext {
+    pub(crate) fn new<EQ: ?Sized + 'static + SyncSend>(
+        domain: &DomainBase<EQ>,
+        attr: TxAttr,
+        context: Option<&mut Context>,
+    ) -> Result<Self, crate::error::Error> {
+        let c_void = match context {
+            Some(ctx) => ctx.inner_mut(),
+            None => std::ptr::null_mut(),
+        };
+
+        Ok(Self {
+            inner: MyRc::new(SharedTxContextImpl::new(&domain.inner, attr, c_void)?),
+        })
+    }
+}
+
+impl AsTypedFid<StxRawFid> for SharedTxContextImpl {
+    fn as_typed_fid(&self) -> BorrowedTypedFid<'_, StxRawFid> {
+        self.c_stx.as_typed_fid()
+    }
+
+    fn as_typed_fid_mut(&self) -> crate::fid::MutBorrowedTypedFid<'_, StxRawFid> {
+        self.c_stx.as_typed_fid_mut()
+    }
+}
+
+impl AsTypedFid<StxRawFid> for SharedTxContext {
+    fn as_typed_fid(&self) -> BorrowedTypedFid<'_, StxRawFid> {
+        self.inner.as_typed_fid()
+    }
+
+    fn as_typed_fid_mut(&self) -> crate::fid::MutBorrowedTypedFid<'_, StxRawFid> {
+        self.inner.as_typed_fid_mut()
+    }
+}
+
+//================== SharedTxContext Builder ==================//
+pub struct SharedTxContextBuilder<'a> {
+    pub(crate) tx_attr: TxAttr,
+    pub(crate) ctx: Option<&'a mut Context>,
+}
+
+impl<'a> SharedTxContextBuilder<'a> {
+    /// Initiates the creation of a new [SharedTxContext] using the transmit attributes of `info`
+    /// as defaults.
+    pub fn new<I>(info: &InfoEntry<I>) -> Self {
+        Self {
+            tx_attr: info.tx_attr().clone(),
+            ctx: None,
+        }
+    }
+
+    /// Replaces all the transmit attributes of the shared context with `attr`.
+    pub fn attr(mut self, attr: TxAttr) -> Self {
+        self.tx_attr = attr;
+        self
+    }
+
+    /// Sets the mode for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::mode`.
+    pub fn mode(mut self, mode: crate::enums::Mode) -> Self {
+        self.tx_attr.set_mode(mode);
+        self
+    }
+
+    /// Sets the transmit options for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::op_flags`.
+    pub fn set_transmit_options(mut self, ops: TransferOptions) -> Self {
+        ops.transmit();
+        self.tx_attr.set_op_flags(ops);
+        self
+    }
+
+    /// Sets the message order for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::msg_order`.
+    pub fn msg_order(mut self, msg_order: MsgOrder) -> Self {
+        self.tx_attr.set_msg_order(msg_order);
+        self
+    }
+
+    /// Sets the completion order for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::comp_order`.
+    pub fn comp_order(mut self, comp_order: TxCompOrder) -> Self {
+        self.tx_attr.set_comp_order(comp_order);
+        self
+    }
+
+    /// Sets the inject size for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::inject_size`.
+    pub fn inject_size(mut self, size: usize) -> Self {
+        self.tx_attr.set_inject_size(size);
+        self
+    }
+
+    /// Sets the number of transmit operations that can be queued on the shared context.
+    ///
+    /// Corresponds to `fi_tx_attr::size`.
+    pub fn size(mut self, size: usize) -> Self {
+        self.tx_attr.set_size(size);
+        self
+    }
+
+    /// Sets the I/O vector limit for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::iov_limit`.
+    pub fn iov_limit(mut self, iov_limit: usize) -> Self {
+        self.tx_attr.set_iov_limit(iov_limit);
+        self
+    }
+
+    /// Sets the RMA I/O vector limit for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::rma_iov_limit`.
+    pub fn rma_iov_limit(mut self, rma_iov_limit: usize) -> Self {
+        self.tx_attr.set_rma_iov_limit(rma_iov_limit);
+        self
+    }
+
+    /// Sets the traffic class for the shared transmit context.
+    ///
+    /// Corresponds to `fi_tx_attr::tclass`.
+    pub fn tclass(mut self, class: crate::enums::TrafficClass) -> Self {
+        self.tx_attr.set_traffic_class(class);
+        self
+    }
+
+    /// Sets the context for the shared transmit context.
+    ///
+    /// Corresponds to the context passed to `fi_stx_context`.
+    pub fn context(self, ctx: &'a mut Context) -> SharedTxContextBuilder<'a> {
+        SharedTxContextBuilder {
+            tx_attr: self.tx_attr,
+            ctx: Some(ctx),
+        }
+    }
+
+    /// Builds the shared transmit context.
+    ///
+    /// Corresponds to calling `fi_stx_context`.
+    pub fn build<EQ: ?Sized + 'static + SyncSend>(
+        self,
+        domain: &DomainBase<EQ>,
+    ) -> Result<SharedTxContext, crate::error::Error> {
+        SharedTxContext::new(domain, self.tx_attr, self.ctx)
+    }
+}
+
 //================== RxContext ==================//
 pub struct RxContextBase<I, STATE: EpState, CQ: ?Sized> {
     pub(crate) inner: XContextBase<Receive, I, STATE, CQ>,
@@ -1004,6 +1200,218 @@ impl Default for RxAttr {
     }
 }
 
+//================== SharedRxContext ==================//
+pub(crate) struct SharedRxContextImplBase<I, CQ: ?Sized> {
+    #[cfg(not(feature = "threading-completion"))]
+    pub(crate) c_ep: OwnedEpFid,
+    #[cfg(feature = "threading-completion")]
+    pub(crate) c_ep: EpCompletionOwnedTypedFid<EpRawFid>,
+    pub(crate) cq: MyOnceCell<MyRc<CQ>>,
+    pub(crate) _domain_rc: MyRc<dyn DomainImplT>,
+    pub(crate) iphantom: PhantomData<fn() -> I>,
+}
+
+/// A receive context that can be shared among multiple endpoints of the same domain.
+///
+/// Receive buffers posted to a [SharedRxContextBase] may be consumed by messages arriving at any
+/// of the endpoints bound to it. Completions are reported to the receive [CompletionQueue](crate::cq::CompletionQueue)
+/// of the endpoint on which the message arrived, so all endpoints sharing a context must use the same one.
+///
+/// Both the connectionless (e.g., `recv_from`) and the connected (e.g., `recv`) receive operations are
+/// available, the latter being the ones to use when the context is shared by connected endpoints.
+///
+/// Corresponds to an `fid_ep` created with `fi_srx_context`.
+pub struct SharedRxContextBase<I, CQ: ?Sized> {
+    pub(crate) inner: MyRc<SharedRxContextImplBase<I, CQ>>,
+}
+
+/// Represents a shared context for receiving data.
+pub type SharedRxContext<I> = SharedRxContextBase<I, dyn ReadCq>;
+
+pub(crate) trait SharedRxContextImplT: AsTypedFid<EpRawFid> + SyncSend {}
+
+impl<I, CQ: ?Sized + ReadCq> SharedRxContextImplT for SharedRxContextImplBase<I, CQ> {}
+impl<I, CQ: ?Sized + ReadCq> SyncSend for SharedRxContextImplBase<I, CQ> {}
+impl<I, CQ: ?Sized + ReadCq> SyncSend for SharedRxContextBase<I, CQ> {}
+
+// A shared context may serve connectionless endpoints, which receive from any or selected sources,
+// as well as connected ones, which receive from their peer.
+impl<I, CQ: ?Sized> ConnlessEp for SharedRxContextBase<I, CQ> {}
+impl<I, CQ: ?Sized> ConnectedEp for SharedRxContextBase<I, CQ> {}
+
+impl<I, CQ: ?Sized> SharedRxContextImplBase<I, CQ> {
+    pub(crate) fn new<EQ: ?Sized + 'static + SyncSend>(
+        domain: &MyRc<DomainImplBase<EQ>>,
+        attr: RxAttr,
+        context: *mut std::ffi::c_void,
+    ) -> Result<Self, crate::error::Error> {
+        let c_ep = domain.srx_context(attr, context)?;
+
+        Ok(Self {
+            #[cfg(not(any(feature = "threading-domain", feature = "threading-completion")))]
+            c_ep: OwnedEpFid::from(c_ep),
+            #[cfg(feature = "threading-domain")]
+            c_ep: OwnedEpFid::from(c_ep, domain.c_domain.domain.clone()),
+            #[cfg(feature = "threading-completion")]
+            c_ep: EpCompletionOwnedTypedFid::from(c_ep),
+            cq: MyOnceCell::new(),
+            _domain_rc: domain.clone(),
+            iphantom: PhantomData,
+        })
+    }
+}
+
+impl<I, CQ: ?Sized> SharedRxContextBase<I, CQ> {
+    pub(crate) fn new<EQ: ?Sized + 'static + SyncSend>(
+        domain: &DomainBase<EQ>,
+        attr: RxAttr,
+        context: Option<&mut Context>,
+    ) -> Result<Self, crate::error::Error> {
+        let c_void = match context {
+            Some(ctx) => ctx.inner_mut(),
+            None => std::ptr::null_mut(),
+        };
+
+        Ok(Self {
+            inner: MyRc::new(SharedRxContextImplBase::new(&domain.inner, attr, c_void)?),
+        })
+    }
+}
+
+impl<I, CQ: ?Sized> AsTypedFid<EpRawFid> for SharedRxContextImplBase<I, CQ> {
+    fn as_typed_fid(&self) -> BorrowedTypedFid<'_, EpRawFid> {
+        self.c_ep.as_typed_fid()
+    }
+
+    fn as_typed_fid_mut(&self) -> crate::fid::MutBorrowedTypedFid<'_, EpRawFid> {
+        self.c_ep.as_typed_fid_mut()
+    }
+}
+
+impl<I, CQ: ?Sized> AsTypedFid<EpRawFid> for SharedRxContextBase<I, CQ> {
+    fn as_typed_fid(&self) -> BorrowedTypedFid<'_, EpRawFid> {
+        self.inner.as_typed_fid()
+    }
+
+    fn as_typed_fid_mut(&self) -> crate::fid::MutBorrowedTypedFid<'_, EpRawFid> {
+        self.inner.as_typed_fid_mut()
+    }
+}
+
+impl<I, CQ: ?Sized + ReadCq> ActiveEndpoint for SharedRxContextImplBase<I, CQ> {
+    fn fid(&self) -> &OwnedEpFid {
+        #[cfg(feature = "threading-completion")]
+        return &self.c_ep.typed_fid;
+        #[cfg(not(feature = "threading-completion"))]
+        return &self.c_ep;
+    }
+}
+
+impl<I, CQ: ?Sized + ReadCq> ActiveEndpoint for SharedRxContextBase<I, CQ> {
+    fn fid(&self) -> &OwnedEpFid {
+        self.inner.fid()
+    }
+}
+
+//================== SharedRxContext Builder ==================//
+pub struct SharedRxContextBuilder<'a, I> {
+    pub(crate) rx_attr: RxAttr,
+    pub(crate) ctx: Option<&'a mut Context>,
+    iphantom: PhantomData<fn() -> I>,
+}
+
+impl<'a> SharedRxContextBuilder<'a, ()> {
+    /// Initiates the creation of a new [SharedRxContext] using the receive attributes of `info`
+    /// as defaults.
+    pub fn new<I>(info: &InfoEntry<I>) -> SharedRxContextBuilder<'a, I> {
+        SharedRxContextBuilder::<I> {
+            rx_attr: info.rx_attr().clone(),
+            ctx: None,
+            iphantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, I> SharedRxContextBuilder<'a, I> {
+    /// Sets the mode for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::mode`.
+    pub fn mode(mut self, mode: crate::enums::Mode) -> Self {
+        self.rx_attr.set_mode(mode);
+        self
+    }
+
+    /// Sets the message order for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::msg_order`.
+    pub fn msg_order(mut self, msg_order: MsgOrder) -> Self {
+        self.rx_attr.set_msg_order(msg_order);
+        self
+    }
+
+    /// Sets the completion order for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::comp_order`.
+    pub fn comp_order(mut self, comp_order: RxCompOrder) -> Self {
+        self.rx_attr.set_comp_order(comp_order);
+        self
+    }
+
+    /// Sets the total buffered receive size for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::total_buffered_recv`.
+    pub fn total_buffered_recv(mut self, total_buffered_recv: usize) -> Self {
+        self.rx_attr.set_total_buffered_recv(total_buffered_recv);
+        self
+    }
+
+    /// Sets the number of receive operations that can be posted to the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::size`.
+    pub fn size(mut self, size: usize) -> Self {
+        self.rx_attr.set_size(size);
+        self
+    }
+
+    /// Sets the I/O vector limit for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::iov_limit`.
+    pub fn iov_limit(mut self, iov_limit: usize) -> Self {
+        self.rx_attr.set_iov_limit(iov_limit);
+        self
+    }
+
+    /// Sets the receive options for the shared receive context.
+    ///
+    /// Corresponds to `fi_rx_attr::op_flags`.
+    pub fn set_receive_options(mut self, ops: TransferOptions) -> Self {
+        ops.recv();
+        self.rx_attr.set_op_flags(ops);
+        self
+    }
+
+    /// Sets the context for the shared receive context.
+    ///
+    /// Corresponds to the context passed to `fi_srx_context`.
+    pub fn context(self, ctx: &'a mut Context) -> SharedRxContextBuilder<'a, I> {
+        SharedRxContextBuilder {
+            rx_attr: self.rx_attr,
+            ctx: Some(ctx),
+            iphantom: PhantomData,
+        }
+    }
+
+    /// Builds the shared receive context.
+    ///
+    /// Corresponds to calling `fi_srx_context`.
+    pub fn build<EQ: ?Sized + 'static + SyncSend>(
+        self,
+        domain: &DomainBase<EQ>,
+    ) -> Result<SharedRxContext<I>, crate::error::Error> {
+        SharedRxContext::new(domain, self.rx_attr, self.ctx)
+    }
+}
+
 pub struct TxIncompleteBindCq<'a, I, STATE: EpState> {
     pub(crate) ep: &'a TxContextImpl<I, STATE>,
     pub(crate) flags: u64,
@@ -0,0 +1,148 @@
+use libfabric::{
+    av::{AddressVectorBuilder, AvInAddress},
+    comm::message::{RecvEp, SendEp},
+    cq::{CompletionQueueBuilder, ReadCq},
+    domain::DomainBuilder,
+    enums::{AVOptions, EndpointType, TransferOptions},
+    error::ErrorKind,
+    ep::{ActiveEndpoint, BaseEndpoint, Endpoint, EndpointBuilder},
+    fabric::FabricBuilder,
+    info::Info,
+    infocapsoptions::InfoCaps,
+};
+
+#[test]
+fn alias_with_inject() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = info.into_iter().next().unwrap();
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+
+    let ep = match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &cq, false)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => ep,
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    };
+    let ep = ep.enable(&av).unwrap();
+
+    let alias = match ep.alias(TransferOptions::new().inject()) {
+        Ok(alias) => alias,
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::NotImplemented | ErrorKind::NotSupported
+            ) =>
+        {
+            return
+        } // Provider does not support aliasing
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(alias.transmit_options().unwrap().is_inject());
+
+    drop(ep); // The alias keeps the original endpoint alive
+    drop(alias);
+}
+
+#[test]
+fn receive_alias() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = match info
+        .into_iter()
+        .find(|entry| !entry.domain_attr().mr_mode().is_local())
+    {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+
+    let ep = match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &cq, false)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => ep,
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    };
+    let ep = ep.enable(&av).unwrap();
+
+    let alias = match ep.receive_alias(TransferOptions::new().completion()) {
+        Ok(alias) => alias,
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::NotImplemented | ErrorKind::NotSupported
+            ) =>
+        {
+            return
+        } // Provider does not support aliasing
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(alias.receive_options().unwrap().is_completion());
+
+    let dest = av
+        .insert(
+            AvInAddress::Encoded(&[ep.getname().unwrap()]),
+            AVOptions::new(),
+        )
+        .unwrap()
+        .pop()
+        .unwrap()
+        .unwrap();
+
+    // The message sent by the original endpoint lands in the buffer posted through the alias
+    let mut recv_buf = vec![0u8; 256];
+    alias.recv_from_any(&mut recv_buf, None).unwrap();
+    let send_buf: Vec<u8> = (0..256).map(|v| v as u8).collect();
+    loop {
+        match ep.send_to(&send_buf, None, &dest) {
+            Ok(()) => break,
+            Err(err) if matches!(err.kind, ErrorKind::TryAgain) => {
+                let _ = cq.read(0);
+            }
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+
+    let mut completed = 0;
+    while completed < 2 {
+        match cq.read(1) {
+            Ok(_) => completed += 1,
+            Err(err) if matches!(err.kind, ErrorKind::TryAgain) => {}
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+    assert_eq!(recv_buf, send_buf);
+
+    drop(ep); // The alias keeps the original endpoint alive
+    drop(alias);
+}
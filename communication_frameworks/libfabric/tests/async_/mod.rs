@@ -291,7 +291,7 @@ impl<I: MsgDefaultCap + Caps + 'static> Ofi<I> {
                             .unwrap()
                             .1
                             .into_iter()
-                            .map(|x| MyRc::new(x))
+                            .map(|x| MyRc::new(x.unwrap()))
                             .collect();
 
                         let epname = ep.getname().unwrap();
@@ -349,7 +349,7 @@ impl<I: MsgDefaultCap + Caps + 'static> Ofi<I> {
                             .unwrap()
                             .1
                             .into_iter()
-                            .map(|x| MyRc::new(x))
+                            .map(|x| MyRc::new(x.unwrap()))
                             .collect();
 
                         async_std::task::block_on(ep.send_to_async(
@@ -471,7 +471,7 @@ impl<I: MsgDefaultCap + Caps + 'static> Ofi<I> {
 
         // self.wait_rx(1);
 
-        let mem_info = unsafe { MemAddressInfo::from_bytes(&self.reg_mem.borrow()[mem_bytes.len()..2*mem_bytes.len()]) };
+        let mem_info = MemAddressInfo::try_from_bytes(&self.reg_mem.borrow()[mem_bytes.len()..2*mem_bytes.len()]).unwrap();
         let remote_mem_info = mem_info.into_remote_info(&self.domain).unwrap();
         println!("Remote addr: {:?}, size: {}", remote_mem_info.mem_address().as_ptr(), remote_mem_info.mem_len());
 
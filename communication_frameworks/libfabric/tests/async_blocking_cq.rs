@@ -0,0 +1,63 @@
+#[cfg(all(feature = "use-async-std", not(feature = "async-cqs-spin")))]
+pub mod async_loopback;
+
+#[cfg(test)]
+#[cfg(all(feature = "use-async-std", not(feature = "async-cqs-spin")))]
+pub mod async_blocking_cq {
+    use std::future::Future;
+    use std::task::Poll;
+
+    use crate::async_loopback::{query, Loopback};
+    use libfabric::async_::comm::message::{AsyncRecvEpOwned, AsyncSendEpOwned};
+
+    // The default builder waits on the queue's file descriptor, the receive must be woken up by it
+    #[test]
+    fn recv_wakes_up_on_fd() {
+        let info_entry = match query(&["tcp", "shm", "sockets"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::new(info_entry);
+
+        let mut recv = Box::pin(
+            lb.ep
+                .recv_from_any_owned(vec![0u8; 256], lb.info_entry.allocate_context()),
+        );
+        let mut send = Box::pin(async {
+            // Give the receive time to block on the queue
+            async_std::task::sleep(std::time::Duration::from_millis(50)).await;
+            lb.ep
+                .send_to_owned(
+                    (0..256).map(|v| v as u8).collect::<Vec<_>>(),
+                    &lb.self_addr,
+                    lb.info_entry.allocate_context(),
+                )
+                .await
+        });
+
+        let (mut received, mut sent) = (None, None);
+        async_std::task::block_on(std::future::poll_fn(|cx| {
+            if received.is_none() {
+                if let Poll::Ready(res) = recv.as_mut().poll(cx) {
+                    received = Some(res);
+                }
+            }
+            if sent.is_none() {
+                if let Poll::Ready(res) = send.as_mut().poll(cx) {
+                    sent = Some(res);
+                }
+            }
+            if received.is_some() && sent.is_some() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }));
+
+        let (res, sent, _) = sent.unwrap();
+        res.unwrap();
+        let (res, received, _) = received.unwrap();
+        res.unwrap();
+        assert_eq!(sent, received);
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod async_loopback;
+
+#[cfg(test)]
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod async_cancel {
+    use std::future::Future;
+    use std::task::Poll;
+
+    use crate::async_loopback::{noop_waker, query, Loopback};
+    use libfabric::async_::comm::message::AsyncRecvEp;
+    use libfabric::async_::cq::AsyncWaitCq;
+
+    #[test]
+    fn drop_recv_mid_flight() {
+        // Only providers known to support canceling posted receives
+        let info_entry = match query(&["tcp", "shm", "sockets"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::new(info_entry);
+
+        let waker = noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut ctx = lb.info_entry.allocate_context();
+
+        // Nobody ever sends to this endpoint, so each receive stays in flight until its future is dropped
+        for _ in 0..2 {
+            let mut buf = vec![0u8; 64];
+            {
+                let fut = lb.ep.recv_from_any_async(&mut buf, None, &mut ctx);
+                let mut fut = std::pin::pin!(fut);
+                assert!(matches!(fut.as_mut().poll(&mut cx), Poll::Pending));
+            }
+            // The receive has been canceled and its completion drained, so the buffer can be released
+            // and the context reused
+            drop(buf);
+        }
+
+        lb.cq.progress().unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+// Fixtures shared by the tests driving asynchronous operations on an endpoint that talks to itself
+#![allow(dead_code)]
+
+use std::future::Future;
+use std::task::{RawWaker, RawWakerVTable, Waker};
+
+use libfabric::async_::av::{AddressVector, AddressVectorBuilder};
+use libfabric::async_::connless_ep::ConnectionlessEndpoint;
+use libfabric::async_::cq::{AsyncCompletionQueueImpl, CompletionQueue, CompletionQueueBuilder};
+use libfabric::async_::ctx_pool::ContextPool;
+use libfabric::async_::ep::{Endpoint, EndpointBuilder};
+use libfabric::async_::eq::{AsyncEventQueueImpl, EventQueue, EventQueueBuilder};
+use libfabric::domain::{Domain, DomainBuilder};
+use libfabric::enums::{AVOptions, EndpointType};
+use libfabric::ep::BaseEndpoint;
+use libfabric::fabric::{Fabric, FabricBuilder};
+use libfabric::info::{Info, InfoEntry};
+use libfabric::infocapsoptions::{InfoCaps, MsgDefaultCap, RmaDefaultCap};
+use libfabric::MappedAddress;
+
+/// A waker that does nothing, to poll futures by hand
+pub fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+#[cfg(feature = "use-async-std")]
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    async_std::task::block_on(fut)
+}
+
+#[cfg(all(feature = "use-tokio", not(feature = "use-async-std")))]
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(fut)
+}
+
+/// Returns an RDM entry of the first of `providers` that is available and does not require
+/// local memory registration.
+pub fn query(providers: &[&str]) -> Option<InfoEntry<impl MsgDefaultCap>> {
+    providers.iter().find_map(|prov_name| {
+        Info::new(&libfabric::info::libfabric_version())
+            .enter_hints()
+            .enter_ep_attr()
+            .type_(EndpointType::Rdm)
+            .leave_ep_attr()
+            .enter_fabric_attr()
+            .prov_name(prov_name)
+            .leave_fab_attr()
+            .caps(InfoCaps::new().msg())
+            .leave_hints()
+            .get()
+            .ok()?
+            .into_iter()
+            .find(|entry| !entry.domain_attr().mr_mode().is_local())
+    })
+}
+
+/// Same as [query] but the entry also supports RMA operations on memory regions that do not have to be
+/// bound to an endpoint.
+pub fn query_rma(providers: &[&str]) -> Option<InfoEntry<impl MsgDefaultCap + RmaDefaultCap>> {
+    providers.iter().find_map(|prov_name| {
+        Info::new(&libfabric::info::libfabric_version())
+            .enter_hints()
+            .enter_ep_attr()
+            .type_(EndpointType::Rdm)
+            .leave_ep_attr()
+            .enter_fabric_attr()
+            .prov_name(prov_name)
+            .leave_fab_attr()
+            .caps(InfoCaps::new().msg().rma())
+            .leave_hints()
+            .get()
+            .ok()?
+            .into_iter()
+            .find(|entry| {
+                let mr_mode = entry.domain_attr().mr_mode();
+                !mr_mode.is_local() && !mr_mode.is_endpoint()
+            })
+    })
+}
+
+/// An enabled endpoint whose own address has been inserted in its address vector.
+pub struct Loopback<I> {
+    pub ep: ConnectionlessEndpoint<I>,
+    pub self_addr: MappedAddress,
+    pub av: AddressVector,
+    pub cq: CompletionQueue<AsyncCompletionQueueImpl>,
+    pub eq: EventQueue<AsyncEventQueueImpl<false>>,
+    pub domain: Domain,
+    pub fabric: Fabric,
+    pub info_entry: InfoEntry<I>,
+}
+
+impl<I: MsgDefaultCap + 'static> Loopback<I> {
+    pub fn new(info_entry: InfoEntry<I>) -> Self {
+        Self::build(info_entry, None)
+    }
+
+    /// Same as [Loopback::new] but the completion queue holds a [ContextPool] of `capacity` contexts.
+    pub fn with_context_pool(info_entry: InfoEntry<I>, capacity: usize) -> Self {
+        Self::build(info_entry, Some(capacity))
+    }
+
+    fn build(info_entry: InfoEntry<I>, pool_capacity: Option<usize>) -> Self {
+        let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+        let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+        let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+        let cq_builder = CompletionQueueBuilder::new()
+            .size(info_entry.rx_attr().size() + info_entry.tx_attr().size());
+        let cq = match pool_capacity {
+            Some(capacity) => cq_builder.context_pool(ContextPool::new(&info_entry, capacity)),
+            None => cq_builder,
+        }
+        .build(&domain)
+        .unwrap();
+        let av = AddressVectorBuilder::new(&eq).build(&domain).unwrap();
+        let ep = match EndpointBuilder::new(&info_entry)
+            .build_with_shared_cq(&domain, &cq)
+            .unwrap()
+        {
+            Endpoint::Connectionless(ep) => ep,
+            Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+        };
+        ep.bind_eq(&eq).unwrap();
+        let ep = ep.enable(&av).unwrap();
+
+        let mut av_ctx = info_entry.allocate_context();
+        let self_addr =
+            block_on(av.insert_async(&[ep.getname().unwrap()], AVOptions::new(), &mut av_ctx))
+                .unwrap()
+                .1
+                .pop()
+                .unwrap()
+                .unwrap();
+
+        Self {
+            ep,
+            self_addr,
+            av,
+            cq,
+            eq,
+            domain,
+            fabric,
+            info_entry,
+        }
+    }
+}
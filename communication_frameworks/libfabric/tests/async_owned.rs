@@ -0,0 +1,131 @@
+#[cfg(feature = "use-async-std")]
+pub mod async_loopback;
+
+#[cfg(test)]
+#[cfg(feature = "use-async-std")]
+pub mod async_owned {
+    use std::future::Future;
+    use std::task::Poll;
+
+    use crate::async_loopback::{noop_waker, query, query_rma, Loopback};
+    use libfabric::async_::buf::RegisteredBuf;
+    use libfabric::async_::comm::message::{AsyncRecvEpOwned, AsyncSendEpOwned};
+    use libfabric::async_::comm::rma::AsyncReadEpOwned;
+    use libfabric::enums::HmemIface;
+    use libfabric::mr::{MappedMemoryRegionKey, MaybeDisabledMemoryRegion, MemoryRegionBuilder};
+    use libfabric::RemoteMemoryAddress;
+
+    fn assert_static<F: Future + 'static>(fut: F) -> F {
+        fut
+    }
+
+    #[test]
+    fn sendrecv_owned_to_self() {
+        let info_entry = match query(&["tcp", "shm", "sockets"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::new(info_entry);
+
+        // Neither future borrows the buffers or the contexts
+        let recv = assert_static(
+            lb.ep
+                .recv_from_any_owned(vec![0u8; 256], lb.info_entry.allocate_context()),
+        );
+        let send = assert_static(lb.ep.send_to_owned(
+            (0..256).map(|v| v as u8).collect::<Vec<_>>(),
+            &lb.self_addr,
+            lb.info_entry.allocate_context(),
+        ));
+
+        // Post the receive before the matching send
+        let mut recv = Box::pin(recv);
+        let waker = noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        if let Poll::Ready((res, _, _)) = recv.as_mut().poll(&mut cx) {
+            panic!("Receive completed before the send: {:?}", res.err());
+        }
+
+        let (res, sent, _) = async_std::task::block_on(send);
+        res.unwrap();
+        let (res, received, _) = async_std::task::block_on(recv);
+        res.unwrap();
+        assert_eq!(sent, received);
+    }
+
+    #[test]
+    fn read_owned_dropped_before_completion() {
+        let info_entry = match query_rma(&["tcp", "shm", "sockets"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::new(info_entry);
+
+        let target: Vec<u8> = (0..256).map(|v| v as u8).collect();
+        let target_mr = match MemoryRegionBuilder::new(&target, HmemIface::System)
+            .access_remote_read()
+            .build(&lb.domain)
+            .unwrap()
+        {
+            MaybeDisabledMemoryRegion::Enabled(mr) => mr,
+            MaybeDisabledMemoryRegion::Disabled(_) => panic!("Unexpected disabled memory region"),
+        };
+        let mr_mode = lb.info_entry.domain_attr().mr_mode();
+        let mem_addr = if mr_mode.is_basic() || mr_mode.is_virt_addr() {
+            RemoteMemoryAddress::from_raw(target.as_ptr())
+        } else {
+            RemoteMemoryAddress::from_raw(std::ptr::null())
+        };
+        let key = unsafe {
+            MappedMemoryRegionKey::from_raw(&target_mr.key().unwrap().to_bytes(), &lb.domain)
+        }
+        .unwrap();
+
+        let registered = |buf: Vec<u8>| {
+            let mr = match MemoryRegionBuilder::new(&buf, HmemIface::System)
+                .access_read()
+                .build(&lb.domain)
+                .unwrap()
+            {
+                MaybeDisabledMemoryRegion::Enabled(mr) => mr,
+                MaybeDisabledMemoryRegion::Disabled(_) => {
+                    panic!("Unexpected disabled memory region")
+                }
+            };
+            RegisteredBuf::new(buf, mr)
+        };
+
+        let waker = noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+
+        // Whether the read is still in flight or has already completed when its future is dropped,
+        // the drop settles it before the buffer is released
+        for _ in 0..2 {
+            let read = unsafe {
+                lb.ep.read_from_owned(
+                    registered(vec![0u8; 256]),
+                    &lb.self_addr,
+                    mem_addr,
+                    &key,
+                    lb.info_entry.allocate_context(),
+                )
+            };
+            let mut read = Box::pin(read);
+            let _ = read.as_mut().poll(&mut cx);
+        }
+
+        // The completions of the dropped reads do not leak into the next operation
+        let read = unsafe {
+            lb.ep.read_from_owned(
+                registered(vec![0u8; 256]),
+                &lb.self_addr,
+                mem_addr,
+                &key,
+                lb.info_entry.allocate_context(),
+            )
+        };
+        let (res, read_buf, _) = async_std::task::block_on(read);
+        res.unwrap();
+        assert_eq!(read_buf.buf(), &target);
+    }
+}
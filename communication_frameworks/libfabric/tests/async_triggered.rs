@@ -0,0 +1,70 @@
+#[cfg(feature = "use-async-std")]
+pub mod async_loopback;
+
+#[cfg(test)]
+#[cfg(feature = "use-async-std")]
+pub mod async_triggered {
+    use std::future::Future;
+    use std::task::Poll;
+
+    use crate::async_loopback::{noop_waker, query, Loopback};
+    use libfabric::async_::comm::message::{AsyncRecvEp, AsyncSendEp};
+    use libfabric::cntr::{CounterBuilder, ReadCntr};
+    use libfabric::enums::TriggerEvent;
+    use libfabric::error::ErrorKind;
+    use libfabric::trigger::TriggerThreshold;
+
+    #[test]
+    fn send_deferred_until_threshold() {
+        let info_entry = match query(&["sockets", "shm", "tcp"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::new(info_entry);
+        let cntr = CounterBuilder::new().build(&lb.domain).unwrap();
+
+        let sent: Vec<u8> = (0..64).collect();
+        let mut received = vec![0u8; 64];
+        let mut recv_ctx = lb.info_entry.allocate_context();
+        let mut send_ctx = lb.info_entry.allocate_context();
+        let mut event = TriggerEvent::Threshold(TriggerThreshold::new(&cntr, 1));
+
+        let mut recv = Box::pin(
+            lb.ep
+                .recv_from_any_async(&mut received, None, &mut recv_ctx),
+        );
+        let mut send = Box::pin(lb.ep.send_to_triggered_async(
+            &sent,
+            None,
+            &lb.self_addr,
+            &mut event,
+            &mut send_ctx,
+        ));
+
+        let waker = noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        if let Poll::Ready(res) = recv.as_mut().poll(&mut cx) {
+            panic!("Receive completed before the send: {:?}", res.err());
+        }
+        match send.as_mut().poll(&mut cx) {
+            // The provider does not support triggered operations
+            Poll::Ready(Err(err))
+                if matches!(
+                    err.kind,
+                    ErrorKind::NotSupported | ErrorKind::NotImplemented
+                ) =>
+            {
+                return
+            }
+            Poll::Ready(Err(err)) => panic!("{:?}", err),
+            Poll::Ready(Ok(_)) => panic!("Triggered send completed before its threshold"),
+            Poll::Pending => {}
+        }
+
+        // Reaching the threshold releases the deferred send
+        cntr.add(1).unwrap();
+        async_std::task::block_on(send).unwrap();
+        async_std::task::block_on(recv).unwrap();
+        assert_eq!(sent, received);
+    }
+}
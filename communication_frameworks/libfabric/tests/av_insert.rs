@@ -0,0 +1,113 @@
+use libfabric::{
+    av::{AddressVectorBuilder, AvInAddress},
+    cq::CompletionQueueBuilder,
+    domain::DomainBuilder,
+    enums::{AVOptions, EndpointType},
+    ep::{Address, BaseEndpoint, Endpoint, EndpointBuilder},
+    fabric::FabricBuilder,
+    info::{Info, InfoEntry},
+    infocapsoptions::{InfoCaps, MsgDefaultCap},
+};
+
+// Providers that validate the addresses they insert
+fn query() -> Option<InfoEntry<impl MsgDefaultCap>> {
+    ["tcp", "sockets"].iter().find_map(|prov_name| {
+        Info::new(&libfabric::info::libfabric_version())
+            .enter_hints()
+            .enter_ep_attr()
+            .type_(EndpointType::Rdm)
+            .leave_ep_attr()
+            .enter_fabric_attr()
+            .prov_name(prov_name)
+            .leave_fab_attr()
+            .caps(InfoCaps::new().msg())
+            .leave_hints()
+            .get()
+            .ok()?
+            .into_iter()
+            .next()
+    })
+}
+
+// An address of the right size that does not designate any endpoint
+fn malformed(addr: &Address) -> Address {
+    unsafe { Address::from_bytes(&vec![0u8; addr.as_bytes().len()]) }
+}
+
+#[test]
+fn insert_reports_each_address() {
+    let info_entry = match query() {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let ep = match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &cq, false)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => ep,
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    };
+    let ep = ep.enable(&av).unwrap();
+
+    let name = ep.getname().unwrap();
+    let addresses = [name.clone(), malformed(&name), name];
+    let mapped = av
+        .insert(AvInAddress::Encoded(&addresses), AVOptions::new())
+        .unwrap();
+    assert_eq!(mapped.len(), 3);
+    assert!(mapped[0].is_ok());
+    assert!(mapped[1].is_err());
+    assert!(mapped[2].is_ok());
+}
+
+#[cfg(feature = "use-async-std")]
+#[test]
+fn insert_async_reports_each_address() {
+    use libfabric::async_::{
+        av::AddressVectorBuilder,
+        cq::CompletionQueueBuilder,
+        ep::{Endpoint, EndpointBuilder},
+        eq::EventQueueBuilder,
+    };
+
+    let info_entry = match query() {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let av = AddressVectorBuilder::new(&eq).build(&domain).unwrap();
+    let ep = match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &cq)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => ep,
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    };
+    ep.bind_eq(&eq).unwrap();
+    let ep = ep.enable(&av).unwrap();
+
+    let name = ep.getname().unwrap();
+    let addresses = [name.clone(), malformed(&name), name];
+    let mut ctx = info_entry.allocate_context();
+    let (_, mapped) =
+        async_std::task::block_on(av.insert_async(&addresses, AVOptions::new(), &mut ctx)).unwrap();
+    assert_eq!(mapped.len(), 3);
+    assert!(mapped[0].is_ok());
+    assert!(mapped[1].is_err());
+    assert!(mapped[2].is_ok());
+}
@@ -1,7 +1,7 @@
 pub mod sync_;
 #[cfg(test)]
 pub mod sync_collective {
-    use libfabric::{av_set::AddressVectorSetBuilder, comm::collective::CollectiveEp, cq::{ReadCq, WaitCq}, enums::CollectiveOptions, eq::{Event, ReadEq}, infocapsoptions::{CollCap, InfoCaps}, mcast::MultiCastGroup, mr::{MemoryRegion, MemoryRegionBuilder}};
+    use libfabric::{av_set::AddressVectorSetBuilder, comm::collective::{CollectiveEp, CollectiveRequestEp}, cq::{ReadCq, WaitCq}, enums::CollectiveOptions, eq::{Event, ReadEq}, infocapsoptions::{CollCap, InfoCaps}, mcast::MultiCastGroup, mr::{MemoryRegion, MemoryRegionBuilder}};
 
     use crate::sync_::tests::{enable_ep_mr, handshake, handshake_connectionless, MyEndpoint, Ofi};
 
@@ -274,6 +274,59 @@ pub mod sync_collective {
         allreduce(false, "allreduce0", false);
     }
 
+    fn allreduce_request(server: bool, name: &str, connected: bool) {
+        let (ofi, mc) = collective(server, name, connected);
+        let mut reg_mem = ofi.reg_mem.borrow_mut();
+        if server {
+            reg_mem.fill(2);
+        } else {
+            reg_mem.fill(1);
+        };
+
+        let (send_buf, recv_buf) = reg_mem.split_at_mut(1024);
+        let borrow = ofi.mr.borrow();
+        let mr = borrow.as_ref().unwrap();
+        let desc = mr.descriptor();
+        let mut ctx = ofi.info_entry.allocate_context();
+
+        match &ofi.ep {
+            MyEndpoint::Connected(_) => todo!(),
+            MyEndpoint::Connectionless(ep) => {
+                let mut req = ep
+                    .allreduce_request(
+                        send_buf,
+                        Some(&desc),
+                        &mut recv_buf[..1024],
+                        Some(&desc),
+                        &mc,
+                        libfabric::enums::ReduceOp::Sum,
+                        CollectiveOptions::new(),
+                        ofi.cq_type.tx_cq(),
+                        &mut ctx,
+                    )
+                    .unwrap();
+                while !req.test().unwrap() {}
+                assert_eq!(req.result(), vec![3; 1024]);
+
+                let mut ctx = ofi.info_entry.allocate_context();
+                ep.barrier_request(&mc, CollectiveOptions::new(), ofi.cq_type.tx_cq(), &mut ctx)
+                    .unwrap()
+                    .wait()
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn allreduce_request0() {
+        allreduce_request(true, "allreduce_request0", false);
+    }
+
+    #[test]
+    fn allreduce_request1() {
+        allreduce_request(false, "allreduce_request0", false);
+    }
+
     fn allgather(server: bool, name: &str, connected: bool) {
         println!("Start Allgather Collective!");
         let (ofi, mc) = collective(server, name, connected);
@@ -0,0 +1,59 @@
+use libfabric::{
+    cq::{Completion, CompletionEntry, CompletionQueueBuilder, ReadCq, TaggedEntry},
+    domain::DomainBuilder,
+    enums::{CqFormat, EndpointType},
+    error::ErrorKind,
+    fabric::FabricBuilder,
+    info::Info,
+    infocapsoptions::InfoCaps,
+};
+
+#[test]
+fn typed_and_dynamic_formats() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg().tagged())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = info.into_iter().next().unwrap();
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+
+    let typed_cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size())
+        .format_tagged()
+        .build(&domain)
+        .unwrap();
+
+    // The entries of a typed queue are returned directly, without matching on a runtime variant
+    let res: Result<Vec<CompletionEntry<TaggedEntry>>, _> = typed_cq.read(1);
+    match res {
+        Ok(entries) => assert!(entries.is_empty()),
+        Err(err) => assert!(matches!(err.kind, ErrorKind::TryAgain)),
+    }
+
+    // Each entry comes with its own source address
+    match typed_cq.readfrom(8) {
+        Ok(entries) => assert!(entries.is_empty()),
+        Err(err) => assert!(matches!(err.kind, ErrorKind::TryAgain)),
+    }
+
+    // The dynamic path still selects the format at runtime
+    let dyn_cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size())
+        .format(CqFormat::Tagged)
+        .build(&domain)
+        .unwrap();
+
+    let mut completion = Completion::Tagged(Vec::new());
+    match dyn_cq.read_in(1, &mut completion) {
+        Ok(count) => assert_eq!(count, 0),
+        Err(err) => assert!(matches!(err.kind, ErrorKind::TryAgain)),
+    }
+    assert!(matches!(completion, Completion::Tagged(_)));
+}
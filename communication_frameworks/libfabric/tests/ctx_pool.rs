@@ -0,0 +1,79 @@
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod async_loopback;
+
+#[cfg(test)]
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod ctx_pool {
+    use std::future::Future;
+    use std::task::Poll;
+
+    use crate::async_loopback::{block_on, query, Loopback};
+    use libfabric::async_::comm::message::{AsyncRecvEpPooled, AsyncSendEpPooled};
+    use libfabric::error::ErrorKind;
+
+    fn send_recv_pooled(capacity: usize) {
+        let info_entry = match query(&["tcp", "shm", "sockets"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::with_context_pool(info_entry, capacity);
+
+        // Each round reuses the slots freed by the previous one, or overflow contexts if the pool is empty
+        for round in 0..4u8 {
+            let send_buf: Vec<u8> = (0..64).map(|v| v ^ round).collect();
+            let mut recv_buf = vec![0u8; 64];
+            {
+                let mut recv =
+                    std::pin::pin!(lb.ep.recv_from_any_pooled_async(&mut recv_buf, None));
+                let mut send =
+                    std::pin::pin!(lb.ep.send_to_pooled_async(&send_buf, None, &lb.self_addr));
+                let (mut recv_done, mut send_done) = (false, false);
+                block_on(std::future::poll_fn(|cx| {
+                    if !recv_done {
+                        if let Poll::Ready(res) = recv.as_mut().poll(cx) {
+                            res.unwrap();
+                            recv_done = true;
+                        }
+                    }
+                    if !send_done {
+                        if let Poll::Ready(res) = send.as_mut().poll(cx) {
+                            res.unwrap();
+                            send_done = true;
+                        }
+                    }
+                    if recv_done && send_done {
+                        Poll::Ready(())
+                    } else {
+                        Poll::Pending
+                    }
+                }));
+            }
+            assert_eq!(recv_buf, send_buf);
+        }
+    }
+
+    #[test]
+    fn pooled_send_recv() {
+        send_recv_pooled(2);
+    }
+
+    #[test]
+    fn pooled_send_recv_empty_pool() {
+        send_recv_pooled(0);
+    }
+
+    #[test]
+    fn pooled_requires_context_pool() {
+        let info_entry = match query(&["tcp", "shm", "sockets"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::new(info_entry);
+
+        let buf = vec![0u8; 64];
+        match block_on(lb.ep.send_to_pooled_async(&buf, None, &lb.self_addr)) {
+            Err(err) => assert!(matches!(err.kind, ErrorKind::InvalidArgument)),
+            Ok(_) => panic!("Sent with a pooled context without a context pool"),
+        }
+    }
+}
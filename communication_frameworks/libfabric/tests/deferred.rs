@@ -0,0 +1,167 @@
+use libfabric::{
+    av::{AddressVectorBuilder, AvInAddress},
+    cntr::{CounterBuilder, ReadCntr},
+    comm::message::RecvEp,
+    cq::{CompletionQueueBuilder, ReadCq},
+    deferred::{DeferredOp, DeferredWork},
+    domain::DomainBuilder,
+    enums::{AVOptions, EndpointType, SendMsgOptions},
+    ep::{BaseEndpoint, Endpoint, EndpointBuilder},
+    error::ErrorKind,
+    fabric::FabricBuilder,
+    info::Info,
+    infocapsoptions::InfoCaps,
+    iovec::IoVec,
+    msg::Msg,
+};
+
+#[test]
+fn deferred_counter_chain() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = info.into_iter().next().unwrap();
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let trigger = CounterBuilder::new().build(&domain).unwrap();
+    let target = CounterBuilder::new().build(&domain).unwrap();
+    let done = CounterBuilder::new().build(&domain).unwrap();
+
+    let mut queue = domain.deferred_work_queue();
+    let work =
+        DeferredWork::new(&trigger, 2, DeferredOp::cntr_add(&target, 5)).completion_cntr(&done);
+    let id = queue.queue(work).unwrap();
+    assert_eq!(queue.len(), 1);
+
+    if queue.is_offloaded() == Some(true) {
+        queue.cancel(id).unwrap();
+        assert!(queue.is_empty());
+        return;
+    }
+
+    // The threshold has not been reached yet
+    assert_eq!(queue.progress().unwrap(), 0);
+    trigger.add(1).unwrap();
+    assert_eq!(queue.progress().unwrap(), 0);
+    assert_eq!(target.read(), 0);
+
+    trigger.add(1).unwrap();
+    assert_eq!(queue.progress().unwrap(), 1);
+    assert!(queue.is_empty());
+    assert_eq!(target.read(), 5);
+    assert_eq!(done.read(), 1);
+}
+
+#[test]
+fn deferred_send_completes_before_chaining() {
+    let info_entry = match ["tcp", "sockets"].iter().find_map(|prov_name| {
+        Info::new(&libfabric::info::libfabric_version())
+            .enter_hints()
+            .enter_ep_attr()
+            .type_(EndpointType::Rdm)
+            .leave_ep_attr()
+            .enter_fabric_attr()
+            .prov_name(prov_name)
+            .leave_fab_attr()
+            .caps(InfoCaps::new().msg())
+            .leave_hints()
+            .get()
+            .ok()?
+            .into_iter()
+            .find(|entry| !entry.domain_attr().mr_mode().is_local())
+    }) {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let tx_cntr = CounterBuilder::new().build(&domain).unwrap();
+    let ep = match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &cq, false)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => ep,
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    };
+    ep.bind_cntr().send().cntr(&tx_cntr).unwrap();
+    let ep = ep.enable(&av).unwrap();
+    let self_addr = av
+        .insert(
+            AvInAddress::Encoded(&[ep.getname().unwrap()]),
+            AVOptions::new(),
+        )
+        .unwrap()
+        .pop()
+        .unwrap()
+        .unwrap();
+
+    let trigger = CounterBuilder::new().build(&domain).unwrap();
+    let done = CounterBuilder::new().build(&domain).unwrap();
+    let chained = CounterBuilder::new().build(&domain).unwrap();
+
+    let mut recv_buf = vec![0u8; 64];
+    ep.recv_from_any(&mut recv_buf, None).unwrap();
+    let send_buf: Vec<u8> = (0..64).collect();
+    let iov = IoVec::from_slice(&send_buf);
+    let mut ctx = info_entry.allocate_context();
+    let msg = Msg::from_iov(&iov, None, &self_addr, None, &mut ctx);
+
+    let mut queue = domain.deferred_work_queue();
+    let send = DeferredWork::new(
+        &trigger,
+        1,
+        DeferredOp::send(&ep, &msg, SendMsgOptions::new()),
+    )
+    .completion_cntr(&done)
+    .op_cntr(&tx_cntr);
+    let send_id = queue.queue(send).unwrap();
+    let chain = DeferredWork::new(&done, 1, DeferredOp::cntr_add(&chained, 1));
+    let chain_id = queue.queue(chain).unwrap();
+
+    if queue.is_offloaded() == Some(true) {
+        queue.cancel(chain_id).unwrap();
+        queue.cancel(send_id).unwrap();
+        return;
+    }
+
+    // Without an operation counter the queue cannot tell when the send completes
+    let untracked = DeferredWork::new(
+        &trigger,
+        1,
+        DeferredOp::send(&ep, &msg, SendMsgOptions::new()),
+    )
+    .completion_cntr(&done);
+    assert!(matches!(
+        queue.queue(untracked).unwrap_err().kind,
+        ErrorKind::InvalidArgument
+    ));
+
+    trigger.add(1).unwrap();
+    let mut completions = 0;
+    while completions < 2 || !queue.is_empty() {
+        queue.progress().unwrap();
+        // The chained work only runs once the send has completed
+        assert!(chained.read() <= done.read());
+        if cq.read(1).is_ok() {
+            completions += 1;
+        }
+    }
+
+    assert_eq!(tx_cntr.read(), 1);
+    assert_eq!(done.read(), 1);
+    assert_eq!(chained.read(), 1);
+    assert_eq!(recv_buf, send_buf);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "testing")]
+
+use libfabric::{
+    comm::message::{ConnectedRecvEp, ConnectedSendEp, RecvEp, SendEp},
+    cq::WaitCq,
+    enums::EndpointType,
+    infocapsoptions::{InfoCaps, MsgDefaultCap},
+    testing::{LoopbackBuilder, LoopbackEndpoint, LoopbackPeer},
+};
+
+fn send<I: MsgDefaultCap + 'static>(peer: &LoopbackPeer<I>, len: usize) {
+    let desc = peer.mr.as_ref().map(|mr| mr.descriptor());
+    match &peer.ep {
+        LoopbackEndpoint::Connected(ep) => ep.send(&peer.buf()[..len], desc).unwrap(),
+        LoopbackEndpoint::Connectionless(ep) => ep
+            .send_to(&peer.buf()[..len], desc, peer.peer_addr.as_ref().unwrap())
+            .unwrap(),
+    }
+}
+
+fn recv<I: MsgDefaultCap + 'static>(peer: &mut LoopbackPeer<I>, len: usize) {
+    let peer_addr = peer.peer_addr.clone();
+    let (ep, buf, desc) = peer.split_mut();
+    match ep {
+        LoopbackEndpoint::Connected(ep) => ep.recv(&mut buf[..len], desc).unwrap(),
+        LoopbackEndpoint::Connectionless(ep) => ep
+            .recv_from(&mut buf[..len], desc, peer_addr.as_ref().unwrap())
+            .unwrap(),
+    }
+}
+
+fn pingpong(ep_type: EndpointType) {
+    let mut pair = LoopbackBuilder::new(InfoCaps::new().msg(), ep_type)
+        .build()
+        .unwrap();
+
+    pair.client.buf_mut()[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    recv(&mut pair.server, 8);
+    send(&pair.client, 8);
+    pair.client.cq.sread(1, -1).unwrap();
+    pair.server.cq.sread(1, -1).unwrap();
+    assert_eq!(pair.server.buf()[..8], [1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn loopback_msg() {
+    pingpong(EndpointType::Msg);
+}
+
+#[test]
+fn loopback_rdm() {
+    pingpong(EndpointType::Rdm);
+}
@@ -0,0 +1,155 @@
+use libfabric::{
+    av::AddressVectorBuilder,
+    cq::{CompletionQueueBuilder, ReadCq},
+    domain::DomainBuilder,
+    enums::{EndpointType, JoinOptions},
+    ep::{Address, BaseEndpoint, Endpoint, EndpointBuilder},
+    eq::{Event, EventQueueBuilder, ReadEq},
+    error::ErrorKind,
+    fabric::FabricBuilder,
+    info::{Info, InfoEntry},
+    infocapsoptions::{InfoCaps, McastCap},
+    mcast::MulticastGroupBuilder,
+};
+
+fn query() -> Option<InfoEntry<impl McastCap>> {
+    Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Dgram)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg().mcast())
+        .leave_hints()
+        .get()
+        .ok()?
+        .into_iter()
+        .next()
+}
+
+// An IPv4 multicast address with the same format as `name`, if it is a sockaddr_in
+fn multicast_addr(name: &Address) -> Option<Address> {
+    let mut bytes = name.as_bytes().to_vec();
+    if bytes.len() != 16 || u16::from_ne_bytes([bytes[0], bytes[1]]) != 2 {
+        return None;
+    }
+    bytes[4..8].copy_from_slice(&[239, 255, 0, 1]);
+    Some(unsafe { Address::from_bytes(&bytes) })
+}
+
+#[test]
+fn join_point_to_point() {
+    let info_entry = match query() {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let ep = match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &cq, false)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => ep,
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    };
+    ep.bind_eq(&eq).unwrap();
+    let ep = ep.enable(&av).unwrap();
+
+    let mc_addr = match multicast_addr(&ep.getname().unwrap()) {
+        Some(addr) => addr,
+        None => return,
+    };
+    let waiting = match MulticastGroupBuilder::from_addr(&mc_addr)
+        .build()
+        .join(&ep, JoinOptions::new().send().receive())
+    {
+        Ok(waiting) => waiting,
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::NotSupported | ErrorKind::NotImplemented
+            ) =>
+        {
+            return
+        }
+        Err(err) => panic!("{:?}", err),
+    };
+
+    let event = loop {
+        match eq.read() {
+            Ok(Event::JoinComplete(event)) => break event,
+            Ok(_) => panic!("Unexpected event"),
+            Err(err) if matches!(err.kind, ErrorKind::TryAgain) => {
+                let _ = cq.read(0);
+            }
+            Err(err) => panic!("{:?}", err),
+        }
+    };
+    let group = waiting.join_complete(event);
+
+    // Joining through an established group reuses its address
+    let rejoined = MulticastGroupBuilder::from_multicast_group(&group)
+        .build()
+        .join(&ep, JoinOptions::new().receive());
+    assert!(rejoined.is_ok());
+}
+
+#[cfg(feature = "use-async-std")]
+#[test]
+fn join_point_to_point_async() {
+    use libfabric::async_::{
+        av::AddressVectorBuilder,
+        cq::CompletionQueueBuilder,
+        ep::{Endpoint, EndpointBuilder},
+        eq::EventQueueBuilder,
+    };
+
+    let info_entry = match query() {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let av = AddressVectorBuilder::new(&eq).build(&domain).unwrap();
+    let ep = match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &cq)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => ep,
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    };
+    ep.bind_eq(&eq).unwrap();
+    let ep = ep.enable(&av).unwrap();
+
+    let mc_addr = match multicast_addr(&ep.getname().unwrap()) {
+        Some(addr) => addr,
+        None => return,
+    };
+    let mut ctx = info_entry.allocate_context();
+    let res = async_std::task::block_on(
+        MulticastGroupBuilder::from_addr(&mc_addr)
+            .build()
+            .join_async(&ep, JoinOptions::new().send().receive(), &mut ctx),
+    );
+    match res {
+        Ok((event, _group)) => assert!(matches!(event, Event::JoinComplete(_))),
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::NotSupported | ErrorKind::NotImplemented
+            ) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
@@ -0,0 +1,55 @@
+use libfabric::{MemAddressInfo, MemAddressInfoError};
+
+// A non-raw key, encoded as documented by MemAddressInfo
+fn encoded(key: u64, addr: u64, len: u64) -> Vec<u8> {
+    let mut bytes = b"OFMA".to_vec();
+    bytes.extend_from_slice(&[1, 0, 0, 0]);
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&8u32.to_le_bytes());
+    bytes.extend_from_slice(&key.to_le_bytes());
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+    bytes.extend_from_slice(&addr.to_le_bytes());
+    bytes.extend_from_slice(&len.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn mem_address_info_decoding() {
+    let bytes = encoded(0xdead_beef, 0x1000, 4096);
+    let info = MemAddressInfo::try_from_bytes(&bytes).unwrap();
+    assert_eq!(info.to_bytes(), &bytes[..]);
+
+    assert_eq!(
+        MemAddressInfo::try_from_bytes(&bytes[..bytes.len() - 1]).err(),
+        Some(MemAddressInfoError::InvalidLength {
+            expected: bytes.len(),
+            found: bytes.len() - 1
+        })
+    );
+    assert_eq!(
+        MemAddressInfo::try_from_bytes(&bytes[..10]).err(),
+        Some(MemAddressInfoError::InvalidLength {
+            expected: 16,
+            found: 10
+        })
+    );
+    assert_eq!(
+        MemAddressInfo::try_from_bytes(&bytes[1..]).err(),
+        Some(MemAddressInfoError::BadMagic)
+    );
+
+    let mut future = bytes.clone();
+    future[4] = 2;
+    assert_eq!(
+        MemAddressInfo::try_from_bytes(&future).err(),
+        Some(MemAddressInfoError::UnsupportedVersion(2))
+    );
+
+    // Non-raw keys are always 8 bytes long
+    let mut short_key = bytes.clone();
+    short_key[12] = 4;
+    assert_eq!(
+        MemAddressInfo::try_from_bytes(&short_key).err(),
+        Some(MemAddressInfoError::InvalidKeySize(4))
+    );
+}
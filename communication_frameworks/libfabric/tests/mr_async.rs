@@ -0,0 +1,201 @@
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod mr_async {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Poll;
+
+    use libfabric::{
+        async_::{
+            cq::CompletionQueueBuilder,
+            ep::{Endpoint, EndpointBuilder},
+            eq::EventQueueBuilder,
+        },
+        domain::DomainBuilder,
+        enums::HmemIface,
+        error::ErrorKind,
+        fabric::FabricBuilder,
+        info::{Info, InfoEntry},
+        infocapsoptions::{InfoCaps, MsgDefaultCap},
+        mr::{DisabledMemoryRegion, MaybeDisabledMemoryRegion, MemoryRegionBuilder},
+    };
+
+    #[cfg(feature = "use-async-std")]
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        async_std::task::block_on(fut)
+    }
+
+    #[cfg(all(feature = "use-tokio", not(feature = "use-async-std")))]
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(fut)
+    }
+
+    // Regions that do not need to be bound to an endpoint are enabled as soon as they are registered,
+    // the others once bound to one
+    fn query(endpoint_mr: bool) -> Option<InfoEntry<impl MsgDefaultCap>> {
+        Info::new(&libfabric::info::libfabric_version())
+            .enter_hints()
+            .caps(InfoCaps::new().msg().rma())
+            .leave_hints()
+            .get()
+            .ok()?
+            .into_iter()
+            .find(|entry| entry.domain_attr().mr_mode().is_endpoint() == endpoint_mr)
+    }
+
+    #[test]
+    fn mr_reg_async() {
+        let info_entry = match query(false) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+        let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+        let domain = match DomainBuilder::new(&fabric, &info_entry).build_and_bind_async(&eq, true)
+        {
+            Ok(domain) => domain,
+            Err(err)
+                if matches!(
+                    err.kind,
+                    ErrorKind::NotSupported | ErrorKind::NotImplemented
+                ) =>
+            {
+                return
+            }
+            Err(err) => panic!("{:?}", err),
+        };
+
+        // Register all the buffers at once so that each completion has to be routed to its own future
+        let bufs: Vec<Vec<u8>> = (0..4).map(|i| vec![0u8; 1 << (10 + i)]).collect();
+        let mut ctxs: Vec<_> = bufs.iter().map(|_| info_entry.allocate_context()).collect();
+        let mut futs: Vec<Pin<Box<_>>> = bufs
+            .iter()
+            .zip(ctxs.iter_mut())
+            .map(|(buf, ctx)| {
+                Box::pin(
+                    MemoryRegionBuilder::new(buf, HmemIface::System)
+                        .access_send()
+                        .access_recv()
+                        .access_remote_read()
+                        .access_remote_write()
+                        .build_async(&domain, ctx),
+                )
+            })
+            .collect();
+        let mut mrs: Vec<_> = futs.iter().map(|_| None).collect();
+        block_on(std::future::poll_fn(|cx| {
+            for (fut, mr) in futs.iter_mut().zip(mrs.iter_mut()) {
+                if mr.is_none() {
+                    if let Poll::Ready(res) = fut.as_mut().poll(cx) {
+                        *mr = Some(res);
+                    }
+                }
+            }
+            if mrs.iter().all(Option::is_some) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }));
+        drop(futs);
+
+        for (buf, mr) in bufs.iter().zip(mrs) {
+            match mr.unwrap().unwrap() {
+                MaybeDisabledMemoryRegion::Enabled(mr) => {
+                    assert!(mr.key().is_ok());
+                    // Each region is backed by the buffer it was built from
+                    let slice = unsafe { mr.slice(..) };
+                    assert_eq!(slice.as_slice(), &buf[..]);
+                    assert_eq!(slice.as_slice().as_ptr(), buf.as_ptr());
+                }
+                MaybeDisabledMemoryRegion::Disabled(_) => {
+                    panic!("Unexpected disabled memory region")
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mr_reg_async_requires_event_queue() {
+        let info_entry = match query(false) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+        let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+        let domain = DomainBuilder::new(&fabric, &info_entry)
+            .build_and_bind_async(&eq, false)
+            .unwrap();
+
+        let buf = vec![0u8; 1024];
+        let mut ctx = info_entry.allocate_context();
+        let res = block_on(
+            MemoryRegionBuilder::new(&buf, HmemIface::System)
+                .access_send()
+                .access_recv()
+                .build_async(&domain, &mut ctx),
+        );
+        match res {
+            Err(err) => assert!(matches!(err.kind, ErrorKind::NoEventQueue)),
+            Ok(_) => {
+                panic!("Registered memory without an event queue for asynchronous registration")
+            }
+        }
+    }
+
+    #[test]
+    fn mr_reg_async_ep_bind() {
+        let info_entry = match query(true) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+        let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+        let domain = match DomainBuilder::new(&fabric, &info_entry).build_and_bind_async(&eq, true)
+        {
+            Ok(domain) => domain,
+            Err(err)
+                if matches!(
+                    err.kind,
+                    ErrorKind::NotSupported | ErrorKind::NotImplemented
+                ) =>
+            {
+                return
+            }
+            Err(err) => panic!("{:?}", err),
+        };
+        let cq = CompletionQueueBuilder::new().build(&domain).unwrap();
+
+        let buf = vec![0u8; 1024];
+        let mut ctx = info_entry.allocate_context();
+        let mr = match block_on(
+            MemoryRegionBuilder::new(&buf, HmemIface::System)
+                .access_send()
+                .access_recv()
+                .access_remote_read()
+                .access_remote_write()
+                .build_async(&domain, &mut ctx),
+        )
+        .unwrap()
+        {
+            MaybeDisabledMemoryRegion::Disabled(DisabledMemoryRegion::EpBind(mr)) => mr,
+            _ => panic!("Memory region should wait for an endpoint binding"),
+        };
+
+        // Asynchronously registered regions are bound to asynchronous endpoints like the others
+        let mr = match EndpointBuilder::new(&info_entry)
+            .build_with_shared_cq(&domain, &cq)
+            .unwrap()
+        {
+            Endpoint::Connectionless(ep) => mr.enable(&ep).unwrap(),
+            Endpoint::ConnectionOriented(ep) => mr.enable(&ep).unwrap(),
+        };
+        assert!(mr.key().is_ok());
+    }
+}
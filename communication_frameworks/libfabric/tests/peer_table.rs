@@ -0,0 +1,74 @@
+use libfabric::{
+    av::AddressVectorBuilder,
+    cq::CompletionQueueBuilder,
+    domain::DomainBuilder,
+    enums::EndpointType,
+    ep::{BaseEndpoint, Endpoint, EndpointBuilder},
+    fabric::FabricBuilder,
+    info::Info,
+    infocapsoptions::InfoCaps,
+    peer_table::PeerTable,
+};
+
+#[test]
+fn peer_table_join_and_leave() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = info.into_iter().next().unwrap();
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+
+    let eps: Vec<_> = (0..3)
+        .map(|_| {
+            let ep = match EndpointBuilder::new(&info_entry)
+                .build_with_shared_cq(&domain, &cq, false)
+                .unwrap()
+            {
+                Endpoint::Connectionless(ep) => ep,
+                Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+            };
+            ep.enable(&av).unwrap()
+        })
+        .collect();
+    let names: Vec<_> = eps.iter().map(|ep| ep.getname().unwrap()).collect();
+
+    let mut peers = PeerTable::new(&av);
+    assert_eq!(peers.push(&names[0]).unwrap(), 0);
+    assert_eq!(peers.extend(&names[1..]).unwrap(), 1..3);
+    assert_eq!(peers.num_peers(), 3);
+    for id in 0..3 {
+        let addr = peers.get(id).unwrap().clone();
+        assert_eq!(peers.id_of(&addr), Some(id));
+    }
+
+    // Ids are not reused after a peer leaves
+    peers.remove(1).unwrap();
+    assert!(peers.get(1).is_none());
+    assert!(peers.remove(1).is_err());
+    assert_eq!(peers.len(), 3);
+    assert_eq!(peers.num_peers(), 2);
+    assert_eq!(
+        peers.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+        vec![0, 2]
+    );
+
+    // The peer joins again under its former id
+    assert!(peers.insert(0, &names[1]).is_err());
+    peers.insert(1, &names[1]).unwrap();
+    assert_eq!(peers.num_peers(), 3);
+    let addr = peers.get(1).unwrap().clone();
+    assert_eq!(peers.id_of(&addr), Some(1));
+}
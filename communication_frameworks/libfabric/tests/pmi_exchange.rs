@@ -0,0 +1,63 @@
+#[cfg(feature = "pmi")]
+pub mod pmi_exchange {
+    use libfabric::{
+        av::AddressVectorBuilder,
+        cq::CompletionQueueBuilder,
+        domain::DomainBuilder,
+        enums::EndpointType,
+        ep::{Endpoint, EndpointBuilder},
+        fabric::FabricBuilder,
+        info::Info,
+        infocapsoptions::InfoCaps,
+        pmi_exchange::{
+            exchange_addresses, exchange_addresses_with_key,
+            pmi::{Pmi, PmiBuilder},
+        },
+    };
+
+    // Without a launcher, PMI runs as a singleton job holding a single rank
+    #[test]
+    fn exchange_single_rank() {
+        let pmi = match PmiBuilder::init() {
+            Ok(pmi) => pmi,
+            Err(_) => return,
+        };
+        let info_entry = match Info::new(&libfabric::info::libfabric_version())
+            .enter_hints()
+            .enter_ep_attr()
+            .type_(EndpointType::Rdm)
+            .leave_ep_attr()
+            .caps(InfoCaps::new().msg())
+            .leave_hints()
+            .get()
+            .ok()
+            .and_then(|info| info.into_iter().next())
+        {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+        let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+        let av = AddressVectorBuilder::new().build(&domain).unwrap();
+        let cq = CompletionQueueBuilder::new()
+            .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+            .build(&domain)
+            .unwrap();
+        let ep = match EndpointBuilder::new(&info_entry)
+            .build_with_shared_cq(&domain, &cq, false)
+            .unwrap()
+        {
+            Endpoint::Connectionless(ep) => ep,
+            Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+        };
+        let ep = ep.enable(&av).unwrap();
+
+        let addresses = exchange_addresses(&ep, &pmi, &av).unwrap();
+        assert_eq!(addresses.len(), pmi.ranks().len());
+
+        // Another endpoint address can be published under its own key
+        let addresses = exchange_addresses_with_key(&ep, &pmi, &av, "second-ep-addr").unwrap();
+        assert_eq!(addresses.len(), pmi.ranks().len());
+    }
+}
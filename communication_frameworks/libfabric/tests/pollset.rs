@@ -0,0 +1,79 @@
+use libfabric::{
+    cntr::{CounterBuilder, ReadCntr},
+    cq::CompletionQueueBuilder,
+    domain::DomainBuilder,
+    enums::EndpointType,
+    error::ErrorKind,
+    fabric::FabricBuilder,
+    info::Info,
+    infocapsoptions::InfoCaps,
+    sync::PollSetBuilder,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Ready {
+    TxCq,
+    RxCq,
+    Cntr,
+}
+
+#[test]
+fn poll_heterogeneous_set() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = info.into_iter().next().unwrap();
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let tx_cq = CompletionQueueBuilder::new()
+        .size(info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let rx_cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let cntr = CounterBuilder::new().build(&domain).unwrap();
+
+    let pollset = match PollSetBuilder::new().build(&domain) {
+        Ok(pollset) => pollset,
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::NotImplemented | ErrorKind::NotSupported
+            ) =>
+        {
+            return
+        } // Provider does not support poll sets
+        Err(err) => panic!("{:?}", err),
+    };
+
+    pollset.add(&tx_cq, Ready::TxCq).unwrap();
+    pollset.add(&rx_cq, Ready::RxCq).unwrap();
+    pollset.add(&cntr, Ready::Cntr).unwrap();
+    assert_eq!(pollset.len(), 3);
+    assert!(pollset.add(&cntr, Ready::Cntr).is_err());
+    assert!(pollset.poll().unwrap().is_empty());
+
+    cntr.add(1).unwrap();
+    assert_eq!(pollset.poll().unwrap(), vec![Ready::Cntr]);
+
+    assert_eq!(pollset.del(&rx_cq).unwrap(), Ready::RxCq);
+    assert!(pollset.del(&rx_cq).is_err());
+    assert_eq!(pollset.len(), 2);
+
+    // The poll set keeps its members alive
+    cntr.add(1).unwrap();
+    drop(tx_cq);
+    drop(cntr);
+    assert_eq!(pollset.len(), 2);
+    assert_eq!(pollset.poll().unwrap(), vec![Ready::Cntr]);
+    drop(pollset);
+}
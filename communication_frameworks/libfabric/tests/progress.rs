@@ -0,0 +1,136 @@
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod async_loopback;
+
+use libfabric::{
+    cntr::CounterBuilder,
+    cq::CompletionQueueBuilder,
+    domain::DomainBuilder,
+    enums::EndpointType,
+    eq::EventQueueBuilder,
+    fabric::FabricBuilder,
+    info::Info,
+    infocapsoptions::InfoCaps,
+    progress::ProgressEngineBuilder,
+};
+
+#[test]
+fn drive_progress() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = info.into_iter().next().unwrap();
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let eq = EventQueueBuilder::new(&fabric).build().unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let cntr = CounterBuilder::new().build(&domain).unwrap();
+
+    let engine = ProgressEngineBuilder::new()
+        .add(&cq)
+        .add(&eq)
+        .add(&cntr)
+        .build();
+
+    // Nothing was posted, so there is nothing to dispatch
+    assert_eq!(engine.progress().unwrap(), 0);
+
+    #[cfg(feature = "thread-safe")]
+    {
+        let thread = engine.start().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert!(!thread.is_finished());
+        let engine = thread.stop().unwrap();
+
+        // The engine can be restarted once stopped
+        let thread = engine.start().unwrap();
+        drop(thread);
+    }
+
+    drop(cq); // The engine keeps its objects alive
+    drop(eq);
+    drop(cntr);
+}
+
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod progress_async {
+    use std::future::Future;
+    use std::task::Poll;
+
+    use crate::async_loopback::{noop_waker, query, Loopback};
+    use libfabric::async_::comm::message::{AsyncRecvEp, AsyncSendEp};
+    use libfabric::progress::ProgressEngineBuilder;
+
+    #[test]
+    fn dispatch_to_waiting_context() {
+        let info_entry = match query(&["tcp", "shm", "sockets"]) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let lb = Loopback::new(info_entry);
+        let engine = ProgressEngineBuilder::new().add(&lb.cq).build();
+
+        let waker = noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut recv_ctx = lb.info_entry.allocate_context();
+        let mut send_ctx = lb.info_entry.allocate_context();
+        let send_buf: Vec<u8> = (0..64).collect();
+        let mut recv_buf = vec![0u8; 64];
+        {
+            let recv = lb
+                .ep
+                .recv_from_any_async(&mut recv_buf, None, &mut recv_ctx);
+            let mut recv = std::pin::pin!(recv);
+            let send = lb
+                .ep
+                .send_to_async(&send_buf, None, &lb.self_addr, &mut send_ctx);
+            let mut send = std::pin::pin!(send);
+
+            // Post both operations, then leave the queue to the engine alone
+            let recv_ready = recv.as_mut().poll(&mut cx);
+            let send_ready = send.as_mut().poll(&mut cx);
+            let pending = [&recv_ready, &send_ready]
+                .iter()
+                .filter(|res| res.is_pending())
+                .count();
+
+            let start = std::time::Instant::now();
+            let mut dispatched = 0;
+            while dispatched < pending {
+                assert!(
+                    start.elapsed() < std::time::Duration::from_secs(10),
+                    "Operations did not complete"
+                );
+                dispatched += engine.progress().unwrap();
+            }
+
+            // The completions were handed over to the contexts, so the futures are done without reading the queue
+            let recv_res = match recv_ready {
+                Poll::Ready(res) => res,
+                Poll::Pending => match recv.as_mut().poll(&mut cx) {
+                    Poll::Ready(res) => res,
+                    Poll::Pending => panic!("Receive completion was not dispatched"),
+                },
+            };
+            let send_res = match send_ready {
+                Poll::Ready(res) => res,
+                Poll::Pending => match send.as_mut().poll(&mut cx) {
+                    Poll::Ready(res) => res,
+                    Poll::Pending => panic!("Send completion was not dispatched"),
+                },
+            };
+            recv_res.unwrap();
+            send_res.unwrap();
+        }
+        assert_eq!(recv_buf, send_buf);
+    }
+}
@@ -1,4 +1,13 @@
-use libfabric::{ep::EndpointBuilder, infocapsoptions::InfoCaps};
+use libfabric::{
+    av::AddressVectorBuilder,
+    domain::DomainBuilder,
+    enums::{EndpointType, HmemIface},
+    ep::EndpointBuilder,
+    fabric::FabricBuilder,
+    info::{Info, InfoEntry},
+    infocapsoptions::{InfoCaps, RmaDefaultCap},
+    mr::{DisabledMemoryRegion, MaybeDisabledMemoryRegion, MemoryRegionBuilder},
+};
 
 use crate::sync_::tests::{get_ip, Ofi, TestConfigBuilder};
 pub mod sync_;
@@ -38,3 +47,52 @@ fn enable_scalable_endpoint0() {
     sep.bind_av(&info.av.unwrap()).unwrap();
     sep.enable().unwrap();
 }
+
+// Providers that require memory regions to be bound to the (scalable) endpoint using them
+fn query_mr_endpoint() -> Option<InfoEntry<impl RmaDefaultCap>> {
+    Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg().rma())
+        .leave_hints()
+        .get()
+        .ok()?
+        .into_iter()
+        .find(|entry| {
+            entry.domain_attr().max_ep_tx_ctx() > 1 && entry.domain_attr().mr_mode().is_endpoint()
+        })
+}
+
+#[test]
+fn bind_mr_scalable_endpoint() {
+    let info_entry = match query_mr_endpoint() {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let sep = EndpointBuilder::new(&info_entry)
+        .build_scalable(&domain)
+        .unwrap();
+    sep.bind_av(&av).unwrap();
+
+    let buf = vec![0u8; 4096];
+    let mr = match MemoryRegionBuilder::new(&buf, HmemIface::System)
+        .access_remote_read()
+        .access_remote_write()
+        .build(&domain)
+        .unwrap()
+    {
+        MaybeDisabledMemoryRegion::Disabled(DisabledMemoryRegion::EpBind(mr)) => mr,
+        _ => panic!("Memory region should wait for an endpoint binding"),
+    };
+
+    // A region bound to the scalable endpoint is usable from all of its contexts
+    let mr = mr.enable_scalable(&sep).unwrap();
+    assert!(mr.key().is_ok());
+    sep.enable().unwrap();
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "serde")]
+pub mod serde_roundtrip {
+    use libfabric::{
+        av::AuthKey,
+        domain::DomainBuilder,
+        enums::HmemIface,
+        ep::Address,
+        fabric::FabricBuilder,
+        info::{Info, InfoEntry},
+        infocapsoptions::{InfoCaps, RmaDefaultCap},
+        mr::{MaybeDisabledMemoryRegion, MemoryRegionBuilder, UnmappedMemoryRegionKey},
+        MemAddressInfo,
+    };
+
+    // A non-raw key, encoded as documented by MemAddressInfo
+    fn encoded(key: u64, addr: u64, len: u64) -> Vec<u8> {
+        let mut bytes = b"OFMA".to_vec();
+        bytes.extend_from_slice(&[1, 0, 0, 0]);
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        bytes.extend_from_slice(&key.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+        bytes.extend_from_slice(&addr.to_le_bytes());
+        bytes.extend_from_slice(&len.to_le_bytes());
+        bytes
+    }
+
+    // Regions that do not need to be bound to an endpoint have a key as soon as they are registered
+    fn query() -> Option<InfoEntry<impl RmaDefaultCap>> {
+        Info::new(&libfabric::info::libfabric_version())
+            .enter_hints()
+            .caps(InfoCaps::new().rma())
+            .leave_hints()
+            .get()
+            .ok()?
+            .into_iter()
+            .find(|entry| !entry.domain_attr().mr_mode().is_endpoint())
+    }
+
+    #[test]
+    fn address_roundtrip() {
+        let addr = unsafe { Address::from_bytes(&[1, 2, 3, 4, 5, 6, 7, 8]) };
+        let json = serde_json::to_string(&addr).unwrap();
+        let decoded: Address = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.as_bytes(), addr.as_bytes());
+    }
+
+    #[test]
+    fn auth_key_roundtrip() {
+        let json = r#"{"auth_key":[0,1,2,3]}"#;
+        let key: AuthKey = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&key).unwrap(), json);
+    }
+
+    #[test]
+    fn mem_address_info_roundtrip() {
+        let bytes = encoded(0xdead_beef, 0x1000, 4096);
+        let info = MemAddressInfo::try_from_bytes(&bytes).unwrap();
+        let json = serde_json::to_string(&info).unwrap();
+        let decoded: MemAddressInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.to_bytes(), &bytes[..]);
+
+        // Invalid encodings are rejected while deserializing
+        let truncated = serde_json::to_string(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(serde_json::from_str::<MemAddressInfo>(&truncated).is_err());
+    }
+
+    #[test]
+    fn memory_region_key_roundtrip() {
+        let info_entry = match query() {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+        let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+        let buf = vec![0u8; 1024];
+        let mr = match MemoryRegionBuilder::new(&buf, HmemIface::System)
+            .access_remote_read()
+            .access_remote_write()
+            .build(&domain)
+            .unwrap()
+        {
+            MaybeDisabledMemoryRegion::Enabled(mr) => mr,
+            MaybeDisabledMemoryRegion::Disabled(_) => panic!("Unexpected disabled memory region"),
+        };
+
+        let key = mr.key().unwrap();
+        let json = serde_json::to_string(&key).unwrap();
+        let decoded: UnmappedMemoryRegionKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.as_bytes(), &key.to_bytes()[..]);
+        assert_eq!(decoded, UnmappedMemoryRegionKey::from(mr.key().unwrap()));
+
+        // The peer maps the key in its own domain before accessing the region
+        decoded.into_mapped(&domain).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+pub mod sync_;
+#[cfg(test)]
+pub mod sync_software_collective {
+    use libfabric::{
+        av_set::{AddressVectorSet, AddressVectorSetBuilder},
+        comm::software_collective::{Pod, SoftwareCollective},
+        enums::{CollectiveOptions, ReduceOp},
+        infocapsoptions::{InfoCaps, TagDefaultCap},
+    };
+
+    use crate::sync_::tests::{handshake_connectionless, MyEndpoint, Ofi};
+
+    // Both peers list the server first, so that ranks are consistent across them
+    fn group(server: bool, name: &str) -> (Ofi<impl TagDefaultCap>, AddressVectorSet) {
+        let ofi =
+            handshake_connectionless(None, server, name, Some(InfoCaps::new().msg().tagged()));
+
+        let mut avset = AddressVectorSetBuilder::new(ofi.av.as_ref().unwrap())
+            .count(2)
+            .build()
+            .unwrap();
+        let addrs = ofi.mapped_addr.as_ref().unwrap();
+        let order = if server { [0, 1] } else { [1, 0] };
+        for i in order {
+            avset.insert(&addrs[i]).unwrap();
+        }
+
+        (ofi, avset)
+    }
+
+    fn collectives(server: bool, name: &str) {
+        let (ofi, avset) = group(server, name);
+        let ep = match &ofi.ep {
+            MyEndpoint::Connectionless(ep) => ep,
+            MyEndpoint::Connected(_) => unreachable!(),
+        };
+        let coll = SoftwareCollective::new(
+            ep,
+            ofi.cq_type.tx_cq(),
+            ofi.cq_type.rx_cq(),
+            &avset,
+            &ofi.mapped_addr.as_ref().unwrap()[0],
+        )
+        .unwrap();
+        assert_eq!(coll.size(), 2);
+        assert_eq!(coll.rank(), if server { 0 } else { 1 });
+
+        coll.barrier().unwrap();
+
+        let rank = coll.rank() as u64;
+        let mut result = [0u64; 4];
+        coll.allreduce(
+            &[rank + 1; 4],
+            None,
+            &mut result,
+            None,
+            ReduceOp::Sum,
+            CollectiveOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(result, [3; 4]);
+
+        let mut result = [0u64; 4];
+        coll.allgather(
+            &[rank; 2],
+            None,
+            &mut result,
+            None,
+            CollectiveOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(result, [0, 0, 1, 1]);
+
+        let mut result = [0u64; 2];
+        coll.alltoall(
+            &[rank * 10, rank * 10 + 1],
+            None,
+            &mut result,
+            None,
+            CollectiveOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(result, [coll.rank() as u64, 10 + coll.rank() as u64]);
+
+        // Minimum with the rank that holds it
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct MinLoc {
+            value: f64,
+            rank: u64,
+        }
+        // Two 8-byte fields: no padding, and any bit pattern is a valid value
+        unsafe impl Pod for MinLoc {}
+        let mut result = [MinLoc { value: 0., rank: 0 }; 2];
+        coll.allreduce_with(
+            &[
+                MinLoc {
+                    value: rank as f64,
+                    rank,
+                },
+                MinLoc {
+                    value: -(rank as f64),
+                    rank,
+                },
+            ],
+            None,
+            &mut result,
+            None,
+            &|acc: &mut MinLoc, other: &MinLoc| {
+                if other.value < acc.value {
+                    *acc = *other;
+                }
+            },
+            CollectiveOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            [
+                MinLoc { value: 0., rank: 0 },
+                MinLoc {
+                    value: -1.,
+                    rank: 1
+                }
+            ]
+        );
+
+        // Collectives over empty buffers complete without exchanging any message
+        coll.alltoall::<u64>(&[], None, &mut [], None, CollectiveOptions::new())
+            .unwrap();
+        coll.allgather::<u64>(&[], None, &mut [], None, CollectiveOptions::new())
+            .unwrap();
+        coll.allreduce::<u64>(
+            &[],
+            None,
+            &mut [],
+            None,
+            ReduceOp::Sum,
+            CollectiveOptions::new(),
+        )
+        .unwrap();
+
+        let root = &avset.members()[0];
+        let mut buf = [rank as u8 + 7; 16];
+        coll.broadcast(&mut buf, None, root, CollectiveOptions::new())
+            .unwrap();
+        assert_eq!(buf, [7; 16]);
+
+        let mut result = [0i32; 2];
+        coll.reduce(
+            &[rank as i32, -(rank as i32)],
+            None,
+            &mut result,
+            None,
+            root,
+            ReduceOp::Max,
+            CollectiveOptions::new(),
+        )
+        .unwrap();
+        if server {
+            assert_eq!(result, [1, 0]);
+        }
+
+        coll.barrier().unwrap();
+    }
+
+    #[test]
+    fn software_collective0() {
+        collectives(true, "software_collective0");
+    }
+
+    #[test]
+    fn software_collective1() {
+        collectives(false, "software_collective0");
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+pub mod async_loopback;
+
+use libfabric::{
+    av::{AddressVectorBuilder, AvInAddress},
+    comm::message::{RecvEp, SendEp},
+    cq::{CompletionQueueBuilder, ReadCq},
+    domain::DomainBuilder,
+    enums::{AVOptions, EndpointType},
+    ep::{BaseEndpoint, Endpoint, EndpointBuilder},
+    error::ErrorKind,
+    fabric::FabricBuilder,
+    info::{Info, InfoEntry},
+    infocapsoptions::{InfoCaps, MsgDefaultCap},
+    xcontext::SharedRxContextBuilder,
+};
+
+// Providers that support shared receive contexts without requiring local memory registration
+fn query() -> Option<InfoEntry<impl MsgDefaultCap>> {
+    Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .shared_rx_ctx()
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .ok()?
+        .into_iter()
+        .find(|entry| {
+            entry.domain_attr().max_ep_srx_ctx() > 0 && !entry.domain_attr().mr_mode().is_local()
+        })
+}
+
+#[test]
+fn bind_shared_rx_context() {
+    let info_entry = match query() {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let srx = match SharedRxContextBuilder::new(&info_entry).build(&domain) {
+        Ok(srx) => srx,
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::NotSupported | ErrorKind::NotImplemented
+            ) =>
+        {
+            return
+        }
+        Err(err) => panic!("{:?}", err),
+    };
+
+    let eps: Vec<_> = (0..2)
+        .map(|_| {
+            let ep = match EndpointBuilder::new(&info_entry)
+                .build_with_shared_cq(&domain, &cq, false)
+                .unwrap()
+            {
+                Endpoint::Connectionless(ep) => ep,
+                Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+            };
+            ep.bind_srx(&srx).unwrap();
+            assert!(matches!(
+                ep.bind_srx(&srx).unwrap_err().kind,
+                ErrorKind::AlreadyInProgress
+            ));
+            ep.enable(&av).unwrap()
+        })
+        .collect();
+
+    // Completions of the shared context can only be reported to a single queue
+    let other_cq = CompletionQueueBuilder::new().build(&domain).unwrap();
+    match EndpointBuilder::new(&info_entry)
+        .build_with_shared_cq(&domain, &other_cq, false)
+        .unwrap()
+    {
+        Endpoint::Connectionless(ep) => assert!(matches!(
+            ep.bind_srx(&srx).unwrap_err().kind,
+            ErrorKind::InvalidArgument
+        )),
+        Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+    }
+
+    let dest = av
+        .insert(
+            AvInAddress::Encoded(&[eps[1].getname().unwrap()]),
+            AVOptions::new(),
+        )
+        .unwrap()
+        .pop()
+        .unwrap()
+        .unwrap();
+
+    // The message sent to the second endpoint lands in the buffer posted to the shared context
+    let mut recv_buf = vec![0u8; 256];
+    srx.recv_from_any(&mut recv_buf, None).unwrap();
+    let send_buf: Vec<u8> = (0..256).map(|v| v as u8).collect();
+    loop {
+        match eps[0].send_to(&send_buf, None, &dest) {
+            Ok(()) => break,
+            Err(err) if matches!(err.kind, ErrorKind::TryAgain) => {
+                let _ = cq.read(0);
+            }
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+
+    let mut completed = 0;
+    while completed < 2 {
+        match cq.read(1) {
+            Ok(_) => completed += 1,
+            Err(err) if matches!(err.kind, ErrorKind::TryAgain) => {}
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+    assert_eq!(recv_buf, send_buf);
+
+    drop(srx); // The endpoints keep the shared context alive
+    drop(eps);
+}
+
+#[cfg(any(feature = "use-async-std", feature = "use-tokio"))]
+#[test]
+fn async_recv_on_unbound_shared_rx_context() {
+    use libfabric::async_::comm::message::AsyncRecvEp;
+
+    let info_entry = match query() {
+        Some(entry) => entry,
+        None => return,
+    };
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let srx = match libfabric::async_::xcontext::SharedRxContextBuilder::new(&info_entry)
+        .build(&domain)
+    {
+        Ok(srx) => srx,
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::NotSupported | ErrorKind::NotImplemented
+            ) =>
+        {
+            return
+        }
+        Err(err) => panic!("{:?}", err),
+    };
+
+    // No endpoint is bound to the shared context yet, so there is no queue to report the completion to
+    let mut buf = vec![0u8; 64];
+    let mut ctx = info_entry.allocate_context();
+    match async_loopback::block_on(srx.recv_from_any_async(&mut buf, None, &mut ctx)) {
+        Err(err) => assert!(matches!(err.kind, ErrorKind::NoCompletionQueue)),
+        Ok(_) => panic!("Received on a shared context without a completion queue"),
+    }
+}
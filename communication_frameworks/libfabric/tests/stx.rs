@@ -0,0 +1,63 @@
+use libfabric::{
+    av::AddressVectorBuilder,
+    cq::CompletionQueueBuilder,
+    domain::DomainBuilder,
+    enums::EndpointType,
+    ep::{Endpoint, EndpointBuilder},
+    error::ErrorKind,
+    fabric::FabricBuilder,
+    info::Info,
+    infocapsoptions::InfoCaps,
+    xcontext::SharedTxContextBuilder,
+};
+
+#[test]
+fn bind_shared_tx_context() {
+    let info = Info::new(&libfabric::info::libfabric_version())
+        .enter_hints()
+        .enter_ep_attr()
+        .type_(EndpointType::Rdm)
+        .shared_tx_ctx()
+        .leave_ep_attr()
+        .caps(InfoCaps::new().msg())
+        .leave_hints()
+        .get()
+        .unwrap();
+    let info_entry = info
+        .into_iter()
+        .find(|entry| entry.domain_attr().max_ep_stx_ctx() > 0)
+        .unwrap();
+
+    let fabric = FabricBuilder::new().build(&info_entry).unwrap();
+    let domain = DomainBuilder::new(&fabric, &info_entry).build().unwrap();
+    let av = AddressVectorBuilder::new().build(&domain).unwrap();
+    let cq = CompletionQueueBuilder::new()
+        .size(info_entry.rx_attr().size() + info_entry.tx_attr().size())
+        .build(&domain)
+        .unwrap();
+    let stx = SharedTxContextBuilder::new(&info_entry)
+        .attr(info_entry.tx_attr().clone())
+        .build(&domain)
+        .unwrap();
+
+    let eps: Vec<_> = (0..4)
+        .map(|_| {
+            let ep = match EndpointBuilder::new(&info_entry)
+                .build_with_shared_cq(&domain, &cq, false)
+                .unwrap()
+            {
+                Endpoint::Connectionless(ep) => ep,
+                Endpoint::ConnectionOriented(_) => panic!("Unexpected endpoint type"),
+            };
+            ep.bind_stx(&stx).unwrap();
+            assert!(matches!(
+                ep.bind_stx(&stx).unwrap_err().kind,
+                ErrorKind::AlreadyInProgress
+            ));
+            ep.enable(&av).unwrap()
+        })
+        .collect();
+
+    drop(stx); // The endpoints keep the shared context alive
+    drop(eps);
+}
@@ -373,7 +373,7 @@ impl<I: MsgDefaultCap + Caps + 'static> Ofi<I> {
                                 pending
                                     .av_complete(av_complete)
                                     .into_iter()
-                                    .map(MyRc::new)
+                                    .map(|addr| MyRc::new(addr.unwrap()))
                                     .collect()
                             } else {
                                 panic!("Unexpected event retrieved");
@@ -468,7 +468,7 @@ impl<I: MsgDefaultCap + Caps + 'static> Ofi<I> {
                                 pending
                                     .av_complete(av_complete)
                                     .into_iter()
-                                    .map(MyRc::new)
+                                    .map(|addr| MyRc::new(addr.unwrap()))
                                     .collect()
                             } else {
                                 panic!("Unexpected event retrieved");
@@ -1718,7 +1718,7 @@ impl<I: MsgDefaultCap + 'static> Ofi<I> {
         
         // self.cq_type.rx_cq().sread(1, -1).unwrap();
         self.wait_rx(1);
-        let mem_info = unsafe { MemAddressInfo::from_bytes(&self.reg_mem.borrow()[len..2*len]) };
+        let mem_info = MemAddressInfo::try_from_bytes(&self.reg_mem.borrow()[len..2*len]).unwrap();
         let remote_mem_info = mem_info.into_remote_info(&self.domain).unwrap();
         println!("Received : {:?}", &self.reg_mem.borrow()[len..2*len]);
         println!("Remote addr: {:?}, size: {}", remote_mem_info.mem_address().as_ptr(), remote_mem_info.mem_len());